@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::io::{self, stdout};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, Local};
@@ -15,22 +17,31 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Terminal,
 };
+#[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
 const SPINNER_RATE_MS: u128 = 50;
+/// Quiet period after the last filesystem event before the tree is reloaded
+const FS_DEBOUNCE: Duration = Duration::from_millis(300);
+/// How long an expand waits for its background read before showing the spinner
+const CHILDREN_INLINE_WAIT: Duration = Duration::from_millis(30);
+const TEMP_STATUS_SECS: u64 = 3;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 fn main() -> io::Result<()> {
-    if let Some(arg) = env::args().nth(1) {
+    let mut config = Config::from_env();
+    let mut root = None;
+    for arg in env::args().skip(1) {
         match arg.as_str() {
             "--version" | "-V" => {
                 println!("texplore {}", VERSION);
@@ -42,53 +53,124 @@ fn main() -> io::Result<()> {
                     VERSION
                 );
                 println!();
-                println!("Usage: texplore [PATH]");
+                println!("Usage: texplore [OPTIONS] [PATH]");
                 println!();
                 println!("Arguments:");
                 println!("  [PATH]  Directory to explore (default: current directory)");
                 println!();
                 println!("Options:");
-                println!("  -h, --help     Print help");
-                println!("  -V, --version  Print version");
+                println!(
+                    "      --ascii-tree   Draw the tree with |, + and \\ (TEXPLORE_ASCII_TREE=1)"
+                );
+                println!("      --curved-tree  Draw the tree with rounded corners (TEXPLORE_CURVED_TREE=1)");
+                println!("      --ascii-icons  Use ASCII markers instead of Nerd Font icons (TEXPLORE_ASCII=1)");
+                println!("      --dirs-only    Pick a directory: only directories are listed, Enter prints");
+                println!("                     its absolute path and exits (cd \"$(texplore --dirs-only)\").");
+                println!(
+                    "                     Drawn on stderr; keys that view or edit files are off"
+                );
+                println!("      --print-on-quit");
+                println!("                     Print the focused directory (a file's parent) to stdout on q,");
+                println!("                     for cd-on-quit: cd \"$(texplore --print-on-quit)\"");
+                println!(
+                    "      --print-keys   Print the default keys.toml (every remappable action)"
+                );
+                println!("  -h, --help         Print help");
+                println!("  -V, --version      Print version");
+                println!();
+                println!("Environment:");
+                println!("  TEXPLORE_REFRESH_SECS     How often git status is re-read (the tree itself is watched)");
+                println!("                            (default 30, invalid values use 30, 0 disables auto-refresh)");
+                println!(
+                    "  TEXPLORE_FOLLOW_SYMLINKS  Expand symlinks to directories (toggle with L)"
+                );
+                println!("  LS_COLORS                 Name colors for file types and extensions (as with ls)");
+                println!();
+                println!("Files:");
+                println!("  ~/.config/texplore/keys.toml  Key bindings (see --print-keys)");
                 return Ok(());
             }
-            _ => {}
+            "--ascii-tree" => config.tree_chars = TreeChars::ASCII,
+            "--curved-tree" => config.tree_chars = TreeChars::CURVED,
+            "--ascii-icons" => config.ascii_icons = true,
+            "--dirs-only" => config.dirs_only = true,
+            "--print-on-quit" => config.print_on_quit = true,
+            "--print-keys" => {
+                print_default_keys();
+                return Ok(());
+            }
+            _ => {
+                if root.is_none() {
+                    root = Some(PathBuf::from(arg));
+                }
+            }
         }
     }
 
-    let root = match env::args().nth(1) {
-        Some(arg) => PathBuf::from(arg),
-        None => env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
-    };
+    let root = root.unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
     let root_abs = fs::canonicalize(&root).unwrap_or_else(|_| root.clone());
-    let git_status = load_git_status(&root_abs);
-    let gitignore = build_gitignore(&root_abs);
-    let mut root_node = build_node(&root_abs, &gitignore, &git_status)?;
-    if root_node.is_dir {
-        root_node.expanded = true;
-        load_children(&mut root_node, &gitignore, &git_status)?;
-    }
-    expand_changed_paths(&mut root_node, &root_abs, &gitignore, &git_status)?;
-
+    let refresh_interval = config.refresh_interval;
+    let ls_colors = LsColors::from_env().map(Arc::new);
+    let app = load_app(root_abs, config, KeyMap::load(), ls_colors)?;
+
+    let dirs_only = config.dirs_only;
+    let print_on_quit = config.print_on_quit;
+    UI_ON_STDERR.store(dirs_only || print_on_quit, Ordering::Relaxed);
+    let mut output = ui_output();
     enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    execute!(output, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(output);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(root_node, gitignore, git_status, root_abs);
+    // One pane normally, two in split view (Ctrl+T); `active` receives keys
+    let mut panes = vec![app];
+    let mut active = 0;
     let mut last_refresh = Instant::now();
     let mut has_focus = true;
 
+    // Set when we come back from Ctrl+Z (or any other stop) via `fg`
+    let resumed = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    signal_hook::flag::register(signal_hook::consts::SIGCONT, Arc::clone(&resumed))?;
+
     loop {
-        app.refresh_visible();
-        terminal.draw(|frame| render(frame, &mut app))?;
+        if resumed.swap(false, Ordering::Relaxed) {
+            enable_raw_mode()?;
+            execute!(
+                terminal.backend_mut(),
+                EnterAlternateScreen,
+                EnableMouseCapture
+            )?;
+            for app in panes.iter_mut() {
+                resync(app)?;
+                app.force_redraw = true;
+            }
+            last_refresh = Instant::now();
+        }
+
+        let split = panes.len() > 1;
+        let mut force_redraw = false;
+        for (idx, app) in panes.iter_mut().enumerate() {
+            app.refresh_visible();
+            app.split_active = split.then_some(idx == active);
+            force_redraw |= std::mem::take(&mut app.force_redraw);
+        }
+        if force_redraw {
+            terminal.clear()?;
+        }
+        terminal.draw(|frame| {
+            let areas = pane_areas(frame.area(), panes.len());
+            for (app, area) in panes.iter_mut().zip(areas.iter()) {
+                render(frame, app, *area);
+            }
+        })?;
 
         if event::poll(Duration::from_millis(SPINNER_RATE_MS as u64))? {
             match event::read()? {
                 Event::Key(key) => {
-                    if handle_key(&mut app, key)? {
+                    let quit = handle_pane_key(&mut panes, &mut active, key)?;
+                    if quit {
                         break;
                     }
                 }
@@ -97,22 +179,59 @@ fn main() -> io::Result<()> {
                 }
                 Event::FocusGained => {
                     has_focus = true;
-                    app.refreshing = true;
-                    resync(&mut app)?;
-                    app.refreshing = false;
+                    for app in panes.iter_mut() {
+                        app.refreshing = true;
+                        resync(app)?;
+                        app.refreshing = false;
+                    }
                     last_refresh = Instant::now();
                 }
-                Event::Mouse(mouse) if has_focus => {
-                    handle_mouse(&mut app, mouse)?;
+                Event::Mouse(mut mouse) if has_focus => {
+                    // Route the click to the pane under the cursor
+                    if let Some(idx) = panes.iter().position(|app| {
+                        mouse.column >= app.area.x && mouse.column < app.area.x + app.area.width
+                    }) {
+                        if matches!(mouse.kind, MouseEventKind::Down(_)) {
+                            active = idx;
+                        }
+                        mouse.column -= panes[idx].area.x;
+                        handle_mouse(&mut panes[idx], mouse)?;
+                    }
                 }
                 _ => {}
             }
         }
 
-        if last_refresh.elapsed() >= Duration::from_secs(30) {
-            app.refreshing = true;
-            resync(&mut app)?;
-            app.refreshing = false;
+        for app in panes.iter_mut() {
+            if let Some((until, previous)) = app.status_restore.take() {
+                if Instant::now() >= until {
+                    app.status = previous;
+                } else {
+                    app.status_restore = Some((until, previous));
+                }
+            }
+        }
+
+        for app in panes.iter_mut() {
+            receive_children(app, Duration::ZERO);
+        }
+
+        for app in panes.iter_mut() {
+            if app.fs_changed() {
+                app.refreshing = true;
+                hard_resync(app)?;
+                app.refreshing = false;
+            }
+        }
+
+        // Poll git status in every pane: watchers ignore .git, so a `git add`,
+        // commit or stash from another terminal only shows up here
+        if refresh_interval.is_some_and(|interval| last_refresh.elapsed() >= interval) {
+            for app in panes.iter_mut() {
+                app.refreshing = true;
+                refresh_git_status_only(app);
+                app.refreshing = false;
+            }
             last_refresh = Instant::now();
         }
     }
@@ -124,9 +243,621 @@ fn main() -> io::Result<()> {
         DisableMouseCapture
     )?;
 
+    // Only now that the alternate screen is gone, or the path would vanish with it
+    if let Some(path) = panes.iter().find_map(|app| app.picked.as_ref()) {
+        println!("{}", path.display());
+    } else if print_on_quit {
+        println!("{}", focused_dir(&panes[active]).display());
+    } else if dirs_only {
+        // Quitting without a pick fails, so `dir=$(texplore --dirs-only) && cd "$dir"` stays put
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Set by --dirs-only and --print-on-quit: stdout carries the result, so the
+/// UI (and programs run from it) use stderr
+static UI_ON_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Where the TUI and terminal mode switches are written
+fn ui_output() -> Box<dyn io::Write> {
+    if UI_ON_STDERR.load(Ordering::Relaxed) {
+        Box::new(io::stderr())
+    } else {
+        Box::new(stdout())
+    }
+}
+
+/// Build the tree for a root directory and the app state around it
+fn load_app(
+    root_abs: PathBuf,
+    config: Config,
+    keymap: KeyMap,
+    ls_colors: Option<Arc<LsColors>>,
+) -> io::Result<App> {
+    let case_sensitive = filesystem_case_sensitive(&root_abs);
+    let sort = SortOrder {
+        mode: config.sort_mode,
+        reversed: config.sort_reversed,
+        case_sensitive,
+        follow_symlinks: config.follow_symlinks,
+        dirs_only: config.dirs_only,
+    };
+    let git_status = load_git_status(&root_abs);
+    let gitignore = build_gitignore(&root_abs);
+    let inherited = ancestor_gitignores(&root_abs, &gitignore);
+    let mut root_node = build_node(&root_abs, &gitignore, &inherited, &git_status)?;
+    if root_node.is_dir {
+        root_node.expanded = true;
+        load_children(&mut root_node, &gitignore, &git_status, sort)?;
+    }
+    expand_changed_paths(&mut root_node, &root_abs, &gitignore, &git_status, sort)?;
+
+    // With auto-refresh disabled only focus changes and R reload the tree
+    let fs_watch = config
+        .refresh_interval
+        .and_then(|_| FsWatch::new(&root_abs));
+    let mut app = App::new(root_node, gitignore, git_status, root_abs, config, keymap);
+    app.filesystem_case_sensitive = case_sensitive;
+    app.fs_watch = fs_watch;
+    app.ls_colors = ls_colors;
+    Ok(app)
+}
+
+/// Recursive watch on the root; events are debounced by `App::fs_changed`
+struct FsWatch {
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<()>,
+    /// When the most recent unhandled event arrived
+    pending: Option<Instant>,
+}
+
+impl FsWatch {
+    fn new(root: &Path) -> Option<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                if is_tree_change(&event) {
+                    let _ = tx.send(());
+                }
+            }
+        })
+        .ok()?;
+        watcher.watch(root, RecursiveMode::Recursive).ok()?;
+        Some(Self {
+            _watcher: watcher,
+            events,
+            pending: None,
+        })
+    }
+}
+
+/// Skip reads (our own resyncs open every directory) and churn inside .git,
+/// which `git status` itself causes by refreshing the index (the main loop's
+/// periodic `refresh_git_status_only` picks up index and HEAD changes instead)
+fn is_tree_change(event: &notify::Event) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+    !event
+        .paths
+        .iter()
+        .all(|path| path.components().any(|c| c.as_os_str() == ".git"))
+}
+
+/// Split the screen into side-by-side panes
+fn pane_areas(area: Rect, count: usize) -> Vec<Rect> {
+    let count = count.max(1);
+    Layout::horizontal(vec![Constraint::Ratio(1, count as u32); count])
+        .split(area)
+        .to_vec()
+}
+
+/// Handle split-view keys (Ctrl+T, Tab, m), passing everything else to the active pane
+fn handle_pane_key(panes: &mut Vec<App>, active: &mut usize, key: KeyEvent) -> io::Result<bool> {
+    let app = &panes[*active];
+    let modal = app.viewer.is_some()
+        || app.editing_filter
+        || app.editing_name_filter
+        || app.prompt.is_some()
+        || app.palette.is_some()
+        || app.pending_delete.is_some()
+        || app.pending_copy.is_some();
+
+    match key.code {
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) && !modal => {
+            if panes.len() > 1 {
+                panes.truncate(1);
+                *active = 0;
+                panes[0].status = String::from("split view closed");
+            } else {
+                let mut second = load_app(
+                    panes[0].root_path.clone(),
+                    panes[0].config,
+                    panes[0].keymap.clone(),
+                    panes[0].ls_colors.clone(),
+                )?;
+                second.status =
+                    String::from("split view: Tab switch pane  m move to other pane  Ctrl+T close");
+                panes.push(second);
+                *active = 1;
+            }
+        }
+        KeyCode::Tab if panes.len() > 1 && !modal => *active = 1 - *active,
+        KeyCode::Char('m') if panes.len() > 1 && !modal => move_to_other_pane(panes, *active)?,
+        _ => return handle_key(&mut panes[*active], key),
+    }
+
+    Ok(false)
+}
+
+/// Move the focused entry of the active pane into the other pane's focused directory
+fn move_to_other_pane(panes: &mut [App], active: usize) -> io::Result<()> {
+    let (left, right) = panes.split_at_mut(1);
+    let (from, to) = if active == 0 {
+        (&mut left[0], &mut right[0])
+    } else {
+        (&mut right[0], &mut left[0])
+    };
+
+    let entry = match from.visible.get(from.focus) {
+        Some(entry) if !entry.indices.is_empty() => entry.clone(),
+        Some(_) => {
+            from.status = String::from("cannot move the root");
+            return Ok(());
+        }
+        None => return Ok(()),
+    };
+    let dest_dir = focused_dir(to);
+    let name = match entry.path.file_name() {
+        Some(name) => name.to_owned(),
+        None => return Ok(()),
+    };
+    let dest = dest_dir.join(&name);
+
+    if dest_dir.starts_with(&entry.path) {
+        from.status = String::from("cannot move a directory into itself");
+        return Ok(());
+    }
+    if dest.exists() {
+        from.status = format!("already exists: {}", dest.display());
+        return Ok(());
+    }
+
+    match fs::rename(&entry.path, &dest) {
+        Ok(()) => {
+            resync(from)?;
+            resync(to)?;
+            reveal_path(to, &dest);
+            from.status = format!("moved {} → {}", entry.name, dest_dir.display());
+        }
+        Err(err) => from.status = format!("move failed: {}", err),
+    }
     Ok(())
 }
 
+/// Display settings from command-line flags and the environment
+#[derive(Clone, Copy)]
+struct Config {
+    tree_chars: TreeChars,
+    /// Ctrl+R: list entries Z→A (kept across resyncs, reset on restart)
+    sort_reversed: bool,
+    /// s: what entries are ordered by (kept across resyncs, reset on restart)
+    sort_mode: SortMode,
+    /// .: list gitignored entries (dimmed) or leave them out entirely
+    show_ignored: bool,
+    /// c: list only entries with git changes (and the directories holding them)
+    changes_only: bool,
+    /// L / TEXPLORE_FOLLOW_SYMLINKS: expand symlinks to directories
+    follow_symlinks: bool,
+    /// TEXPLORE_REFRESH_SECS: None disables automatic refreshes (0)
+    refresh_interval: Option<Duration>,
+    /// Plain ASCII markers instead of Nerd Font icons
+    ascii_icons: bool,
+    /// --dirs-only: list directories only and pick one with Enter
+    dirs_only: bool,
+    /// --print-on-quit: print the focused directory on q (cd-on-quit)
+    print_on_quit: bool,
+}
+
+impl Config {
+    fn from_env() -> Self {
+        let enabled = |name: &str| env::var(name).is_ok_and(|v| !v.is_empty() && v != "0");
+        let tree_chars = if enabled("TEXPLORE_ASCII_TREE") {
+            TreeChars::ASCII
+        } else if enabled("TEXPLORE_CURVED_TREE") {
+            TreeChars::CURVED
+        } else {
+            TreeChars::UNICODE
+        };
+        Self {
+            tree_chars,
+            sort_reversed: false,
+            sort_mode: SortMode::Name,
+            show_ignored: true,
+            changes_only: false,
+            follow_symlinks: enabled("TEXPLORE_FOLLOW_SYMLINKS"),
+            refresh_interval: refresh_interval_from_env(),
+            ascii_icons: enabled("TEXPLORE_ASCII"),
+            dirs_only: false,
+            print_on_quit: false,
+        }
+    }
+}
+
+/// TEXPLORE_REFRESH_SECS, defaulting to 30 when unset or not a number
+fn refresh_interval_from_env() -> Option<Duration> {
+    let secs = env::var("TEXPLORE_REFRESH_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(30);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Tree-view commands that can be rebound in keys.toml
+#[derive(Clone, Copy, PartialEq)]
+enum Action {
+    Quit,
+    MoveDown,
+    MoveUp,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    Collapse,
+    Expand,
+    CollapseLevel,
+    ExpandAll,
+    CollapseAll,
+    FocusParent,
+    NextSibling,
+    PrevSibling,
+    Open,
+    ToggleOrOpen,
+    Diff,
+    Blame,
+    Nvim,
+    Edit,
+    NewFile,
+    AddFile,
+    AddDir,
+    Rename,
+    Delete,
+    CopyPath,
+    CopyAbsPath,
+    NameFilter,
+    CycleSort,
+    ToggleIgnored,
+    ToggleChanges,
+    ToggleSymlinks,
+    Reload,
+}
+
+/// Every remappable action: (action, name in keys.toml, default key, description)
+const ACTIONS: &[(Action, &str, &str, &str)] = &[
+    (Action::Quit, "quit", "q", "Quit texplore"),
+    (
+        Action::MoveDown,
+        "move_down",
+        "j",
+        "Move down (Down always works)",
+    ),
+    (Action::MoveUp, "move_up", "k", "Move up (Up always works)"),
+    (
+        Action::PageDown,
+        "page_down",
+        "pagedown",
+        "Move down a page",
+    ),
+    (Action::PageUp, "page_up", "pageup", "Move up a page"),
+    (Action::Top, "top", "g", "Focus the root"),
+    (Action::Bottom, "bottom", "G", "Focus the last entry"),
+    (
+        Action::Collapse,
+        "collapse",
+        "h",
+        "Collapse, or move to the parent (Left always works)",
+    ),
+    (
+        Action::Expand,
+        "expand",
+        "l",
+        "Expand a directory (Right always works)",
+    ),
+    (
+        Action::CollapseLevel,
+        "collapse_level",
+        "_",
+        "Collapse every directory at the focused depth",
+    ),
+    (
+        Action::ExpandAll,
+        "expand_all",
+        "E",
+        "Expand everything below the focused directory",
+    ),
+    (
+        Action::CollapseAll,
+        "collapse_all",
+        "Z",
+        "Collapse the whole tree",
+    ),
+    (
+        Action::FocusParent,
+        "focus_parent",
+        "p",
+        "Focus the parent directory",
+    ),
+    (
+        Action::NextSibling,
+        "next_sibling",
+        "]",
+        "Focus the next sibling",
+    ),
+    (
+        Action::PrevSibling,
+        "prev_sibling",
+        "[",
+        "Focus the previous sibling",
+    ),
+    (Action::Open, "open", "o", "View the file"),
+    (
+        Action::ToggleOrOpen,
+        "toggle_or_open",
+        "enter",
+        "Expand/collapse a directory or view a file",
+    ),
+    (Action::Diff, "diff", "D", "Show the file's git diff"),
+    (Action::Blame, "blame", "b", "Show git blame"),
+    (Action::Nvim, "nvim", "N", "Open in an nvim popup"),
+    (Action::Edit, "edit", "e", "Open in $EDITOR"),
+    (Action::NewFile, "new_file", "n", "Create a file"),
+    (
+        Action::AddFile,
+        "add_file",
+        "a",
+        "Add a file in the focused directory",
+    ),
+    (
+        Action::AddDir,
+        "add_dir",
+        "A",
+        "Add a directory in the focused directory",
+    ),
+    (Action::Rename, "rename", "r", "Rename the focused entry"),
+    (
+        Action::Delete,
+        "delete",
+        "d",
+        "Delete (asks for y to confirm)",
+    ),
+    (Action::CopyPath, "copy_path", "C", "Copy the relative path"),
+    (
+        Action::CopyAbsPath,
+        "copy_abs_path",
+        "Y",
+        "Copy the absolute path",
+    ),
+    (
+        Action::NameFilter,
+        "name_filter",
+        "/",
+        "Filter entries by name",
+    ),
+    (Action::CycleSort, "cycle_sort", "s", "Cycle the sort mode"),
+    (
+        Action::ToggleIgnored,
+        "toggle_ignored",
+        ".",
+        "Show or hide gitignored entries",
+    ),
+    (
+        Action::ToggleChanges,
+        "toggle_changes",
+        "c",
+        "List only entries with git changes",
+    ),
+    (
+        Action::ToggleSymlinks,
+        "toggle_symlinks",
+        "L",
+        "Expand directory symlinks",
+    ),
+    (Action::Reload, "reload", "f5", "Reload the tree from disk"),
+];
+
+/// Plain keys for the tree view, from the defaults above overridden by keys.toml
+#[derive(Clone)]
+struct KeyMap {
+    keys: HashMap<KeyCode, Action>,
+}
+
+impl KeyMap {
+    fn defaults() -> Self {
+        let keys = ACTIONS
+            .iter()
+            .filter_map(|(action, _, key, _)| parse_key(key).map(|code| (code, *action)))
+            .collect();
+        Self { keys }
+    }
+
+    /// Read keys.toml, falling back to the defaults (with a warning) when it's malformed
+    fn load() -> Self {
+        let path = match keys_file() {
+            Some(path) => path,
+            None => return Self::defaults(),
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Self::defaults(),
+            Err(err) => {
+                eprintln!(
+                    "texplore: can't read {}: {}; using default keys",
+                    path.display(),
+                    err
+                );
+                return Self::defaults();
+            }
+        };
+        match Self::from_toml(&text) {
+            Ok(keymap) => keymap,
+            Err(err) => {
+                eprintln!("texplore: {}: {}; using default keys", path.display(), err);
+                Self::defaults()
+            }
+        }
+    }
+
+    /// A rebound action loses its default key, and steals its new key from whichever
+    /// default action had it
+    fn from_toml(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|err: toml::de::Error| err.message().to_string())?;
+        let mut keymap = Self::defaults();
+        let mut rebound = Vec::new();
+        for (name, value) in &table {
+            let action = match ACTIONS.iter().find(|(_, n, _, _)| n == name) {
+                Some((action, _, _, _)) => *action,
+                None => return Err(format!("unknown action `{}`", name)),
+            };
+            let code = match value.as_str().and_then(parse_key) {
+                Some(code) => code,
+                None => return Err(format!("`{}` is not a key ({})", value, name)),
+            };
+            if rebound.contains(&code) {
+                return Err(format!("{} is bound twice", value));
+            }
+            rebound.push(code);
+            keymap.keys.retain(|_, bound| *bound != action);
+            keymap.keys.insert(code, action);
+        }
+        Ok(keymap)
+    }
+
+    fn action(&self, code: KeyCode) -> Option<Action> {
+        self.keys.get(&code).copied()
+    }
+}
+
+/// `$XDG_CONFIG_HOME/texplore/keys.toml`, usually ~/.config/texplore/keys.toml
+fn keys_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("texplore").join("keys.toml"))
+}
+
+/// A single character, or a key name like `enter`, `space`, `pagedown` or `f5`
+fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let code = match key.to_ascii_lowercase().as_str() {
+        "enter" => KeyCode::Enter,
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        name => match name.strip_prefix('f').map(str::parse::<u8>) {
+            Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+            _ => return None,
+        },
+    };
+    Some(code)
+}
+
+/// --print-keys: the default keys.toml, listing every remappable action
+fn print_default_keys() {
+    println!("# texplore key bindings, read from ~/.config/texplore/keys.toml");
+    println!("# Values are a single character or one of: enter space tab backspace delete");
+    println!("# insert home end pageup pagedown f1-f12. Arrow keys, Esc and the Ctrl/Alt");
+    println!("# shortcuts can't be rebound.");
+    println!();
+    for (_, name, key, description) in ACTIONS {
+        println!("# {}", description);
+        println!("{} = {:?}", name, key);
+    }
+}
+
+/// How directory entries are listed and ordered
+#[derive(Clone, Copy)]
+struct SortOrder {
+    mode: SortMode,
+    reversed: bool,
+    /// Compare names byte-wise instead of ignoring case (see `filesystem_case_sensitive`)
+    case_sensitive: bool,
+    /// List symlinks to directories as expandable directories
+    follow_symlinks: bool,
+    /// Leave files out entirely (--dirs-only)
+    dirs_only: bool,
+}
+
+/// Key cycled with `s`; the metadata-based modes list directories first
+#[derive(Clone, Copy, PartialEq)]
+enum SortMode {
+    Name,
+    ModifiedDesc,
+    SizeDesc,
+}
+
+impl SortMode {
+    fn next(self) -> Self {
+        match self {
+            SortMode::Name => SortMode::ModifiedDesc,
+            SortMode::ModifiedDesc => SortMode::SizeDesc,
+            SortMode::SizeDesc => SortMode::Name,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortMode::Name => "name",
+            SortMode::ModifiedDesc => "modified",
+            SortMode::SizeDesc => "size",
+        }
+    }
+
+    /// Whether the unreversed order is largest/newest first
+    fn descending(self) -> bool {
+        self != SortMode::Name
+    }
+}
+
+/// Characters used to draw tree guides
+#[derive(Clone, Copy)]
+struct TreeChars {
+    bar: char,
+    branch: char,
+    last: char,
+}
+
+impl TreeChars {
+    const UNICODE: Self = Self {
+        bar: '│',
+        branch: '├',
+        last: '└',
+    };
+    const ASCII: Self = Self {
+        bar: '|',
+        branch: '+',
+        last: '\\',
+    };
+    const CURVED: Self = Self {
+        bar: '│',
+        branch: '├',
+        last: '╰',
+    };
+}
+
 struct App {
     root: Node,
     gitignore: Option<Gitignore>,
@@ -137,18 +868,61 @@ struct App {
     scroll: usize,
     status: String,
     pending_delete: Option<usize>,
+    /// Large file waiting for y/n before its content is copied
+    pending_copy: Option<PathBuf>,
     viewer: Option<Viewer>,
+    scroll_history: HashMap<PathBuf, usize>,
+    status_restore: Option<(Instant, String)>,
+    path_filter: Option<String>,
+    editing_filter: bool,
+    /// `/`: keep entries whose name contains this (plus their parents)
+    name_filter: Option<String>,
+    editing_name_filter: bool,
+    prompt: Option<Prompt>,
+    palette: Option<Palette>,
+    force_redraw: bool,
     last_click: Option<(Instant, usize)>,
     refreshing: bool,
+    config: Config,
+    /// Screen area the app was last drawn in
+    area: Rect,
+    /// In split view: whether this pane receives keys (None when not split)
+    split_active: Option<bool>,
+    /// Alt+G: recent `git log --graph` output shown under the tree
+    mini_log: Option<Vec<StyledLine>>,
+    show_mini_log: bool,
+    /// Detected at startup; case-insensitive filesystems sort names ignoring case
+    filesystem_case_sensitive: bool,
+    /// Opened on first copy and kept alive, since X11 selections vanish with their owner
+    clipboard: Option<arboard::Clipboard>,
+    /// None when the filesystem can't be watched (the main loop polls instead)
+    fs_watch: Option<FsWatch>,
+    /// Plain-key bindings for the tree view (keys.toml)
+    keymap: KeyMap,
+    /// Name colors from `LS_COLORS`; the built-in palette covers what it doesn't
+    ls_colors: Option<Arc<LsColors>>,
+    /// --dirs-only: the directory chosen with Enter, printed on exit
+    picked: Option<PathBuf>,
+    /// Where the focused symlink points, shown in the status bar
+    symlink_target: Option<SymlinkTarget>,
+    /// Directory listings read on worker threads (see `load_children_async`)
+    children_tx: mpsc::Sender<LoadedChildren>,
+    children_rx: mpsc::Receiver<LoadedChildren>,
 }
 
+/// A directory and its children, as read by a `load_children_async` worker
+type LoadedChildren = (PathBuf, io::Result<Vec<Node>>);
+
 impl App {
     fn new(
         root: Node,
         gitignore: Option<Gitignore>,
         git_status: GitStatus,
         root_path: PathBuf,
+        config: Config,
+        keymap: KeyMap,
     ) -> Self {
+        let (children_tx, children_rx) = mpsc::channel();
         Self {
             root,
             gitignore,
@@ -158,12 +932,64 @@ impl App {
             focus: 0,
             scroll: 0,
             status: String::from(
-                "q: quit  j/k: move  h/l/Enter: collapse/expand  d: delete  o: open  N: nvim  C: copy",
+                "q: quit  j/k: move  h/l/Enter: collapse/expand  n: new  d: delete  o: open  N: nvim  e: edit  C/Y: copy rel/abs path",
             ),
             pending_delete: None,
+            pending_copy: None,
             viewer: None,
+            scroll_history: HashMap::new(),
+            status_restore: None,
+            path_filter: None,
+            editing_filter: false,
+            name_filter: None,
+            editing_name_filter: false,
+            prompt: None,
+            palette: None,
+            force_redraw: false,
             last_click: None,
             refreshing: false,
+            config,
+            area: Rect::default(),
+            split_active: None,
+            mini_log: None,
+            show_mini_log: false,
+            filesystem_case_sensitive: true,
+            clipboard: None,
+            fs_watch: None,
+            keymap,
+            ls_colors: None,
+            picked: None,
+            symlink_target: None,
+            children_tx,
+            children_rx,
+        }
+    }
+
+    /// True once filesystem events have settled for `FS_DEBOUNCE`
+    fn fs_changed(&mut self) -> bool {
+        let watch = match self.fs_watch.as_mut() {
+            Some(watch) => watch,
+            None => return false,
+        };
+        if watch.events.try_iter().count() > 0 {
+            watch.pending = Some(Instant::now());
+        }
+        match watch.pending {
+            Some(at) if at.elapsed() >= FS_DEBOUNCE => {
+                watch.pending = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn sort_order(&self) -> SortOrder {
+        SortOrder {
+            mode: self.config.sort_mode,
+            reversed: self.config.sort_reversed,
+            case_sensitive: self.filesystem_case_sensitive,
+            follow_symlinks: self.config.follow_symlinks,
+            dirs_only: self.config.dirs_only,
         }
     }
 
@@ -177,14 +1003,22 @@ impl App {
             &mut indices,
             &mut bars,
             true,
-            true,
             &metrics,
+            &self.config,
             &mut self.visible,
         );
 
+        if let Some(query) = self.path_filter.as_deref().filter(|q| !q.is_empty()) {
+            filter_by_path(&mut self.visible, &self.root_path, query);
+        }
+        if let Some(query) = self.name_filter.as_deref().filter(|q| !q.is_empty()) {
+            filter_by_name(&mut self.visible, query);
+        }
+
         if self.visible.is_empty() {
             self.focus = 0;
             self.scroll = 0;
+            self.symlink_target = None;
             return;
         }
 
@@ -192,6 +1026,25 @@ impl App {
             self.focus = self.visible.len() - 1;
         }
 
+        // Only read the link again once focus lands on a different one
+        let focused = &self.visible[self.focus];
+        if focused.icon_key != "symlink" {
+            self.symlink_target = None;
+        } else if self
+            .symlink_target
+            .as_ref()
+            .is_none_or(|link| link.path != focused.path)
+        {
+            self.symlink_target = fs::read_link(&focused.path)
+                .ok()
+                .map(|target| SymlinkTarget {
+                    name: focused.name.clone(),
+                    path: focused.path.clone(),
+                    target,
+                    broken: !focused.path.exists(),
+                });
+        }
+
         if self.pending_delete.is_some() {
             if let Some(idx) = self.pending_delete {
                 if idx >= self.visible.len() {
@@ -211,11 +1064,14 @@ struct VisibleEntry {
     icon: &'static str,
     icon_key: String,
     status: String,
+    size: String,
     modified: String,
     subtree_changes: usize,
     metrics: String,
     name: String,
     ignored: bool,
+    /// Expanded while its children are still being read
+    loading: bool,
 }
 
 struct Node {
@@ -223,20 +1079,208 @@ struct Node {
     is_dir: bool,
     expanded: bool,
     children: Option<Vec<Node>>,
-    icon: &'static str,
     icon_key: String,
     status: String,
+    /// Human-readable file size; empty for directories
+    size: String,
     modified: String,
+    /// Raw metadata kept for the size and modified sort modes
+    len: u64,
+    modified_at: Option<SystemTime>,
     subtree_changes: usize,
     name: String,
     ignored: bool,
+    /// A symlink; with `follow_symlinks` it may also be an expandable directory
+    symlink: bool,
+    /// Matchers for nested .gitignore files that apply inside this directory,
+    /// nearest last (the repo-root one lives in `App::gitignore`)
+    gitignores: Vec<Arc<Gitignore>>,
+    /// Children are being read in the background (`load_children_async`)
+    loading: bool,
+}
+
+struct SymlinkTarget {
+    name: String,
+    path: PathBuf,
+    target: PathBuf,
+    /// The link resolves to nothing
+    broken: bool,
+}
+
+/// A single-line text prompt shown in the status bar
+struct Prompt {
+    kind: PromptKind,
+    buffer: String,
+}
+
+enum PromptKind {
+    NewFile,
+    Touch,
+    AddFile,
+    AddDir,
+    /// Rename the entry at these tree indices
+    Rename(Vec<usize>),
+}
+
+impl PromptKind {
+    fn label(&self) -> &'static str {
+        match self {
+            PromptKind::NewFile => "New file: ",
+            PromptKind::Touch => "Touch: ",
+            PromptKind::AddFile => "Add file: ",
+            PromptKind::AddDir => "Add directory: ",
+            PromptKind::Rename(_) => "Rename to: ",
+        }
+    }
+}
+
+/// Ctrl+P command palette state
+struct Palette {
+    query: String,
+    selected: usize,
+}
+
+impl Palette {
+    fn matches(&self) -> Vec<&'static PaletteCommand> {
+        let query = self.query.to_lowercase();
+        PALETTE_COMMANDS
+            .iter()
+            .filter(|cmd| {
+                cmd.label.to_lowercase().contains(&query)
+                    || cmd.description.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+}
+
+struct PaletteCommand {
+    label: &'static str,
+    description: &'static str,
+    action: PaletteAction,
+}
+
+#[derive(Clone, Copy)]
+enum PaletteAction {
+    OpenViewer,
+    OpenLess,
+    OpenNvim,
+    CopyPath,
+    NewFile,
+    FilterPath,
+    SizeSummary,
+    GitLog,
+    GitDiff,
+    GitAdd,
+    Reveal,
+    Terminal,
+    Delete,
+    Refresh,
+    HardRefresh,
+    Quit,
 }
 
+const PALETTE_COMMANDS: &[PaletteCommand] = &[
+    PaletteCommand {
+        label: "Open in viewer",
+        description: "View the file with bat (o)",
+        action: PaletteAction::OpenViewer,
+    },
+    PaletteCommand {
+        label: "Open in less",
+        description: "Page the file with less -R (Alt+Enter)",
+        action: PaletteAction::OpenLess,
+    },
+    PaletteCommand {
+        label: "Open in nvim",
+        description: "Edit the file in a tmux popup (N)",
+        action: PaletteAction::OpenNvim,
+    },
+    PaletteCommand {
+        label: "Copy path",
+        description: "Copy the relative path to the clipboard (C)",
+        action: PaletteAction::CopyPath,
+    },
+    PaletteCommand {
+        label: "New file",
+        description: "Create a file and open it (n)",
+        action: PaletteAction::NewFile,
+    },
+    PaletteCommand {
+        label: "Filter by path",
+        description: "Show entries matching path components (Ctrl+F)",
+        action: PaletteAction::FilterPath,
+    },
+    PaletteCommand {
+        label: "Size summary",
+        description: "Count files and sizes (Ctrl+G)",
+        action: PaletteAction::SizeSummary,
+    },
+    PaletteCommand {
+        label: "View git log",
+        description: "Recent commits touching the entry",
+        action: PaletteAction::GitLog,
+    },
+    PaletteCommand {
+        label: "Show diff",
+        description: "Uncommitted changes to the entry",
+        action: PaletteAction::GitDiff,
+    },
+    PaletteCommand {
+        label: "Git add",
+        description: "Stage the entry",
+        action: PaletteAction::GitAdd,
+    },
+    PaletteCommand {
+        label: "Reveal in finder",
+        description: "Show the entry in the system file manager",
+        action: PaletteAction::Reveal,
+    },
+    PaletteCommand {
+        label: "Open terminal here",
+        description: "Split a tmux pane in the entry's directory",
+        action: PaletteAction::Terminal,
+    },
+    PaletteCommand {
+        label: "Delete",
+        description: "Move the entry to the trash (d)",
+        action: PaletteAction::Delete,
+    },
+    PaletteCommand {
+        label: "Refresh",
+        description: "Reload the tree and git status",
+        action: PaletteAction::Refresh,
+    },
+    PaletteCommand {
+        label: "Hard refresh",
+        description: "Re-read every expanded directory from disk (F5)",
+        action: PaletteAction::HardRefresh,
+    },
+    PaletteCommand {
+        label: "Quit",
+        description: "Exit texplore (q)",
+        action: PaletteAction::Quit,
+    },
+];
+
 struct Viewer {
+    /// File being viewed (None for command output such as git log)
+    path: Option<PathBuf>,
     title: String,
     lines: Vec<StyledLine>,
     scroll: usize,
     pending_g: bool,
+    /// Active search: pattern and the indices of matching lines
+    search: Option<(String, Vec<usize>)>,
+    /// Pattern being typed after `/`
+    search_input: Option<String>,
+    /// Index into the search matches of the match n/N last landed on
+    current_match: Option<usize>,
+    /// w: soft-wrap long lines instead of cutting them off
+    wrap: bool,
+    /// #: show a gutter with line numbers
+    line_numbers: bool,
+    /// h/l: leading characters hidden from every line (ignored while wrapping)
+    h_scroll: usize,
 }
 
 #[derive(Clone, Default, PartialEq)]
@@ -265,49 +1309,885 @@ fn handle_key(app: &mut App, key: KeyEvent) -> io::Result<bool> {
         return handle_viewer_key(app, key);
     }
 
-    match key.code {
-        KeyCode::Char('q') => return Ok(true),
-        KeyCode::Char('j') | KeyCode::Down => move_focus(app, 1),
-        KeyCode::Char('k') | KeyCode::Up => move_focus(app, -1),
-        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::SHIFT) => {
-            app.focus = app.visible.len().saturating_sub(1);
-        }
-        KeyCode::Char('g') => app.focus = 0,
-        KeyCode::Char('h') | KeyCode::Left => collapse_node(app),
-        KeyCode::Char('l') | KeyCode::Right => expand_node(app),
-        KeyCode::Char('d') => prompt_delete(app),
-        KeyCode::Char('o') => open_with_bat(app)?,
-        KeyCode::Char('y') => confirm_delete(app)?,
-        KeyCode::Char('C') => copy_path_to_clipboard(app)?,
-        KeyCode::Char('N') => open_in_nvim_popup(app)?,
-        KeyCode::Enter => toggle_or_open(app)?,
-        KeyCode::Esc => cancel_delete(app),
-        _ => {}
+    if app.editing_filter {
+        handle_filter_key(app, key);
+        return Ok(false);
     }
 
-    Ok(false)
-}
+    if app.editing_name_filter {
+        handle_name_filter_key(app, key);
+        return Ok(false);
+    }
 
-fn handle_mouse(app: &mut App, mouse: MouseEvent) -> io::Result<()> {
-    if let Some(viewer) = app.viewer.as_mut() {
-        let height = crossterm::terminal::size()?.1 as usize;
-        let view_height = height.saturating_sub(2);
-        let max_scroll = viewer.lines.len().saturating_sub(view_height);
-        match mouse.kind {
-            MouseEventKind::ScrollDown => {
-                viewer.scroll = (viewer.scroll + 1).min(max_scroll);
-                viewer.pending_g = false;
-            }
-            MouseEventKind::ScrollUp => {
-                viewer.scroll = viewer.scroll.saturating_sub(1);
-                viewer.pending_g = false;
-            }
-            _ => {}
-        }
-        return Ok(());
+    if app.prompt.is_some() {
+        handle_prompt_key(app, key)?;
+        return Ok(false);
     }
 
-    match mouse.kind {
+    if app.palette.is_some() {
+        return handle_palette_key(app, key);
+    }
+
+    if let Some(path) = app.pending_copy.take() {
+        if key.code == KeyCode::Char('y') {
+            copy_file_contents(app, &path)?;
+        } else {
+            app.status = String::from("copy canceled");
+        }
+        return Ok(false);
+    }
+
+    if app.config.dirs_only {
+        if let Some(quit) = handle_dirs_only_key(app, key) {
+            return Ok(quit);
+        }
+    }
+
+    // Modifier combos, arrow keys and Esc are fixed; plain keys go through the keymap
+    match key.code {
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            move_focus(app, tree_page_rows(app) / 2)
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            move_focus(app, -(tree_page_rows(app) / 2))
+        }
+        KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            move_focus(app, -tree_page_rows(app))
+        }
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.palette = Some(Palette {
+                query: String::new(),
+                selected: 0,
+            });
+        }
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.path_filter.get_or_insert_with(String::new);
+            app.editing_filter = true;
+        }
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            show_size_summary(app)
+        }
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::ALT) => toggle_mini_log(app),
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => suspend()?,
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => reverse_sort(app),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            copy_content_to_clipboard(app)?
+        }
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.prompt = Some(Prompt {
+                kind: PromptKind::Touch,
+                buffer: String::new(),
+            });
+        }
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.focus = app.visible.len().saturating_sub(1);
+        }
+        KeyCode::Char('y') if app.pending_delete.is_some() => confirm_delete(app)?,
+        KeyCode::Down => move_focus(app, 1),
+        KeyCode::Up => move_focus(app, -1),
+        KeyCode::Left => collapse_node(app),
+        KeyCode::Right => expand_node(app),
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => open_with_less(app)?,
+        KeyCode::Esc if app.show_mini_log => app.show_mini_log = false,
+        KeyCode::Esc if app.pending_delete.is_none() && app.name_filter.is_some() => {
+            clear_name_filter(app)
+        }
+        KeyCode::Esc if app.pending_delete.is_none() && app.path_filter.is_some() => {
+            clear_path_filter(app)
+        }
+        KeyCode::Esc => cancel_delete(app),
+        code => {
+            if let Some(action) = app.keymap.action(code) {
+                return run_action(app, action);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+fn handle_palette_key(app: &mut App, key: KeyEvent) -> io::Result<bool> {
+    let palette = match app.palette.as_mut() {
+        Some(palette) => palette,
+        None => return Ok(false),
+    };
+    let count = palette.matches().len();
+
+    match key.code {
+        KeyCode::Esc => app.palette = None,
+        KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+        KeyCode::Down if palette.selected + 1 < count => palette.selected += 1,
+        KeyCode::Backspace => {
+            palette.query.pop();
+            palette.selected = 0;
+        }
+        KeyCode::Char(c) => {
+            palette.query.push(c);
+            palette.selected = 0;
+        }
+        KeyCode::Enter => {
+            let action = palette.matches().get(palette.selected).map(|c| c.action);
+            app.palette = None;
+            if let Some(action) = action {
+                return run_palette_action(app, action);
+            }
+        }
+        _ => {}
+    }
+
+    Ok(false)
+}
+
+fn run_palette_action(app: &mut App, action: PaletteAction) -> io::Result<bool> {
+    let entry = app.visible.get(app.focus).cloned();
+
+    match action {
+        PaletteAction::OpenViewer => open_with_bat(app)?,
+        PaletteAction::OpenLess => open_with_less(app)?,
+        PaletteAction::OpenNvim => open_in_nvim_popup(app)?,
+        PaletteAction::CopyPath => copy_path_to_clipboard(app, false)?,
+        PaletteAction::NewFile => {
+            app.prompt = Some(Prompt {
+                kind: PromptKind::NewFile,
+                buffer: String::new(),
+            });
+        }
+        PaletteAction::FilterPath => {
+            app.path_filter.get_or_insert_with(String::new);
+            app.editing_filter = true;
+        }
+        PaletteAction::SizeSummary => show_size_summary(app),
+        PaletteAction::GitLog => {
+            if let Some(entry) = entry {
+                let mut cmd = std::process::Command::new("git");
+                cmd.current_dir(&app.root_path)
+                    .args(["log", "--color=always", "--oneline", "-n", "200", "--"])
+                    .arg(&entry.path);
+                open_command_output(app, format!("git log {}", entry.path.display()), &mut cmd);
+            }
+        }
+        PaletteAction::GitDiff => {
+            if let Some(entry) = entry {
+                let mut cmd = std::process::Command::new("git");
+                cmd.current_dir(&app.root_path)
+                    .args(["diff", "--color=always", "HEAD", "--"])
+                    .arg(&entry.path);
+                open_command_output(app, format!("git diff {}", entry.path.display()), &mut cmd);
+            }
+        }
+        PaletteAction::GitAdd => {
+            if let Some(entry) = entry {
+                let result = std::process::Command::new("git")
+                    .current_dir(&app.root_path)
+                    .args(["add", "--"])
+                    .arg(&entry.path)
+                    .output();
+                match result {
+                    Ok(output) if output.status.success() => {
+                        resync(app)?;
+                        app.status = format!("staged: {}", entry.path.display());
+                    }
+                    Ok(output) => {
+                        let error = String::from_utf8_lossy(&output.stderr);
+                        app.status = format!("git add failed: {}", error.trim());
+                    }
+                    Err(err) => app.status = format!("git add failed: {}", err),
+                }
+            }
+        }
+        PaletteAction::Reveal => {
+            if let Some(entry) = entry {
+                reveal_in_file_manager(app, &entry.path);
+            }
+        }
+        PaletteAction::Terminal => open_terminal_here(app),
+        PaletteAction::Delete => prompt_delete(app),
+        PaletteAction::Refresh => {
+            resync(app)?;
+            app.status = String::from("refreshed");
+        }
+        PaletteAction::HardRefresh => {
+            hard_resync(app)?;
+            app.status = String::from("reloaded from disk");
+        }
+        PaletteAction::Quit => return Ok(true),
+    }
+
+    Ok(false)
+}
+
+/// Show a command's ANSI-colored output in the viewer
+fn open_command_output(app: &mut App, title: String, cmd: &mut std::process::Command) {
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            let content = String::from_utf8_lossy(&output.stdout);
+            if content.trim().is_empty() {
+                app.status = format!("{}: no output", title);
+                return;
+            }
+            show_output(app, title, &content);
+        }
+        Ok(output) => {
+            let error = String::from_utf8_lossy(&output.stderr);
+            app.status = format!("{} failed: {}", title, error.trim());
+        }
+        Err(err) => app.status = format!("{} failed: {}", title, err),
+    }
+}
+
+fn show_output(app: &mut App, title: String, content: &str) {
+    take_viewer(app);
+    app.viewer = Some(Viewer {
+        path: None,
+        title,
+        lines: parse_ansi_lines(content),
+        scroll: 0,
+        pending_g: false,
+        search: None,
+        search_input: None,
+        current_match: None,
+        wrap: false,
+        line_numbers: false,
+        h_scroll: 0,
+    });
+    app.status = String::from("VIEW: q close  j/k scroll  gg/G top/bottom");
+}
+
+/// Files above this size are not blamed, since git blame can take a long time on them
+const BLAME_MAX_BYTES: u64 = 2 * 1024 * 1024;
+
+/// b: show `git blame` for the focused file, with commit and author/date dimmed
+fn open_blame(app: &mut App) {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) => entry.clone(),
+        None => return,
+    };
+    if entry.is_dir {
+        app.status = String::from("cannot blame a directory");
+        return;
+    }
+    if entry.status == "??" || entry.ignored {
+        app.status = format!("{} is not tracked by git", entry.name);
+        return;
+    }
+    match fs::metadata(&entry.path) {
+        Ok(meta) if meta.len() > BLAME_MAX_BYTES => {
+            app.status = format!(
+                "{} is too large to blame ({})",
+                entry.name,
+                format_megabytes(meta.len())
+            );
+            return;
+        }
+        Ok(_) => {}
+        Err(err) => {
+            app.status = format!("blame failed: {}", err);
+            return;
+        }
+    }
+
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(&app.root_path)
+        .args(["blame", "--date=short", "--"])
+        .arg(&entry.path)
+        .output();
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            let error = String::from_utf8_lossy(&output.stderr);
+            app.status = if error.contains("no such path") {
+                format!("{} is not tracked by git", entry.name)
+            } else {
+                format!("blame failed: {}", error.trim())
+            };
+            return;
+        }
+        Err(err) => {
+            app.status = format!("blame failed: {}", err);
+            return;
+        }
+    };
+
+    let content = String::from_utf8_lossy(&output.stdout);
+    take_viewer(app);
+    app.viewer = Some(Viewer {
+        path: None,
+        title: format!("git blame {}", entry.path.display()),
+        lines: content.lines().map(blame_line).collect(),
+        scroll: 0,
+        pending_g: false,
+        search: None,
+        search_input: None,
+        current_match: None,
+        wrap: false,
+        line_numbers: false,
+        h_scroll: 0,
+    });
+    app.status = String::from("VIEW: q close  j/k scroll  gg/G top/bottom");
+}
+
+/// Split `<commit> (<author> <date> <line>) <code>` into styled spans
+fn blame_line(line: &str) -> StyledLine {
+    let dim = TextStyle {
+        fg: Some(Color::DarkGray),
+        dim: true,
+        ..TextStyle::default()
+    };
+    let span = |text: &str, style: TextStyle| StyledSpan {
+        text: text.replace('\t', "    "),
+        style,
+    };
+
+    let (meta_start, meta_end) = match (line.find(" ("), line.find(") ")) {
+        (Some(start), Some(end)) if start < end => (start, end + 2),
+        _ => {
+            return StyledLine {
+                spans: vec![span(line, TextStyle::default())],
+            }
+        }
+    };
+    StyledLine {
+        spans: vec![
+            span(
+                &line[..meta_start],
+                TextStyle {
+                    fg: Some(Color::Yellow),
+                    ..TextStyle::default()
+                },
+            ),
+            span(&line[meta_start..meta_end], dim),
+            span(&line[meta_end..], TextStyle::default()),
+        ],
+    }
+}
+
+/// D: show the focused entry's unstaged diff, or its staged diff when nothing
+/// is unstaged. Untracked files have no diff, so they open in full instead.
+fn open_file_diff(app: &mut App) -> io::Result<()> {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) => entry.clone(),
+        None => return Ok(()),
+    };
+    if entry.status == "??" && !entry.is_dir {
+        return open_with_bat(app);
+    }
+
+    let mut status = entry.status.chars();
+    let staged = status.next().is_some_and(|c| c != ' ');
+    let unstaged = status.next().is_some_and(|c| c != ' ');
+    let mut cmd = std::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(&app.root_path)
+        .args(["diff", "--color=always"]);
+    if staged && !unstaged {
+        cmd.arg("--staged");
+    }
+    cmd.arg("--").arg(&entry.path);
+
+    let title = format!("git diff {}", entry.path.display());
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            let content = String::from_utf8_lossy(&output.stdout);
+            if content.trim().is_empty() {
+                app.status = String::from("no changes");
+            } else {
+                show_output(app, title, &content);
+            }
+        }
+        Ok(output) => {
+            let error = String::from_utf8_lossy(&output.stderr);
+            app.status = format!("{} failed: {}", title, error.trim());
+        }
+        Err(err) => app.status = format!("{} failed: {}", title, err),
+    }
+    Ok(())
+}
+
+fn reveal_in_file_manager(app: &mut App, path: &Path) {
+    #[cfg(target_os = "macos")]
+    let result = std::process::Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .status();
+
+    #[cfg(windows)]
+    let result = std::process::Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .status();
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let result = std::process::Command::new("xdg-open")
+        .arg(if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        })
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+
+    app.status = match result {
+        Ok(status) if status.success() => format!("revealed: {}", path.display()),
+        _ => String::from("failed to open file manager"),
+    };
+}
+
+fn open_terminal_here(app: &mut App) {
+    if std::env::var("TMUX").is_err() {
+        app.status = String::from("opening a terminal requires tmux");
+        return;
+    }
+
+    let dir = focused_dir(app);
+    let result = std::process::Command::new("tmux")
+        .args(["split-window", "-c"])
+        .arg(&dir)
+        .status();
+
+    app.status = match result {
+        Ok(status) if status.success() => format!("terminal opened in {}", dir.display()),
+        _ => String::from("failed to open terminal"),
+    };
+}
+
+fn handle_prompt_key(app: &mut App, key: KeyEvent) -> io::Result<()> {
+    let prompt = match app.prompt.as_mut() {
+        Some(prompt) => prompt,
+        None => return Ok(()),
+    };
+
+    match key.code {
+        KeyCode::Esc => app.prompt = None,
+        KeyCode::Backspace => {
+            prompt.buffer.pop();
+        }
+        KeyCode::Char(c) => prompt.buffer.push(c),
+        KeyCode::Tab => complete_prompt(app),
+        KeyCode::Enter => {
+            if let Some(prompt) = app.prompt.take() {
+                let input = prompt.buffer.trim().to_string();
+                if !input.is_empty() {
+                    match prompt.kind {
+                        PromptKind::NewFile => create_and_open_file(app, &input)?,
+                        PromptKind::Touch => touch_file(app, &input)?,
+                        PromptKind::AddFile => {
+                            add_entry(app, &input, false)?;
+                        }
+                        PromptKind::AddDir => {
+                            add_entry(app, &input, true)?;
+                        }
+                        PromptKind::Rename(indices) => rename_entry(app, &indices, &input)?,
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Directory for new entries: the focused directory, or the focused file's parent
+fn focused_dir(app: &App) -> PathBuf {
+    match app.visible.get(app.focus) {
+        Some(entry) if entry.is_dir => entry.path.clone(),
+        Some(entry) => entry
+            .path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| app.root_path.clone()),
+        None => app.root_path.clone(),
+    }
+}
+
+fn create_and_open_file(app: &mut App, name: &str) -> io::Result<()> {
+    if add_entry(app, name, false)? {
+        open_with_bat(app)?;
+    }
+    Ok(())
+}
+
+/// Create an empty file or a directory and select it, refusing to overwrite.
+/// True when the new entry was created and is now focused.
+fn add_entry(app: &mut App, name: &str, is_dir: bool) -> io::Result<bool> {
+    let path = focused_dir(app).join(name);
+    if path.exists() {
+        app.status = format!("already exists: {}", name);
+        return Ok(false);
+    }
+
+    let created = if is_dir {
+        fs::create_dir_all(&path)
+    } else {
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::File::create(&path).map(|_| ()))
+    };
+    if let Err(err) = created {
+        app.status = format!("create failed: {}", err);
+        return Ok(false);
+    }
+
+    resync(app)?;
+    let revealed = reveal_path(app, &path);
+    app.status = format!("created {}", name);
+    Ok(revealed)
+}
+
+fn prompt_rename(app: &mut App) {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) => entry,
+        None => return,
+    };
+    if entry.indices.is_empty() {
+        app.status = String::from("cannot rename root");
+        return;
+    }
+
+    app.prompt = Some(Prompt {
+        kind: PromptKind::Rename(entry.indices.clone()),
+        buffer: entry.name.trim_end_matches('/').to_string(),
+    });
+}
+
+/// Rename an entry in place, keeping its expansion and the rest of the tree intact
+fn rename_entry(app: &mut App, indices: &[usize], name: &str) -> io::Result<()> {
+    let old_path = match node_at_mut(&mut app.root, indices) {
+        Some(node) => node.path.clone(),
+        None => return Ok(()),
+    };
+    if name.contains('/') {
+        app.status = String::from("rename expects a name, not a path");
+        return Ok(());
+    }
+    let new_path = match old_path.parent() {
+        Some(parent) => parent.join(name),
+        None => return Ok(()),
+    };
+    if new_path == old_path {
+        return Ok(());
+    }
+    if new_path.symlink_metadata().is_ok() {
+        app.status = format!("already exists: {}", name);
+        return Ok(());
+    }
+    if let Err(err) = fs::rename(&old_path, &new_path) {
+        app.status = format!("rename failed: {}", err);
+        return Ok(());
+    }
+
+    app.git_status = load_git_status(&app.root_path);
+    let gitignore = &app.gitignore;
+    let git_status = &app.git_status;
+    let sort = app.sort_order();
+    if let Some(parent) = parent_at_mut(&mut app.root, indices) {
+        let mut node = build_node(&new_path, gitignore, &parent.gitignores, git_status)?;
+        if sort.follow_symlinks {
+            follow_dir_symlink(&mut node);
+        }
+        let idx = *indices.last().unwrap();
+        if let Some(children) = parent.children.as_mut().filter(|c| idx < c.len()) {
+            if node.is_dir && children[idx].expanded {
+                load_children(&mut node, gitignore, git_status, sort)?;
+                node.expanded = true;
+            }
+            children[idx] = node;
+            sort_children(children, sort);
+        }
+    }
+
+    app.refresh_visible();
+    if let Some(idx) = app.visible.iter().position(|e| e.path == new_path) {
+        app.focus = idx;
+    }
+    app.status = format!("renamed to {}", name);
+    Ok(())
+}
+
+/// Create an empty file (or bump an existing file's mtime) and select it
+fn touch_file(app: &mut App, name: &str) -> io::Result<()> {
+    let path = focused_dir(app).join(name);
+    if !path.exists() {
+        add_entry(app, name, false)?;
+        return Ok(());
+    }
+
+    let touched = fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(err) = touched {
+        app.status = format!("touch failed: {}", err);
+        return Ok(());
+    }
+
+    resync(app)?;
+    reveal_path(app, &path);
+    app.status = format!("touched {}", name);
+    Ok(())
+}
+
+/// Tab in the prompt: complete the last path component against existing names
+fn complete_prompt(app: &mut App) {
+    let base = focused_dir(app);
+    let prompt = match app.prompt.as_mut() {
+        Some(prompt) => prompt,
+        None => return,
+    };
+
+    let (dir_part, partial) = match prompt.buffer.rfind('/') {
+        Some(idx) => prompt.buffer.split_at(idx + 1),
+        None => ("", prompt.buffer.as_str()),
+    };
+    let entries = match fs::read_dir(base.join(dir_part)) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    let mut candidates: Vec<(String, bool)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            name.starts_with(partial).then_some((name, is_dir))
+        })
+        .collect();
+    candidates.sort();
+
+    let completed = match candidates.as_slice() {
+        [] => return,
+        [(name, is_dir)] => {
+            if *is_dir {
+                format!("{}/", name)
+            } else {
+                name.clone()
+            }
+        }
+        [(first, _), rest @ ..] => {
+            let mut common = first.clone();
+            for (name, _) in rest {
+                let len = common
+                    .chars()
+                    .zip(name.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a.len_utf8())
+                    .sum();
+                common.truncate(len);
+            }
+            let names: Vec<&str> = candidates.iter().map(|(n, _)| n.as_str()).collect();
+            app.status = names.join("  ");
+            common
+        }
+    };
+    prompt.buffer = format!("{}{}", dir_part, completed);
+}
+
+/// Expand every ancestor of a path so it shows in the tree, then focus it
+fn reveal_path(app: &mut App, path: &Path) -> bool {
+    let rel = match path.strip_prefix(&app.root_path) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => return false,
+    };
+
+    let gitignore = &app.gitignore;
+    let git_status = &app.git_status;
+    let sort = app.sort_order();
+    let mut current = &mut app.root;
+    let mut dir = app.root_path.clone();
+    for component in rel.parent().into_iter().flat_map(|p| p.components()) {
+        if current.children.is_none()
+            && load_children(current, gitignore, git_status, sort).is_err()
+        {
+            return false;
+        }
+        current.expanded = true;
+        dir.push(component);
+        current = match current
+            .children
+            .as_mut()
+            .and_then(|children| children.iter_mut().find(|c| c.path == dir))
+        {
+            Some(child) => child,
+            None => return false,
+        };
+    }
+    if current.children.is_none() && load_children(current, gitignore, git_status, sort).is_err() {
+        return false;
+    }
+    current.expanded = true;
+
+    app.refresh_visible();
+    match app.visible.iter().position(|e| e.path == path) {
+        Some(idx) => {
+            app.focus = idx;
+            true
+        }
+        None => false,
+    }
+}
+
+fn handle_filter_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => clear_path_filter(app),
+        KeyCode::Enter => {
+            app.editing_filter = false;
+            if app.path_filter.as_deref() == Some("") {
+                app.path_filter = None;
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(query) = app.path_filter.as_mut() {
+                query.pop();
+            }
+            focus_first_match(app);
+        }
+        KeyCode::Char(c) => {
+            if let Some(query) = app.path_filter.as_mut() {
+                query.push(c);
+            }
+            focus_first_match(app);
+        }
+        _ => {}
+    }
+}
+
+/// Drop the path filter, keeping focus on the same entry
+fn clear_path_filter(app: &mut App) {
+    let focused = app.visible.get(app.focus).map(|e| e.path.clone());
+    app.path_filter = None;
+    app.editing_filter = false;
+    app.refresh_visible();
+    if let Some(path) = focused {
+        if let Some(idx) = app.visible.iter().position(|e| e.path == path) {
+            app.focus = idx;
+        }
+    }
+}
+
+fn focus_first_match(app: &mut App) {
+    app.refresh_visible();
+    let query = app.path_filter.clone().unwrap_or_default();
+    app.focus = app
+        .visible
+        .iter()
+        .position(|e| {
+            let rel = e.path.strip_prefix(&app.root_path).unwrap_or(&e.path);
+            !e.indices.is_empty() && path_matches(&rel.display().to_string(), &query)
+        })
+        .unwrap_or(0);
+}
+
+fn handle_name_filter_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => clear_name_filter(app),
+        KeyCode::Enter => {
+            app.editing_name_filter = false;
+            if app.name_filter.as_deref() == Some("") {
+                app.name_filter = None;
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(query) = app.name_filter.as_mut() {
+                query.pop();
+            }
+            focus_first_name_match(app);
+        }
+        KeyCode::Char(c) => {
+            if let Some(query) = app.name_filter.as_mut() {
+                query.push(c);
+            }
+            focus_first_name_match(app);
+        }
+        _ => {}
+    }
+}
+
+/// Drop the name filter, keeping focus on the same entry
+fn clear_name_filter(app: &mut App) {
+    let focused = app.visible.get(app.focus).map(|e| e.path.clone());
+    app.name_filter = None;
+    app.editing_name_filter = false;
+    app.refresh_visible();
+    if let Some(path) = focused {
+        if let Some(idx) = app.visible.iter().position(|e| e.path == path) {
+            app.focus = idx;
+        }
+    }
+}
+
+fn focus_first_name_match(app: &mut App) {
+    app.refresh_visible();
+    let query = app.name_filter.clone().unwrap_or_default();
+    app.focus = app
+        .visible
+        .iter()
+        .position(|e| !e.indices.is_empty() && name_matches(&e.name, &query))
+        .unwrap_or(0);
+}
+
+/// Keep entries whose name matches, plus their ancestors
+fn filter_by_name(visible: &mut Vec<VisibleEntry>, query: &str) {
+    let mut keep: HashSet<Vec<usize>> = HashSet::new();
+    for entry in visible.iter().filter(|e| !e.indices.is_empty()) {
+        if name_matches(&entry.name, query) {
+            for len in 0..=entry.indices.len() {
+                keep.insert(entry.indices[..len].to_vec());
+            }
+        }
+    }
+    visible.retain(|e| e.indices.is_empty() || keep.contains(&e.indices));
+}
+
+fn name_matches(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// Keep entries whose relative path matches, plus their ancestors
+fn filter_by_path(visible: &mut Vec<VisibleEntry>, root: &Path, query: &str) {
+    let mut keep: HashSet<Vec<usize>> = HashSet::new();
+    for entry in visible.iter().filter(|e| !e.indices.is_empty()) {
+        let rel = entry.path.strip_prefix(root).unwrap_or(&entry.path);
+        if path_matches(&rel.display().to_string(), query) {
+            for len in 0..=entry.indices.len() {
+                keep.insert(entry.indices[..len].to_vec());
+            }
+        }
+    }
+    visible.retain(|e| e.indices.is_empty() || keep.contains(&e.indices));
+}
+
+/// Each `/`-separated part of the query must appear, in order, in a path component
+fn path_matches(path: &str, query: &str) -> bool {
+    let path = path.to_lowercase();
+    let query = query.to_lowercase();
+    let mut components = path.split('/');
+    query
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .all(|part| components.any(|c| c.contains(part)))
+}
+
+fn handle_mouse(app: &mut App, mouse: MouseEvent) -> io::Result<()> {
+    if let Some(viewer) = app.viewer.as_mut() {
+        let height = crossterm::terminal::size()?.1 as usize;
+        let view_height = height.saturating_sub(2);
+        let max_scroll = viewer.lines.len().saturating_sub(view_height);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => {
+                viewer.scroll = (viewer.scroll + 1).min(max_scroll);
+                viewer.pending_g = false;
+            }
+            MouseEventKind::ScrollUp => {
+                viewer.scroll = viewer.scroll.saturating_sub(1);
+                viewer.pending_g = false;
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match mouse.kind {
         MouseEventKind::ScrollDown => {
             move_focus(app, 1);
             app.last_click = None;
@@ -318,11 +2198,21 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent) -> io::Result<()> {
         }
         MouseEventKind::Down(MouseButton::Left) => {
             let height = crossterm::terminal::size()?.1 as usize;
-            let view_height = height.saturating_sub(2);
+            let mut view_height = height.saturating_sub(2);
+            if app.show_mini_log {
+                view_height = view_height.saturating_sub(MINI_LOG_HEIGHT as usize);
+            }
             let row = mouse.row as usize;
             if row == 0 || row > view_height {
                 return Ok(());
             }
+
+            if app.visible.len() > view_height && mouse.column + 1 == app.area.width {
+                scroll_to_fraction(app, row - 1, view_height);
+                app.last_click = None;
+                return Ok(());
+            }
+
             let idx = app.scroll + row - 1;
             if idx >= app.visible.len() {
                 return Ok(());
@@ -348,27 +2238,179 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent) -> io::Result<()> {
                 open_with_bat(app)?;
             }
         }
-        _ => {}
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Jump the viewport to the position of a scrollbar row, keeping focus on screen
+fn scroll_to_fraction(app: &mut App, row: usize, view_height: usize) {
+    let total = app.visible.len();
+    let max_scroll = total.saturating_sub(view_height);
+    app.scroll = (row * total / view_height).min(max_scroll);
+    app.focus = app
+        .focus
+        .clamp(app.scroll, app.scroll + view_height.saturating_sub(1));
+}
+
+/// Rebuild the tree from disk: directories that were open stay open, changed
+/// paths are expanded, and the focused entry keeps its row on screen
+fn resync(app: &mut App) -> io::Result<()> {
+    let focused_path = app.visible.get(app.focus).map(|entry| entry.path.clone());
+    let focus_row = app.focus.saturating_sub(app.scroll);
+    let mut expanded = HashSet::new();
+    collect_expanded(&app.root, &mut expanded);
+
+    let root_abs = app.root_path.clone();
+    let sort = app.sort_order();
+    let git_status = load_git_status(&root_abs);
+    let gitignore = build_gitignore(&root_abs);
+    let inherited = ancestor_gitignores(&root_abs, &gitignore);
+    let mut root_node = build_node(&root_abs, &gitignore, &inherited, &git_status)?;
+    if root_node.is_dir {
+        expanded.insert(root_abs.clone());
+        reload_expanded(&mut root_node, &expanded, &gitignore, &git_status, sort)?;
+    }
+    expand_changed_paths(&mut root_node, &root_abs, &gitignore, &git_status, sort)?;
+
+    app.root = root_node;
+    app.gitignore = gitignore;
+    app.git_status = git_status;
+    app.refresh_visible();
+
+    if let Some(path) = focused_path {
+        if let Some(idx) = app.visible.iter().position(|entry| entry.path == path) {
+            app.focus = idx;
+            app.scroll = idx.saturating_sub(focus_row);
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_expanded(node: &Node, expanded: &mut HashSet<PathBuf>) {
+    if node.expanded {
+        expanded.insert(node.path.clone());
+    }
+    for child in node.children.iter().flatten() {
+        collect_expanded(child, expanded);
+    }
+}
+
+fn reverse_sort(app: &mut App) {
+    app.config.sort_reversed = !app.config.sort_reversed;
+    apply_sort(app);
+}
+
+/// .: show or hide gitignored entries, keeping focus where possible
+fn toggle_ignored(app: &mut App) {
+    let focused_path = app.visible.get(app.focus).map(|entry| entry.path.clone());
+    app.config.show_ignored = !app.config.show_ignored;
+    app.refresh_visible();
+
+    if let Some(path) = focused_path {
+        if let Some(idx) = app.visible.iter().position(|entry| entry.path == path) {
+            app.focus = idx;
+        }
+    }
+    app.focus = app.focus.min(app.visible.len().saturating_sub(1));
+    let state = if app.config.show_ignored {
+        "shown"
+    } else {
+        "hidden"
+    };
+    set_temp_status(app, format!("ignored files {}", state));
+}
+
+/// L: expand directory symlinks like directories, or list them as leaves again
+fn toggle_follow_symlinks(app: &mut App) -> io::Result<()> {
+    app.config.follow_symlinks = !app.config.follow_symlinks;
+    hard_resync(app)?;
+    let state = if app.config.follow_symlinks {
+        "followed"
+    } else {
+        "not followed"
+    };
+    set_temp_status(app, format!("directory symlinks {}", state));
+    Ok(())
+}
+
+/// c: show only git-changed entries, or everything again
+fn toggle_changes_only(app: &mut App) {
+    let focused_path = app.visible.get(app.focus).map(|entry| entry.path.clone());
+    app.config.changes_only = !app.config.changes_only;
+    app.refresh_visible();
+
+    if let Some(path) = focused_path {
+        if let Some(idx) = app.visible.iter().position(|entry| entry.path == path) {
+            app.focus = idx;
+        }
     }
+    app.focus = app.focus.min(app.visible.len().saturating_sub(1));
+    let message = if app.config.changes_only {
+        "showing changed entries only"
+    } else {
+        "showing all entries"
+    };
+    set_temp_status(app, message.to_string());
+}
 
-    Ok(())
+/// s: cycle name → modified → size
+fn cycle_sort_mode(app: &mut App) {
+    app.config.sort_mode = app.config.sort_mode.next();
+    apply_sort(app);
 }
 
-fn resync(app: &mut App) -> io::Result<()> {
+/// Re-sort the loaded tree in place, keeping focus on the same entry
+fn apply_sort(app: &mut App) {
     let focused_path = app.visible.get(app.focus).map(|entry| entry.path.clone());
-    let root_abs = app.root_path.clone();
-    let git_status = load_git_status(&root_abs);
-    let gitignore = build_gitignore(&root_abs);
-    let mut root_node = build_node(&root_abs, &gitignore, &git_status)?;
-    if root_node.is_dir {
-        root_node.expanded = true;
-        load_children(&mut root_node, &gitignore, &git_status)?;
+    let sort = app.sort_order();
+    sort_tree(&mut app.root, sort);
+    app.refresh_visible();
+
+    if let Some(path) = focused_path {
+        if let Some(idx) = app.visible.iter().position(|entry| entry.path == path) {
+            app.focus = idx;
+        }
     }
-    expand_changed_paths(&mut root_node, &root_abs, &gitignore, &git_status)?;
+    let descending = sort.mode.descending() != sort.reversed;
+    set_temp_status(
+        app,
+        format!(
+            "sort: {} {}",
+            sort.mode.label(),
+            if descending {
+                "descending"
+            } else {
+                "ascending"
+            }
+        ),
+    );
+}
 
-    app.root = root_node;
-    app.gitignore = gitignore;
-    app.git_status = git_status;
+/// Like `resync`, but drops every cached child list so each expanded
+/// directory is re-read from disk. Expanded directories stay expanded.
+fn hard_resync(app: &mut App) -> io::Result<()> {
+    let focused_path = app.visible.get(app.focus).map(|entry| entry.path.clone());
+    let mut expanded = HashSet::new();
+    clear_children(&mut app.root, &mut expanded);
+
+    app.git_status = load_git_status(&app.root_path);
+    app.gitignore = build_gitignore(&app.root_path);
+    let inherited = ancestor_gitignores(&app.root_path, &app.gitignore);
+    app.root = build_node(&app.root_path, &app.gitignore, &inherited, &app.git_status)?;
+    let sort = app.sort_order();
+    if app.root.is_dir {
+        expanded.insert(app.root_path.clone());
+        reload_expanded(
+            &mut app.root,
+            &expanded,
+            &app.gitignore,
+            &app.git_status,
+            sort,
+        )?;
+    }
     app.refresh_visible();
 
     if let Some(path) = focused_path {
@@ -380,22 +2422,160 @@ fn resync(app: &mut App) -> io::Result<()> {
     Ok(())
 }
 
+/// Reload `git status` and update the loaded nodes' statuses and change counts in
+/// place; unlike `resync` the tree keeps its shape, so expansion and focus are
+/// untouched (entries created or deleted meanwhile show up on the next reload)
+fn refresh_git_status_only(app: &mut App) {
+    app.git_status = load_git_status(&app.root_path);
+    update_node_status(&mut app.root, &app.git_status);
+    app.refresh_visible();
+}
+
+fn update_node_status(node: &mut Node, git_status: &GitStatus) {
+    node.status = git_status
+        .map
+        .get(&node.path)
+        .cloned()
+        .unwrap_or_else(|| "  ".to_string());
+    node.subtree_changes = match node.children.as_mut() {
+        Some(children) => {
+            for child in children.iter_mut() {
+                update_node_status(child, git_status);
+            }
+            children.iter().map(|child| child.subtree_changes).sum()
+        }
+        // Not listed yet: count the changed paths below it instead
+        None if node.is_dir => git_status
+            .map
+            .keys()
+            .filter(|path| path.starts_with(&node.path) && **path != node.path)
+            .count(),
+        None => usize::from(!node.status.trim().is_empty()),
+    };
+}
+
+fn clear_children(node: &mut Node, expanded: &mut HashSet<PathBuf>) {
+    if node.expanded {
+        expanded.insert(node.path.clone());
+    }
+    if let Some(children) = node.children.as_mut() {
+        for child in children.iter_mut() {
+            clear_children(child, expanded);
+        }
+    }
+    node.children = None;
+}
+
+fn reload_expanded(
+    node: &mut Node,
+    expanded: &HashSet<PathBuf>,
+    gitignore: &Option<Gitignore>,
+    git_status: &GitStatus,
+    sort: SortOrder,
+) -> io::Result<()> {
+    if !expanded.contains(&node.path) {
+        return Ok(());
+    }
+
+    node.expanded = true;
+    // A directory removed behind our back just disappears from the tree
+    if load_children(node, gitignore, git_status, sort).is_err() {
+        node.expanded = false;
+        return Ok(());
+    }
+    if let Some(children) = node.children.as_mut() {
+        for child in children.iter_mut() {
+            reload_expanded(child, expanded, gitignore, git_status, sort)?;
+        }
+        node.subtree_changes = children.iter().map(|child| child.subtree_changes).sum();
+    }
+    Ok(())
+}
+
 fn handle_viewer_key(app: &mut App, key: KeyEvent) -> io::Result<bool> {
     let height = crossterm::terminal::size()?.1 as usize;
     let view_height = height.saturating_sub(2);
+    let width = app.area.width;
     let max_scroll = app
         .viewer
         .as_ref()
-        .map(|viewer| viewer.lines.len().saturating_sub(view_height))
+        .map(|viewer| viewer_max_scroll(viewer, width, view_height))
         .unwrap_or(0);
 
     if let Some(viewer) = app.viewer.as_mut() {
+        if let Some(input) = viewer.search_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => viewer.search_input = None,
+                KeyCode::Enter => {
+                    let pattern = viewer.search_input.take().unwrap_or_default();
+                    if pattern.is_empty() {
+                        viewer.search = None;
+                    } else {
+                        let matches = find_matches(&viewer.lines, &pattern);
+                        // Jump to the first match at or below the current position
+                        viewer.current_match = matches
+                            .iter()
+                            .position(|&line| line >= viewer.scroll)
+                            .or((!matches.is_empty()).then_some(0));
+                        if let Some(idx) = viewer.current_match {
+                            viewer.scroll = matches[idx].min(max_scroll);
+                        }
+                        viewer.search = Some((pattern, matches));
+                    }
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Ok(false);
+        }
+
         match key.code {
+            KeyCode::Esc if viewer.search.is_some() => viewer.search = None,
             KeyCode::Char('q') | KeyCode::Esc => {
-                app.viewer = None;
-                app.status = String::from(
-                    "q: quit  j/k: move  h/l/Enter: collapse/expand  d: delete  y: confirm  o: open",
-                );
+                close_viewer(app);
+            }
+            KeyCode::Char('/') => {
+                viewer.search_input = Some(String::new());
+                viewer.pending_g = false;
+            }
+            KeyCode::Char('w') => {
+                viewer.wrap = !viewer.wrap;
+                viewer.scroll = viewer
+                    .scroll
+                    .min(viewer_max_scroll(viewer, width, view_height));
+                viewer.pending_g = false;
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                viewer.h_scroll = viewer.h_scroll.saturating_sub(VIEWER_H_STEP);
+                viewer.pending_g = false;
+            }
+            KeyCode::Char('l') | KeyCode::Right if !viewer.wrap => {
+                let longest = viewer
+                    .lines
+                    .iter()
+                    .map(|line| line.spans.iter().map(|s| s.text.chars().count()).sum())
+                    .max()
+                    .unwrap_or(0);
+                viewer.h_scroll = (viewer.h_scroll + VIEWER_H_STEP).min(longest);
+                viewer.pending_g = false;
+            }
+            KeyCode::Char('#') => {
+                viewer.line_numbers = !viewer.line_numbers;
+                viewer.scroll = viewer
+                    .scroll
+                    .min(viewer_max_scroll(viewer, width, view_height));
+                viewer.pending_g = false;
+            }
+            KeyCode::Char('N') => {
+                jump_to_match(viewer, max_scroll, false);
+                viewer.pending_g = false;
+            }
+            KeyCode::Char('n') => {
+                jump_to_match(viewer, max_scroll, true);
+                viewer.pending_g = false;
             }
             KeyCode::Char('j') | KeyCode::Down => {
                 viewer.scroll = (viewer.scroll + 1).min(max_scroll);
@@ -426,6 +2606,153 @@ fn handle_viewer_key(app: &mut App, key: KeyEvent) -> io::Result<bool> {
     Ok(false)
 }
 
+/// Move to the next (or previous) search match, wrapping around
+fn jump_to_match(viewer: &mut Viewer, max_scroll: usize, forward: bool) {
+    let matches = match viewer.search.as_ref() {
+        Some((_, matches)) if !matches.is_empty() => matches,
+        _ => return,
+    };
+    let count = matches.len();
+
+    // Step from the current match while the view is still on it; after manual
+    // scrolling, continue from the scroll position instead. Matches on the last
+    // page all clamp to max_scroll, so compare clamped positions.
+    let target = match viewer.current_match {
+        Some(idx) if matches[idx].min(max_scroll) == viewer.scroll => {
+            if forward {
+                (idx + 1) % count
+            } else {
+                (idx + count - 1) % count
+            }
+        }
+        _ if forward => matches
+            .iter()
+            .position(|&line| line.min(max_scroll) > viewer.scroll)
+            .unwrap_or(0),
+        _ => matches
+            .iter()
+            .rposition(|&line| line.min(max_scroll) < viewer.scroll)
+            .unwrap_or(count - 1),
+    };
+    viewer.current_match = Some(target);
+    viewer.scroll = matches[target].min(max_scroll);
+}
+
+/// Close the viewer, remembering the scroll position of the viewed file
+fn take_viewer(app: &mut App) {
+    if let Some(Viewer {
+        path: Some(path),
+        scroll,
+        ..
+    }) = app.viewer.take()
+    {
+        app.scroll_history.insert(path, scroll);
+    }
+}
+
+fn close_viewer(app: &mut App) {
+    take_viewer(app);
+    app.status = String::from(
+        "q: quit  j/k: move  h/l/Enter: collapse/expand  d: delete  y: confirm  o: open",
+    );
+}
+
+/// --dirs-only: Enter picks the focused directory, and keys that view, edit or
+/// suspend to the terminal are off (they would write to the captured stdout).
+/// None passes the key on to the normal handling
+fn handle_dirs_only_key(app: &mut App, key: KeyEvent) -> Option<bool> {
+    let disabled = match key.code {
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => true,
+        KeyCode::Enter => {
+            let entry = app.visible.get(app.focus)?;
+            app.picked = Some(fs::canonicalize(&entry.path).unwrap_or_else(|_| entry.path.clone()));
+            return Some(true);
+        }
+        KeyCode::Char('z' | 'c') if key.modifiers.contains(KeyModifiers::CONTROL) => true,
+        code => matches!(
+            app.keymap.action(code),
+            Some(Action::Open | Action::Diff | Action::Blame | Action::Nvim | Action::Edit)
+        ),
+    };
+    if disabled {
+        app.status = String::from("not available with --dirs-only (Enter picks a directory)");
+        return Some(false);
+    }
+    None
+}
+
+/// Run a remappable action from the keymap; returns true to quit
+fn run_action(app: &mut App, action: Action) -> io::Result<bool> {
+    match action {
+        Action::Quit => return Ok(true),
+        Action::MoveDown => move_focus(app, 1),
+        Action::MoveUp => move_focus(app, -1),
+        Action::PageDown => move_focus(app, tree_page_rows(app)),
+        Action::PageUp => move_focus(app, -tree_page_rows(app)),
+        Action::Top => app.focus = 0,
+        Action::Bottom => app.focus = app.visible.len().saturating_sub(1),
+        Action::Collapse => collapse_node(app),
+        Action::Expand => expand_node(app),
+        Action::CollapseLevel => collapse_same_depth(app),
+        Action::ExpandAll => expand_all(app),
+        Action::CollapseAll => collapse_all(app),
+        Action::FocusParent => focus_parent(app),
+        Action::NextSibling => focus_sibling(app, true),
+        Action::PrevSibling => focus_sibling(app, false),
+        Action::Open => open_with_bat(app)?,
+        Action::ToggleOrOpen => toggle_or_open(app)?,
+        Action::Diff => open_file_diff(app)?,
+        Action::Blame => open_blame(app),
+        Action::Nvim => open_in_nvim_popup(app)?,
+        Action::Edit => open_in_editor(app)?,
+        Action::NewFile => {
+            app.prompt = Some(Prompt {
+                kind: PromptKind::NewFile,
+                buffer: String::new(),
+            });
+        }
+        Action::AddFile => {
+            app.prompt = Some(Prompt {
+                kind: PromptKind::AddFile,
+                buffer: String::new(),
+            });
+        }
+        Action::AddDir => {
+            app.prompt = Some(Prompt {
+                kind: PromptKind::AddDir,
+                buffer: String::new(),
+            });
+        }
+        Action::Rename => prompt_rename(app),
+        Action::Delete => prompt_delete(app),
+        Action::CopyPath => copy_path_to_clipboard(app, false)?,
+        Action::CopyAbsPath => copy_path_to_clipboard(app, true)?,
+        Action::NameFilter => {
+            app.name_filter.get_or_insert_with(String::new);
+            app.editing_name_filter = true;
+        }
+        Action::CycleSort => cycle_sort_mode(app),
+        Action::ToggleIgnored => toggle_ignored(app),
+        Action::ToggleChanges => toggle_changes_only(app),
+        Action::ToggleSymlinks => toggle_follow_symlinks(app)?,
+        Action::Reload => {
+            hard_resync(app)?;
+            app.status = String::from("reloaded from disk");
+        }
+    }
+    Ok(false)
+}
+
+/// Rows in the tree view: the pane minus the title and status bars, and the
+/// mini log when it's shown
+fn tree_page_rows(app: &App) -> isize {
+    let mut height = app.area.height.saturating_sub(2);
+    if app.show_mini_log && height > MINI_LOG_HEIGHT {
+        height -= MINI_LOG_HEIGHT;
+    }
+    (height as isize).max(1)
+}
+
 fn move_focus(app: &mut App, delta: isize) {
     if app.visible.is_empty() {
         return;
@@ -441,56 +2768,220 @@ fn move_focus(app: &mut App, delta: isize) {
     app.focus = next as usize;
 }
 
-fn copy_path_to_clipboard(app: &mut App) -> io::Result<()> {
+/// Show a status message for a few seconds, then restore the previous one
+fn set_temp_status(app: &mut App, message: String) {
+    let previous = match app.status_restore.take() {
+        Some((_, previous)) => previous,
+        None => std::mem::take(&mut app.status),
+    };
+    app.status = message;
+    app.status_restore = Some((
+        Instant::now() + Duration::from_secs(TEMP_STATUS_SECS),
+        previous,
+    ));
+}
+
+fn show_size_summary(app: &mut App) {
+    let mut files = 0;
+    let mut dirs = 0;
+    let mut bytes = 0;
+    // Skip the root entry itself
+    for entry in app.visible.iter().filter(|e| !e.indices.is_empty()) {
+        if entry.is_dir {
+            dirs += 1;
+        } else {
+            files += 1;
+            bytes += fs::symlink_metadata(&entry.path)
+                .map(|m| m.len())
+                .unwrap_or(0);
+        }
+    }
+
+    let (total_files, total_dirs, total_bytes) = walk_totals(&app.root_path);
+
+    set_temp_status(
+        app,
+        format!(
+            "{} files, {} dirs, {} visible ({} files, {} dirs, {} total including collapsed)",
+            files,
+            dirs,
+            format_megabytes(bytes),
+            total_files,
+            total_dirs,
+            format_megabytes(total_bytes)
+        ),
+    );
+}
+
+/// Count files, directories and bytes below a directory (symlinks are not followed)
+fn walk_totals(root: &Path) -> (usize, usize, u64) {
+    let mut files = 0;
+    let mut dirs = 0;
+    let mut bytes = 0;
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let meta = match entry.path().symlink_metadata() {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+            if meta.is_dir() {
+                dirs += 1;
+                stack.push(entry.path());
+            } else {
+                files += 1;
+                bytes += meta.len();
+            }
+        }
+    }
+
+    (files, dirs, bytes)
+}
+
+fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// C copies the path relative to the root, Y the absolute path
+fn copy_path_to_clipboard(app: &mut App, absolute: bool) -> io::Result<()> {
     let entry = match app.visible.get(app.focus) {
         Some(entry) => entry.clone(),
         None => return Ok(()),
     };
 
-    // Get relative path from root
-    let rel_path = entry
-        .path
-        .strip_prefix(&app.root_path)
-        .unwrap_or(&entry.path);
+    let path_str = if absolute {
+        fs::canonicalize(&entry.path)
+            .unwrap_or_else(|_| entry.path.clone())
+            .display()
+            .to_string()
+    } else {
+        entry
+            .path
+            .strip_prefix(&app.root_path)
+            .unwrap_or(&entry.path)
+            .display()
+            .to_string()
+    };
 
-    let path_str = rel_path.display().to_string();
+    match write_clipboard(&mut app.clipboard, path_str.as_bytes()) {
+        Ok(()) => {
+            app.status = format!("copied: {}", path_str);
+        }
+        Err(_) => {
+            app.status = String::from("failed to copy to clipboard");
+        }
+    }
 
-    // Use pbcopy on macOS, xclip/xsel on Linux
-    #[cfg(target_os = "macos")]
-    let result = std::process::Command::new("pbcopy")
-        .stdin(std::process::Stdio::piped())
-        .spawn()
-        .and_then(|mut child| {
-            use std::io::Write;
-            if let Some(stdin) = child.stdin.as_mut() {
-                stdin.write_all(path_str.as_bytes())?;
+    Ok(())
+}
+
+/// Files above this size ask before their content is copied
+const LARGE_COPY_BYTES: u64 = 1024 * 1024;
+
+/// Ctrl+C: copy the focused file's content (C copies the path)
+fn copy_content_to_clipboard(app: &mut App) -> io::Result<()> {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) => entry.clone(),
+        None => return Ok(()),
+    };
+    if entry.is_dir {
+        app.status = String::from("cannot copy a directory");
+        return Ok(());
+    }
+
+    let size = match fs::metadata(&entry.path) {
+        Ok(meta) => meta.len(),
+        Err(err) => {
+            app.status = format!("copy failed: {}", err);
+            return Ok(());
+        }
+    };
+    if size > LARGE_COPY_BYTES {
+        app.status = format!(
+            "File is large ({:.1} MB). Copy anyway? y/n",
+            size as f64 / (1024.0 * 1024.0)
+        );
+        app.pending_copy = Some(entry.path);
+        return Ok(());
+    }
+
+    copy_file_contents(app, &entry.path)
+}
+
+fn copy_file_contents(app: &mut App, path: &Path) -> io::Result<()> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(err) => {
+            app.status = format!("copy failed: {}", err);
+            return Ok(());
+        }
+    };
+    if content.iter().take(512).any(|&b| b == 0) {
+        app.status = String::from("Cannot copy binary file");
+        return Ok(());
+    }
+
+    match write_clipboard(&mut app.clipboard, &content) {
+        Ok(()) => {
+            app.status = format!("copied {} bytes", content.len());
+        }
+        Err(_) => {
+            app.status = String::from("failed to copy to clipboard");
+        }
+    }
+    Ok(())
+}
+
+/// Put bytes on the system clipboard through `arboard`, falling back to
+/// pbcopy/clip/xclip when it can't be initialized or the bytes aren't UTF-8
+fn write_clipboard(clipboard: &mut Option<arboard::Clipboard>, bytes: &[u8]) -> io::Result<()> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        if clipboard.is_none() {
+            *clipboard = arboard::Clipboard::new().ok();
+        }
+        if let Some(clipboard) = clipboard.as_mut() {
+            if clipboard.set_text(text).is_ok() {
+                return Ok(());
             }
-            child.wait()
-        });
+        }
+    }
+
+    let status = write_clipboard_command(bytes)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("clipboard command failed"))
+    }
+}
+
+fn write_clipboard_command(bytes: &[u8]) -> io::Result<std::process::ExitStatus> {
+    #[cfg(target_os = "macos")]
+    let mut cmd = std::process::Command::new("pbcopy");
+
+    #[cfg(windows)]
+    let mut cmd = std::process::Command::new("clip");
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new("xclip");
+        cmd.args(["-selection", "clipboard"]);
+        cmd
+    };
 
-    #[cfg(not(target_os = "macos"))]
-    let result = std::process::Command::new("xclip")
-        .args(["-selection", "clipboard"])
-        .stdin(std::process::Stdio::piped())
+    cmd.stdin(std::process::Stdio::piped())
         .spawn()
         .and_then(|mut child| {
             use std::io::Write;
             if let Some(stdin) = child.stdin.as_mut() {
-                stdin.write_all(path_str.as_bytes())?;
+                stdin.write_all(bytes)?;
             }
             child.wait()
-        });
-
-    match result {
-        Ok(status) if status.success() => {
-            app.status = format!("copied: {}", path_str);
-        }
-        _ => {
-            app.status = String::from("failed to copy to clipboard");
-        }
-    }
-
-    Ok(())
+        })
 }
 
 fn open_in_nvim_popup(app: &mut App) -> io::Result<()> {
@@ -540,6 +3031,108 @@ fn open_in_nvim_popup(app: &mut App) -> io::Result<()> {
     Ok(())
 }
 
+/// Restore the terminal and stop the process like a shell job; the main
+/// loop re-initializes everything once SIGCONT arrives
+#[cfg(unix)]
+fn suspend() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(ui_output(), LeaveAlternateScreen, DisableMouseCapture)?;
+    signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)
+}
+
+/// Windows consoles have no job control, so Ctrl+Z does nothing there
+#[cfg(windows)]
+fn suspend() -> io::Result<()> {
+    Ok(())
+}
+
+/// Leave the TUI, run a command in the plain terminal, then restore the TUI
+fn run_outside_tui(cmd: &mut std::process::Command) -> io::Result<std::process::ExitStatus> {
+    disable_raw_mode()?;
+    execute!(ui_output(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    if UI_ON_STDERR.load(Ordering::Relaxed) {
+        cmd.stdout(io::stderr());
+    }
+    let result = cmd.status();
+
+    enable_raw_mode()?;
+    execute!(ui_output(), EnterAlternateScreen, EnableMouseCapture)?;
+    result
+}
+
+/// Suspend the TUI and edit the focused file with $EDITOR (then nvim, then vi)
+fn open_in_editor(app: &mut App) -> io::Result<()> {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) => entry.clone(),
+        None => return Ok(()),
+    };
+
+    if entry.path.is_dir() {
+        app.status = String::from("cannot open a directory");
+        return Ok(());
+    }
+
+    let mut editors: Vec<String> = env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .into_iter()
+        .collect();
+    editors.extend(["nvim".to_string(), "vi".to_string()]);
+
+    let mut result = Err(io::Error::from(io::ErrorKind::NotFound));
+    for editor in &editors {
+        // $EDITOR may carry arguments, e.g. "code --wait"
+        let mut parts = editor.split_whitespace();
+        let program = match parts.next() {
+            Some(program) => program,
+            None => continue,
+        };
+        result = run_outside_tui(
+            std::process::Command::new(program)
+                .args(parts)
+                .arg(&entry.path),
+        );
+        match &result {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            _ => break,
+        }
+    }
+    app.force_redraw = true;
+
+    match result {
+        Ok(_) => resync(app)?,
+        Err(_) => app.status = String::from("failed to run an editor"),
+    }
+
+    Ok(())
+}
+
+fn open_with_less(app: &mut App) -> io::Result<()> {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) => entry.clone(),
+        None => return Ok(()),
+    };
+
+    if entry.path.is_dir() {
+        app.status = String::from("cannot open a directory");
+        return Ok(());
+    }
+
+    let result = run_outside_tui(
+        std::process::Command::new("less")
+            .arg("-R")
+            .arg(&entry.path),
+    );
+    app.force_redraw = true;
+
+    if result.is_err() {
+        app.status = String::from("failed to run less");
+    }
+
+    Ok(())
+}
+
 fn open_with_bat(app: &mut App) -> io::Result<()> {
     let entry = match app.visible.get(app.focus) {
         Some(entry) => entry.clone(),
@@ -551,40 +3144,84 @@ fn open_with_bat(app: &mut App) -> io::Result<()> {
         return Ok(());
     }
 
-    let width = crossterm::terminal::size()?.0;
+    let width = app.area.width;
     let output = std::process::Command::new("bat")
         .arg("--paging=never")
-        .arg("--color=always")
+        .arg(if should_color() {
+            "--color=always"
+        } else {
+            "--color=never"
+        })
         .arg("--decorations=always")
         .arg("--style=full")
         .arg(format!("--terminal-width={}", width))
         .arg(&entry.path)
         .output();
 
-    match output {
+    let lines = match output {
         Ok(output) if output.status.success() => {
-            let content = String::from_utf8_lossy(&output.stdout);
-            let lines = parse_ansi_lines(&content);
-            app.viewer = Some(Viewer {
-                title: entry.path.display().to_string(),
-                lines,
-                scroll: 0,
-                pending_g: false,
-            });
-            app.status = String::from("VIEW: q close  j/k scroll  gg/G top/bottom");
+            parse_ansi_lines(&String::from_utf8_lossy(&output.stdout))
         }
         Ok(output) => {
             let error = String::from_utf8_lossy(&output.stderr);
             app.status = format!("bat failed: {}", error.trim());
+            return Ok(());
         }
+        // bat isn't installed: show the file without highlighting
+        Err(err) if err.kind() == io::ErrorKind::NotFound => match plain_file_lines(&entry.path) {
+            Ok(lines) => lines,
+            Err(err) => {
+                app.status = format!("read failed: {}", err);
+                return Ok(());
+            }
+        },
         Err(err) => {
             app.status = format!("bat failed: {}", err);
+            return Ok(());
         }
-    }
+    };
+
+    take_viewer(app);
+    let scroll = app.scroll_history.get(&entry.path).copied().unwrap_or(0);
+    app.viewer = Some(Viewer {
+        path: Some(entry.path.clone()),
+        title: entry.path.display().to_string(),
+        lines,
+        scroll,
+        pending_g: false,
+        search: None,
+        search_input: None,
+        current_match: None,
+        wrap: false,
+        line_numbers: false,
+        h_scroll: 0,
+    });
+    app.status = String::from("VIEW: q close  j/k scroll  gg/G top/bottom");
 
     Ok(())
 }
 
+/// Unstyled lines for the viewer, or a placeholder for files that aren't UTF-8
+fn plain_file_lines(path: &Path) -> io::Result<Vec<StyledLine>> {
+    let plain = |text: String| StyledLine {
+        spans: vec![StyledSpan {
+            text,
+            style: TextStyle::default(),
+        }],
+    };
+
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content
+            .lines()
+            .map(|line| plain(line.replace('\t', "    ")))
+            .collect()),
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            Ok(vec![plain(String::from("binary file"))])
+        }
+        Err(err) => Err(err),
+    }
+}
+
 fn collapse_node(app: &mut App) {
     if let Some(entry) = app.visible.get(app.focus) {
         if let Some(node) = node_at_mut(&mut app.root, &entry.indices) {
@@ -596,38 +3233,242 @@ fn collapse_node(app: &mut App) {
     }
 }
 
-fn expand_node(app: &mut App) {
-    if let Some(entry) = app.visible.get(app.focus) {
-        if let Some(node) = node_at_mut(&mut app.root, &entry.indices) {
-            if node.is_dir {
-                if !node.expanded {
-                    node.expanded = true;
-                }
-                if node.children.is_none() {
-                    if let Err(err) = load_children(node, &app.gitignore, &app.git_status) {
-                        app.status = format!("error: {}", err);
-                    }
-                }
+/// Rows taken by the Alt+G log overlay, including its border
+const MINI_LOG_HEIGHT: u16 = 8;
+
+/// Alt+G: show or hide the recent commit graph under the tree
+fn toggle_mini_log(app: &mut App) {
+    if app.show_mini_log {
+        app.show_mini_log = false;
+        return;
+    }
+
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(&app.root_path)
+        .args([
+            "log",
+            "--oneline",
+            "--graph",
+            "--color=always",
+            "-20",
+            "HEAD",
+        ])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let text = String::from_utf8_lossy(&output.stdout);
+            app.mini_log = Some(parse_ansi_lines(&text));
+            app.show_mini_log = true;
+        }
+        Ok(output) => {
+            let error = String::from_utf8_lossy(&output.stderr);
+            app.status = format!("git log failed: {}", error.trim());
+        }
+        Err(err) => app.status = format!("git log failed: {}", err),
+    }
+}
+
+/// Collapse every expanded directory at the focused entry's depth.
+fn collapse_same_depth(app: &mut App) {
+    let (depth, focused_path) = match app.visible.get(app.focus) {
+        Some(entry) => (entry.indices.len(), entry.path.clone()),
+        None => return,
+    };
+
+    let targets: Vec<Vec<usize>> = app
+        .visible
+        .iter()
+        .filter(|entry| entry.is_dir && entry.indices.len() == depth)
+        .map(|entry| entry.indices.clone())
+        .collect();
+
+    let mut collapsed = 0;
+    for indices in targets {
+        if let Some(node) = node_at_mut(&mut app.root, &indices) {
+            if node.expanded {
+                node.expanded = false;
+                collapsed += 1;
             }
         }
     }
+
+    app.refresh_visible();
+    if let Some(idx) = app.visible.iter().position(|e| e.path == focused_path) {
+        app.focus = idx;
+    }
+    app.status = format!("collapsed {} directories at depth {}", collapsed, depth);
+}
+
+/// p: move to the directory containing the focused entry
+fn focus_parent(app: &mut App) {
+    let indices = match app.visible.get(app.focus) {
+        Some(entry) if !entry.indices.is_empty() => &entry.indices[..entry.indices.len() - 1],
+        _ => return,
+    };
+    if let Some(idx) = app.visible.iter().position(|e| e.indices == indices) {
+        app.focus = idx;
+    }
+}
+
+/// ]/[: move to the next/previous entry with the same parent, skipping the
+/// expanded children in between. At the first or last sibling focus stays put.
+fn focus_sibling(app: &mut App, forward: bool) {
+    let indices = match app.visible.get(app.focus) {
+        Some(entry) if !entry.indices.is_empty() => entry.indices.clone(),
+        _ => return,
+    };
+    let parent = &indices[..indices.len() - 1];
+    let is_sibling =
+        |e: &VisibleEntry| e.indices.len() == indices.len() && e.indices.starts_with(parent);
+
+    let target = if forward {
+        app.visible
+            .iter()
+            .enumerate()
+            .skip(app.focus + 1)
+            .find(|(_, e)| is_sibling(e))
+    } else {
+        app.visible
+            .iter()
+            .enumerate()
+            .take(app.focus)
+            .rev()
+            .find(|(_, e)| is_sibling(e))
+    };
+    match target {
+        Some((idx, _)) => app.focus = idx,
+        None if forward => app.status = String::from("last sibling"),
+        None => app.status = String::from("first sibling"),
+    }
+}
+
+/// How many levels below the focused directory `E` will open
+const EXPAND_ALL_DEPTH: usize = 8;
+
+/// E: expand every directory under the focused one, up to `EXPAND_ALL_DEPTH` levels
+fn expand_all(app: &mut App) {
+    let entry = match app.visible.get(app.focus) {
+        Some(entry) if entry.is_dir => entry.clone(),
+        Some(entry) => {
+            app.status = format!("{} is not a directory", entry.name);
+            return;
+        }
+        None => return,
+    };
+
+    let sort = app.sort_order();
+    let node = match node_at_mut(&mut app.root, &entry.indices) {
+        Some(node) => node,
+        None => return,
+    };
+    let mut capped = false;
+    set_expanded_recursive(
+        node,
+        true,
+        EXPAND_ALL_DEPTH,
+        &app.gitignore,
+        &app.git_status,
+        sort,
+        &mut capped,
+    );
+
+    app.refresh_visible();
+    app.status = if capped {
+        format!(
+            "expanded {} (stopped at {} levels, view is partial)",
+            entry.name, EXPAND_ALL_DEPTH
+        )
+    } else {
+        format!("expanded {}", entry.name)
+    };
+}
+
+/// Z: collapse everything back to the root
+fn collapse_all(app: &mut App) {
+    let sort = app.sort_order();
+    let mut capped = false;
+    set_expanded_recursive(
+        &mut app.root,
+        false,
+        usize::MAX,
+        &app.gitignore,
+        &app.git_status,
+        sort,
+        &mut capped,
+    );
+    app.root.expanded = true;
+    app.refresh_visible();
+    app.focus = 0;
+    app.scroll = 0;
+    app.status = String::from("collapsed all");
+}
+
+/// Expand or collapse a subtree. Expanding loads children lazily and skips
+/// .git and gitignored directories below the starting node; `capped` is set when
+/// directories were left closed because `depth` ran out.
+fn set_expanded_recursive(
+    node: &mut Node,
+    expanded: bool,
+    depth: usize,
+    gitignore: &Option<Gitignore>,
+    git_status: &GitStatus,
+    sort: SortOrder,
+    capped: &mut bool,
+) {
+    if !node.is_dir {
+        return;
+    }
+    if !expanded {
+        node.expanded = false;
+        for child in node.children.iter_mut().flatten() {
+            set_expanded_recursive(child, false, depth, gitignore, git_status, sort, capped);
+        }
+        return;
+    }
+
+    if node.children.is_none() && load_children(node, gitignore, git_status, sort).is_err() {
+        return;
+    }
+    node.expanded = true;
+    for child in node.children.iter_mut().flatten() {
+        if !child.is_dir || child.ignored || child.path.ends_with(".git") {
+            continue;
+        }
+        if depth == 0 {
+            *capped = true;
+            continue;
+        }
+        set_expanded_recursive(child, true, depth - 1, gitignore, git_status, sort, capped);
+    }
+}
+
+fn expand_node(app: &mut App) {
+    let indices = match app.visible.get(app.focus) {
+        Some(entry) => entry.indices.clone(),
+        None => return,
+    };
+    if let Some(node) = node_at_mut(&mut app.root, &indices) {
+        if node.is_dir {
+            node.expanded = true;
+            load_children_async(app, &indices);
+        }
+    }
 }
 
 fn toggle_expand(app: &mut App) {
-    if let Some(entry) = app.visible.get(app.focus) {
-        if let Some(node) = node_at_mut(&mut app.root, &entry.indices) {
-            if node.is_dir {
-                if node.expanded {
-                    node.expanded = false;
-                    app.status = format!("collapsed {}", node.name);
-                } else {
-                    node.expanded = true;
-                    if node.children.is_none() {
-                        if let Err(err) = load_children(node, &app.gitignore, &app.git_status) {
-                            app.status = format!("error: {}", err);
-                        }
-                    }
-                }
+    let indices = match app.visible.get(app.focus) {
+        Some(entry) => entry.indices.clone(),
+        None => return,
+    };
+    if let Some(node) = node_at_mut(&mut app.root, &indices) {
+        if node.is_dir {
+            if node.expanded {
+                node.expanded = false;
+                app.status = format!("collapsed {}", node.name);
+            } else {
+                node.expanded = true;
+                load_children_async(app, &indices);
             }
         }
     }
@@ -699,11 +3540,34 @@ fn confirm_delete(app: &mut App) -> io::Result<()> {
 
     app.pending_delete = None;
     app.status = format!("deleted {}", entry.name);
+    focus_after_removal(app, idx, &entry.path);
     Ok(())
 }
 
-fn render(frame: &mut ratatui::Frame, app: &mut App) {
-    let area = frame.area();
+/// Focus the sibling that moved into a removed entry's row, else the previous
+/// sibling, else the parent
+fn focus_after_removal(app: &mut App, idx: usize, removed: &Path) {
+    app.refresh_visible();
+    let parent = match removed.parent() {
+        Some(parent) => parent,
+        None => return,
+    };
+    let is_sibling = |entry: &VisibleEntry| entry.path.parent() == Some(parent);
+
+    let target = if app.visible.get(idx).is_some_and(is_sibling) {
+        Some(idx)
+    } else {
+        app.visible[..idx.min(app.visible.len())]
+            .iter()
+            .rposition(|entry| is_sibling(entry) || entry.path == parent)
+    };
+    if let Some(target) = target {
+        app.focus = target;
+    }
+}
+
+fn render(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
+    app.area = area;
 
     let chunks = Layout::vertical([
         Constraint::Length(1), // Title bar
@@ -714,9 +3578,94 @@ fn render(frame: &mut ratatui::Frame, app: &mut App) {
 
     if app.viewer.is_some() {
         render_viewer(frame, app, chunks[0], chunks[1], chunks[2]);
+    } else if app.show_mini_log && chunks[1].height > MINI_LOG_HEIGHT {
+        let rows = Layout::vertical([Constraint::Min(1), Constraint::Length(MINI_LOG_HEIGHT)])
+            .split(chunks[1]);
+        render_tree(frame, app, chunks[0], rows[0], chunks[2]);
+        render_mini_log(frame, app, rows[1]);
     } else {
         render_tree(frame, app, chunks[0], chunks[1], chunks[2]);
     }
+
+    if let Some(palette) = app.palette.as_ref() {
+        render_palette(frame, palette, chunks[1]);
+    }
+}
+
+fn render_mini_log(frame: &mut ratatui::Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = app
+        .mini_log
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(build_styled_line)
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::TOP)
+                .title(" git log (Alt+G/Esc: close) "),
+        ),
+        area,
+    );
+}
+
+fn title_style(app: &App) -> Style {
+    match app.split_active {
+        Some(true) => Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+        _ => Style::default().add_modifier(Modifier::DIM),
+    }
+}
+
+fn render_palette(frame: &mut ratatui::Frame, palette: &Palette, area: Rect) {
+    let matches = palette.matches();
+    let width = area.width.saturating_sub(4).min(70);
+    let height = (matches.len() as u16 + 3).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y,
+        width,
+        height,
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(format!("{}_", palette.query)),
+    ])];
+    for (i, cmd) in matches.iter().enumerate() {
+        let style = if i == palette.selected {
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<20}", cmd.label), style),
+            Span::styled(
+                format!(" {}", cmd.description),
+                style.add_modifier(Modifier::DIM),
+            ),
+        ]));
+    }
+
+    // Keep the selection visible when the list is taller than the popup
+    let inner_height = height.saturating_sub(2) as usize;
+    let scroll = (palette.selected + 2).saturating_sub(inner_height) as u16;
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).scroll((scroll, 0)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Command Palette "),
+        ),
+        popup,
+    );
 }
 
 fn render_tree(
@@ -742,9 +3691,47 @@ fn render_tree(
     } else {
         format!("texplore - {}", app.root_path.display())
     };
-    let title_widget = Paragraph::new(title).style(Style::default().add_modifier(Modifier::DIM));
+    let title = format!(
+        "{}  {} {}",
+        title,
+        app.config.sort_mode.label(),
+        if app.config.sort_mode.descending() != app.config.sort_reversed {
+            "↓"
+        } else {
+            "↑"
+        }
+    );
+    let title = match app.path_filter.as_deref() {
+        Some(query) => format!("{}  [path: {}]", title, query),
+        None => title,
+    };
+    let title = match app.name_filter.as_deref() {
+        Some(query) => format!("{}  [/{}]", title, query),
+        None => title,
+    };
+    let title = if app.config.changes_only {
+        format!("{}  [changes]", title)
+    } else {
+        title
+    };
+    let title = if app.config.show_ignored {
+        title
+    } else {
+        format!("{}  [ignored hidden]", title)
+    };
+    let title_widget = Paragraph::new(title).style(title_style(app));
     frame.render_widget(title_widget, title_area);
 
+    // Reserve the right column for a scrollbar when entries overflow
+    let (tree_area, scrollbar_area) =
+        if view_height > 0 && app.visible.len() > view_height && tree_area.width > 1 {
+            let cols =
+                Layout::horizontal([Constraint::Min(1), Constraint::Length(1)]).split(tree_area);
+            (cols[0], Some(cols[1]))
+        } else {
+            (tree_area, None)
+        };
+
     // Tree lines
     let use_color = should_color();
     let lines: Vec<Line> = app
@@ -755,23 +3742,91 @@ fn render_tree(
         .enumerate()
         .map(|(row, entry)| {
             let focused = app.focus == app.scroll + row;
-            build_tree_line(entry, focused, use_color, tree_area.width as usize)
+            build_tree_line(
+                entry,
+                focused,
+                use_color,
+                tree_area.width as usize,
+                app.name_filter.as_deref(),
+                app.ls_colors.as_deref(),
+            )
         })
         .collect();
 
     let tree_widget = Paragraph::new(lines);
     frame.render_widget(tree_widget, tree_area);
 
+    if let Some(area) = scrollbar_area {
+        let scrollbar = build_scrollbar(app.visible.len(), app.scroll, view_height);
+        frame.render_widget(Paragraph::new(scrollbar), area);
+    }
+
     // Status bar
-    let status_text = if app.refreshing {
+    let status_text = if let Some(prompt) = app.prompt.as_ref() {
+        format!("{}{}_", prompt.kind.label(), prompt.buffer)
+    } else if app.editing_filter {
+        format!(
+            "path: {}_  (Enter: keep  Esc: clear)",
+            app.path_filter.as_deref().unwrap_or("")
+        )
+    } else if app.editing_name_filter {
+        format!(
+            "/{}_  (Enter: keep  Esc: clear)",
+            app.name_filter.as_deref().unwrap_or("")
+        )
+    } else if app.refreshing {
         format!("{} {}", spinner_frame(), app.status)
     } else {
         app.status.clone()
     };
-    let status_widget = Paragraph::new(status_text);
+    // A focused symlink shows `name -> target` unless something else is being said
+    let idle = app.prompt.is_none()
+        && !app.editing_filter
+        && !app.editing_name_filter
+        && !app.refreshing
+        && app.status_restore.is_none();
+    let status_line = match app.symlink_target.as_ref() {
+        Some(link) if idle => symlink_status_line(link),
+        _ => Line::from(status_text),
+    };
+    let status_widget = Paragraph::new(status_line);
     frame.render_widget(status_widget, status_area);
 }
 
+fn symlink_status_line(link: &SymlinkTarget) -> Line<'static> {
+    let target = link.target.display().to_string();
+    let mut spans = vec![Span::raw(format!("{} -> ", link.name))];
+    if link.broken {
+        let style = if should_color() {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        spans.push(Span::styled(format!("{} (broken)", target), style));
+    } else {
+        spans.push(Span::raw(target));
+    }
+    Line::from(spans)
+}
+
+fn build_scrollbar(total: usize, scroll: usize, view_height: usize) -> Vec<Line<'static>> {
+    let thumb_len = (view_height * view_height / total).max(1);
+    let thumb_start = (scroll * view_height / total).min(view_height - thumb_len);
+
+    (0..view_height)
+        .map(|row| {
+            if row >= thumb_start && row < thumb_start + thumb_len {
+                Line::from(Span::raw("█"))
+            } else {
+                Line::from(Span::styled(
+                    "│",
+                    Style::default().add_modifier(Modifier::DIM),
+                ))
+            }
+        })
+        .collect()
+}
+
 fn render_viewer(
     frame: &mut ratatui::Frame,
     app: &mut App,
@@ -790,32 +3845,55 @@ fn render_viewer(
     } else {
         format!("texplore - {}", app.root_path.display())
     };
-    let title_widget = Paragraph::new(title).style(Style::default().add_modifier(Modifier::DIM));
+    let title_widget = Paragraph::new(title).style(title_style(app));
     frame.render_widget(title_widget, title_area);
 
     // Content
     let view_height = content_area.height as usize;
-    let max_scroll = viewer.lines.len().saturating_sub(view_height);
+    let max_scroll = viewer_max_scroll(viewer, content_area.width, view_height);
     let scroll = viewer.scroll.min(max_scroll);
 
-    let lines: Vec<Line> = viewer
-        .lines
-        .iter()
-        .skip(scroll)
+    let lines: Vec<Line> = (scroll..viewer.lines.len())
         .take(view_height)
-        .map(|styled_line| build_styled_line(styled_line))
+        .map(|idx| viewer_line(viewer, idx))
         .collect();
 
-    let content_widget = Paragraph::new(lines);
+    let mut content_widget = Paragraph::new(lines);
+    if viewer.wrap {
+        content_widget = content_widget.wrap(Wrap { trim: false });
+    }
     frame.render_widget(content_widget, content_area);
 
     // Status bar
-    let status = format!("{} | q close  j/k scroll  gg/G top/bottom", viewer.title);
+    let status = match (&viewer.search_input, &viewer.search) {
+        (Some(input), _) => format!("/{}", input),
+        (None, Some((pattern, matches))) if matches.is_empty() => {
+            format!("{} | /{}: no matches  Esc clear", viewer.title, pattern)
+        }
+        (None, Some((pattern, matches))) => format!(
+            "{} | /{}: match {}/{}  n/N next/prev  Esc clear",
+            viewer.title,
+            pattern,
+            viewer.current_match.map_or(0, |idx| idx + 1),
+            matches.len()
+        ),
+        (None, None) => format!(
+            "{} | q close  j/k scroll  gg/G top/bottom  / search  h/l pan  w wrap  # numbers",
+            viewer.title
+        ),
+    };
     let status_widget = Paragraph::new(status);
     frame.render_widget(status_widget, status_area);
 }
 
-fn build_tree_line(entry: &VisibleEntry, focused: bool, use_color: bool, width: usize) -> Line<'_> {
+fn build_tree_line<'a>(
+    entry: &'a VisibleEntry,
+    focused: bool,
+    use_color: bool,
+    width: usize,
+    name_filter: Option<&str>,
+    ls_colors: Option<&LsColors>,
+) -> Line<'a> {
     let mut spans = Vec::new();
 
     let base_style = if focused {
@@ -849,11 +3927,47 @@ fn build_tree_line(entry: &VisibleEntry, focused: bool, use_color: bool, width:
     let name_style = if entry.ignored && !focused {
         base_style.add_modifier(Modifier::DIM)
     } else if use_color && !focused {
-        base_style.fg(color_for_key(&entry.icon_key))
+        match ls_colors.and_then(|colors| colors.style_for(entry)) {
+            Some(style) => base_style.patch(style),
+            None => base_style.fg(color_for_key(&entry.icon_key)),
+        }
     } else {
         base_style
     };
-    spans.push(Span::styled(entry.name.clone(), name_style));
+    let ranges = match name_filter {
+        Some(query) if !query.is_empty() => entry
+            .name
+            .to_ascii_lowercase()
+            .match_indices(&query.to_ascii_lowercase())
+            .map(|(idx, m)| (idx, idx + m.len()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let match_style = name_style
+        .bg(Color::Yellow)
+        .fg(Color::Black)
+        .remove_modifier(Modifier::DIM);
+    let mut last = 0;
+    for (start, end) in ranges {
+        if start > last {
+            spans.push(Span::styled(
+                entry.name[last..start].to_string(),
+                name_style,
+            ));
+        }
+        spans.push(Span::styled(
+            entry.name[start..end].to_string(),
+            match_style,
+        ));
+        last = end;
+    }
+    spans.push(Span::styled(entry.name[last..].to_string(), name_style));
+    if entry.loading {
+        spans.push(Span::styled(
+            format!(" {} loading…", spinner_frame()),
+            base_style.add_modifier(Modifier::DIM),
+        ));
+    }
 
     // Calculate current length for right-aligned content
     let current_len: usize = spans.iter().map(|s| s.content.chars().count()).sum();
@@ -870,6 +3984,11 @@ fn build_tree_line(entry: &VisibleEntry, focused: bool, use_color: bool, width:
         suffix_parts.push((entry.status.clone(), color));
     }
 
+    let size_index = suffix_parts.len();
+    if !entry.size.is_empty() {
+        suffix_parts.push((entry.size.clone(), Color::DarkGray));
+    }
+
     if !entry.modified.is_empty() {
         suffix_parts.push((entry.modified.clone(), Color::DarkGray));
     }
@@ -882,12 +4001,12 @@ fn build_tree_line(entry: &VisibleEntry, focused: bool, use_color: bool, width:
         suffix_parts.push((entry.metrics.clone(), Color::DarkGray));
     }
 
-    // Calculate suffix length
-    let suffix_len: usize = suffix_parts
-        .iter()
-        .map(|(s, _)| s.chars().count())
-        .sum::<usize>()
-        + suffix_parts.len().saturating_sub(1) * 2; // separators
+    // Calculate suffix length, dropping the size column first when it doesn't fit
+    let mut suffix_len = suffix_width(&suffix_parts);
+    if !entry.size.is_empty() && current_len + 1 + suffix_len >= width {
+        suffix_parts.remove(size_index);
+        suffix_len = suffix_width(&suffix_parts);
+    }
 
     // Add padding and suffix if there's room
     if !suffix_parts.is_empty() && current_len + 1 + suffix_len < width {
@@ -912,36 +4031,200 @@ fn build_tree_line(entry: &VisibleEntry, focused: bool, use_color: bool, width:
     Line::from(spans)
 }
 
+fn suffix_width(parts: &[(String, Color)]) -> usize {
+    parts.iter().map(|(s, _)| s.chars().count()).sum::<usize>() + parts.len().saturating_sub(1) * 2
+    // separators
+}
+
+/// Columns moved per h/l press in the viewer
+const VIEWER_H_STEP: usize = 4;
+
+/// One viewer line with search highlighting and the optional line-number gutter
+fn viewer_line(viewer: &Viewer, idx: usize) -> Line<'_> {
+    let styled_line = &viewer.lines[idx];
+    let mut line = match viewer.search.as_ref() {
+        Some((pattern, matches)) if matches.binary_search(&idx).is_ok() => {
+            let line = build_highlighted_line(styled_line, pattern);
+            // The match n/N landed on gets a full-width background
+            if viewer.current_match.map(|m| matches[m]) == Some(idx) {
+                line.style(Style::default().bg(Color::DarkGray))
+            } else {
+                line
+            }
+        }
+        _ => build_styled_line(styled_line),
+    };
+    if viewer.h_scroll > 0 && !viewer.wrap {
+        skip_leading_chars(&mut line, viewer.h_scroll);
+    }
+
+    if viewer.line_numbers {
+        let width = viewer.lines.len().to_string().len();
+        line.spans.insert(
+            0,
+            Span::styled(
+                // No space after the number, or word wrapping could strand it on its own row
+                format!("{:>width$}│", idx + 1, width = width),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::DIM),
+            ),
+        );
+    }
+    line
+}
+
+/// Drop the first `count` characters of a line, cutting through spans but
+/// keeping each remaining piece's style
+fn skip_leading_chars(line: &mut Line<'_>, count: usize) {
+    let mut remaining = count;
+    line.spans.retain_mut(|span| {
+        if remaining == 0 {
+            return true;
+        }
+        let len = span.content.chars().count();
+        if len <= remaining {
+            remaining -= len;
+            return false;
+        }
+        let rest: String = span.content.chars().skip(remaining).collect();
+        span.content = rest.into();
+        remaining = 0;
+        true
+    });
+}
+
+/// Last scroll position that still fills the view. With wrapping on, count
+/// the rows the trailing lines wrap to rather than assuming one row per line.
+fn viewer_max_scroll(viewer: &Viewer, width: u16, view_height: usize) -> usize {
+    let total = viewer.lines.len();
+    if !viewer.wrap || width == 0 {
+        return total.saturating_sub(view_height);
+    }
+
+    let mut rows = 0;
+    for idx in (0..total).rev() {
+        rows += Paragraph::new(viewer_line(viewer, idx))
+            .wrap(Wrap { trim: false })
+            .line_count(width);
+        if rows > view_height {
+            return idx + 1;
+        }
+    }
+    0
+}
+
 fn build_styled_line(styled_line: &StyledLine) -> Line<'_> {
     let spans: Vec<Span> = styled_line
         .spans
         .iter()
-        .map(|span| {
-            let mut style = Style::default();
-            if let Some(fg) = span.style.fg {
-                style = style.fg(fg);
-            }
-            if let Some(bg) = span.style.bg {
-                style = style.bg(bg);
-            }
-            if span.style.bold {
-                style = style.add_modifier(Modifier::BOLD);
-            }
-            if span.style.dim {
-                style = style.add_modifier(Modifier::DIM);
-            }
-            if span.style.italic {
-                style = style.add_modifier(Modifier::ITALIC);
+        .map(|span| Span::styled(span.text.clone(), span_style(&span.style)))
+        .collect();
+    Line::from(spans)
+}
+
+/// NO_COLOR drops the colors but keeps bold, dim, italic and underline
+fn span_style(text_style: &TextStyle) -> Style {
+    let mut style = Style::default();
+    if should_color() {
+        if let Some(fg) = text_style.fg {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = text_style.bg {
+            style = style.bg(bg);
+        }
+    }
+    if text_style.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if text_style.dim {
+        style = style.add_modifier(Modifier::DIM);
+    }
+    if text_style.italic {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if text_style.underline {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    style
+}
+
+/// Like `build_styled_line`, with every occurrence of `pattern` on a yellow background
+fn build_highlighted_line<'a>(styled_line: &'a StyledLine, pattern: &str) -> Line<'a> {
+    let text: String = styled_line.spans.iter().map(|s| s.text.as_str()).collect();
+    let ranges = match_ranges(&text, pattern);
+    let highlight = Style::default().fg(Color::Black).bg(Color::Yellow);
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in &styled_line.spans {
+        let start = offset;
+        let end = offset + span.text.len();
+        offset = end;
+
+        // Split the span at match boundaries that fall inside it
+        let mut cursor = start;
+        for &(m_start, m_end) in &ranges {
+            if m_end <= cursor || m_start >= end {
+                continue;
             }
-            if span.style.underline {
-                style = style.add_modifier(Modifier::UNDERLINED);
+            let hl_start = m_start.max(cursor);
+            let hl_end = m_end.min(end);
+            if hl_start > cursor {
+                spans.push(Span::styled(
+                    span.text[cursor - start..hl_start - start].to_string(),
+                    span_style(&span.style),
+                ));
             }
-            Span::styled(span.text.clone(), style)
-        })
-        .collect();
+            spans.push(Span::styled(
+                span.text[hl_start - start..hl_end - start].to_string(),
+                highlight,
+            ));
+            cursor = hl_end;
+        }
+        if cursor < end {
+            spans.push(Span::styled(
+                span.text[cursor - start..].to_string(),
+                span_style(&span.style),
+            ));
+        }
+    }
     Line::from(spans)
 }
 
+/// Line indices containing `pattern` (case-insensitive unless it has uppercase)
+fn find_matches(lines: &[StyledLine], pattern: &str) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| {
+            let text: String = line.spans.iter().map(|s| s.text.as_str()).collect();
+            !match_ranges(&text, pattern).is_empty()
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Byte ranges of non-overlapping occurrences of `pattern` in `text`
+fn match_ranges(text: &str, pattern: &str) -> Vec<(usize, usize)> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+
+    // ASCII-only lowercasing keeps byte offsets aligned with the original text
+    let smart_case = pattern.chars().any(|c| c.is_uppercase());
+    let (haystack, needle) = if smart_case {
+        (text.to_string(), pattern.to_string())
+    } else {
+        (text.to_ascii_lowercase(), pattern.to_ascii_lowercase())
+    };
+
+    haystack
+        .match_indices(&needle)
+        .map(|(idx, m)| (idx, idx + m.len()))
+        .collect()
+}
+
 fn spinner_frame() -> &'static str {
     let millis = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -955,15 +4238,15 @@ fn collect_visible(
     node: &Node,
     indices: &mut Vec<usize>,
     bars: &mut Vec<bool>,
-    is_root: bool,
     is_last: bool,
     root_metrics: &str,
+    config: &Config,
     out: &mut Vec<VisibleEntry>,
 ) {
-    let prefix = if is_root {
+    let prefix = if indices.is_empty() {
         String::new()
     } else {
-        make_prefix(bars, is_last)
+        make_prefix(bars, is_last, &config.tree_chars)
     };
 
     let metrics = if indices.is_empty() {
@@ -977,24 +4260,32 @@ fn collect_visible(
         prefix,
         path: node.path.clone(),
         is_dir: node.is_dir,
-        icon: node.icon,
+        icon: icon_glyph(&node.icon_key, config.ascii_icons),
         icon_key: node.icon_key.clone(),
         status: node.status.clone(),
+        size: node.size.clone(),
         modified: node.modified.clone(),
         subtree_changes: node.subtree_changes,
         metrics,
         name: node.name.clone(),
         ignored: node.ignored,
+        loading: node.loading && node.expanded && node.children.is_none(),
     });
 
     if node.is_dir && node.expanded {
         if let Some(children) = &node.children {
             bars.push(!is_last);
-            let last_index = children.len().saturating_sub(1);
-            for (idx, child) in children.iter().enumerate() {
-                let child_last = idx == last_index;
+            let shown: Vec<(usize, &Node)> = children
+                .iter()
+                .enumerate()
+                .filter(|(_, child)| config.show_ignored || !child.ignored)
+                .filter(|(_, child)| !config.changes_only || has_changes(child))
+                .collect();
+            let last_index = shown.len().saturating_sub(1);
+            for (pos, (idx, child)) in shown.into_iter().enumerate() {
+                let child_last = pos == last_index;
                 indices.push(idx);
-                collect_visible(child, indices, bars, false, child_last, root_metrics, out);
+                collect_visible(child, indices, bars, child_last, root_metrics, config, out);
                 indices.pop();
             }
             bars.pop();
@@ -1002,36 +4293,52 @@ fn collect_visible(
     }
 }
 
-fn make_prefix(bars: &[bool], is_last: bool) -> String {
+fn has_changes(node: &Node) -> bool {
+    node.subtree_changes > 0 || !node.status.trim().is_empty()
+}
+
+fn make_prefix(bars: &[bool], is_last: bool, chars: &TreeChars) -> String {
     let mut prefix = String::new();
     for &bar in bars {
         if bar {
-            prefix.push('│');
+            prefix.push(chars.bar);
         } else {
             prefix.push(' ');
         }
     }
-    prefix.push(if is_last { '└' } else { '├' });
+    prefix.push(if is_last { chars.last } else { chars.branch });
     prefix
 }
 
 fn build_node(
     path: &Path,
     gitignore: &Option<Gitignore>,
+    inherited: &[Arc<Gitignore>],
     git_status: &GitStatus,
 ) -> io::Result<Node> {
     let meta = fs::symlink_metadata(path)?;
     let file_type = meta.file_type();
-    let (icon, icon_key) = icon_for(path, &meta, file_type);
+    let icon_key = entry_icon_key(path, &meta, file_type);
     let is_dir = meta.is_dir();
     let name = display_name(path, is_dir);
-    let ignored = is_ignored(gitignore, path, is_dir);
+    let ignored = is_ignored(gitignore, inherited, path, is_dir);
+    let mut gitignores = inherited.to_vec();
+    if is_dir {
+        gitignores.extend(dir_gitignore(path, gitignore));
+    }
     let status = git_status
         .map
         .get(path)
         .cloned()
         .unwrap_or_else(|| "  ".to_string());
+    let size = if is_dir {
+        String::new()
+    } else {
+        format_size(meta.len())
+    };
     let modified = format_modified(&meta);
+    let len = if is_dir { 0 } else { meta.len() };
+    let modified_at = meta.modified().ok();
     let subtree_changes = if is_dir || status.trim().is_empty() {
         0
     } else {
@@ -1043,27 +4350,78 @@ fn build_node(
         is_dir,
         expanded: false,
         children: None,
-        icon,
         icon_key,
         status,
+        size,
         modified,
+        len,
+        modified_at,
         subtree_changes,
         name,
         ignored,
+        symlink: file_type.is_symlink(),
+        gitignores,
+        loading: false,
     })
 }
 
+/// Turn a symlink to a directory into an expandable node (it keeps the link icon)
+fn follow_dir_symlink(node: &mut Node) {
+    if node.symlink && !node.is_dir && node.path.is_dir() {
+        node.is_dir = true;
+        node.name = display_name(&node.path, true);
+        node.size.clear();
+        node.len = 0;
+        node.subtree_changes = 0;
+    }
+}
+
+/// Whether descending into a followed symlink would revisit a directory that
+/// is already on the way down (its target contains one of the node's ancestors)
+fn symlink_cycle(path: &Path) -> io::Result<bool> {
+    let target = fs::canonicalize(path)?;
+    Ok(path
+        .ancestors()
+        .skip(1)
+        .filter_map(|ancestor| fs::canonicalize(ancestor).ok())
+        .any(|ancestor| ancestor.starts_with(&target)))
+}
+
 fn load_children(
     node: &mut Node,
     gitignore: &Option<Gitignore>,
     git_status: &GitStatus,
+    sort: SortOrder,
 ) -> io::Result<()> {
     if !node.is_dir {
         return Ok(());
     }
+    check_symlink_loop(node)?;
+    let children = read_children(&node.path, &node.gitignores, gitignore, git_status, sort)?;
+    set_children(node, children);
+    Ok(())
+}
+
+fn check_symlink_loop(node: &Node) -> io::Result<()> {
+    if node.symlink && symlink_cycle(&node.path)? {
+        return Err(io::Error::other(format!(
+            "symlink loop: {} points into one of its parents",
+            node.name
+        )));
+    }
+    Ok(())
+}
 
+/// Build and sort the nodes for a directory's entries
+fn read_children(
+    dir: &Path,
+    inherited: &[Arc<Gitignore>],
+    gitignore: &Option<Gitignore>,
+    git_status: &GitStatus,
+    sort: SortOrder,
+) -> io::Result<Vec<Node>> {
     let mut children = Vec::new();
-    for entry in fs::read_dir(&node.path)? {
+    for entry in fs::read_dir(dir)? {
         let entry = match entry {
             Ok(e) => e,
             Err(err) => {
@@ -1072,18 +4430,143 @@ fn load_children(
             }
         };
         let child_path = entry.path();
-        match build_node(&child_path, gitignore, git_status) {
-            Ok(child) => children.push(child),
+        match build_node(&child_path, gitignore, inherited, git_status) {
+            Ok(mut child) => {
+                if sort.follow_symlinks {
+                    follow_dir_symlink(&mut child);
+                }
+                if child.is_dir || !sort.dirs_only {
+                    children.push(child)
+                }
+            }
             Err(err) => eprintln!("warn: {}", err),
         }
     }
 
-    children.sort_by_key(|a| sort_key(&a.path));
+    if !sort.dirs_only {
+        children.extend(renamed_away(dir, inherited, git_status));
+    }
+
+    sort_children(&mut children, sort);
+    Ok(children)
+}
+
+/// Placeholder entries for files renamed out of `dir`, so both sides of a
+/// rename show up; they are named `old → new` and carry the deleted status
+fn renamed_away(dir: &Path, inherited: &[Arc<Gitignore>], git_status: &GitStatus) -> Vec<Node> {
+    git_status
+        .renamed
+        .iter()
+        .filter(|(from, _)| from.parent() == Some(dir) && fs::symlink_metadata(from).is_err())
+        .map(|(from, to)| {
+            let status = git_status
+                .map
+                .get(from)
+                .cloned()
+                .unwrap_or_else(|| "D ".to_string());
+            Node {
+                path: from.clone(),
+                is_dir: false,
+                expanded: false,
+                children: None,
+                icon_key: icon_key_for(from),
+                status,
+                size: String::new(),
+                modified: String::new(),
+                len: 0,
+                modified_at: None,
+                subtree_changes: 1,
+                name: format!("{} → {}", display_name(from, false), to),
+                ignored: false,
+                symlink: false,
+                gitignores: inherited.to_vec(),
+                loading: false,
+            }
+        })
+        .collect()
+}
+
+fn set_children(node: &mut Node, children: Vec<Node>) {
+    node.subtree_changes = children.iter().map(|child| child.subtree_changes).sum();
     node.children = Some(children);
-    if let Some(children) = node.children.as_ref() {
-        node.subtree_changes = children.iter().map(|child| child.subtree_changes).sum();
+}
+
+/// Read a directory's children on a worker thread so huge directories don't
+/// stall the UI; the entry shows a spinner until `receive_children` swaps them in
+fn load_children_async(app: &mut App, indices: &[usize]) {
+    let sort = app.sort_order();
+    let node = match node_at_mut(&mut app.root, indices) {
+        Some(node) if node.is_dir && node.children.is_none() => node,
+        _ => return,
+    };
+    if node.loading {
+        return;
     }
-    Ok(())
+    if let Err(err) = check_symlink_loop(node) {
+        node.expanded = false;
+        app.status = format!("error: {}", err);
+        return;
+    }
+
+    node.loading = true;
+    let path = node.path.clone();
+    let inherited = node.gitignores.clone();
+    let gitignore = app.gitignore.clone();
+    let git_status = app.git_status.clone();
+    let tx = app.children_tx.clone();
+    std::thread::spawn(move || {
+        let children = read_children(&path, &inherited, &gitignore, &git_status, sort);
+        let _ = tx.send((path, children));
+    });
+
+    // Most directories are read well within this, so they expand without flashing the spinner
+    receive_children(app, CHILDREN_INLINE_WAIT);
+}
+
+/// Swap in listings finished by `load_children_async` workers, keeping focus on
+/// the same entry. Nodes that were rebuilt or already reloaded meanwhile are skipped
+fn receive_children(app: &mut App, wait: Duration) {
+    let mut loaded = match app.children_rx.recv_timeout(wait) {
+        Ok(first) => vec![first],
+        Err(_) => return,
+    };
+    loaded.extend(app.children_rx.try_iter());
+
+    let focused_path = app.visible.get(app.focus).map(|entry| entry.path.clone());
+    for (path, result) in loaded {
+        let node = match node_for_path_mut(&mut app.root, &path) {
+            Some(node) if node.loading => node,
+            _ => continue,
+        };
+        node.loading = false;
+        match result {
+            Ok(children) if node.children.is_none() => set_children(node, children),
+            Ok(_) => {}
+            Err(err) => {
+                node.expanded = false;
+                app.status = format!("error: {}", err);
+            }
+        }
+    }
+    app.refresh_visible();
+
+    if let Some(path) = focused_path {
+        if let Some(idx) = app.visible.iter().position(|entry| entry.path == path) {
+            app.focus = idx;
+        }
+    }
+}
+
+fn node_for_path_mut<'a>(node: &'a mut Node, path: &Path) -> Option<&'a mut Node> {
+    if node.path == path {
+        return Some(node);
+    }
+    let child = node
+        .children
+        .as_mut()?
+        .iter_mut()
+        .find(|child| path.starts_with(&child.path))?;
+    node_for_path_mut(child, path)
 }
 
 fn node_at_mut<'a>(node: &'a mut Node, indices: &[usize]) -> Option<&'a mut Node> {
@@ -1102,31 +4585,43 @@ fn parent_at_mut<'a>(node: &'a mut Node, indices: &[usize]) -> Option<&'a mut No
     node_at_mut(node, &indices[..indices.len() - 1])
 }
 
-fn icon_for(path: &Path, meta: &fs::Metadata, file_type: fs::FileType) -> (&'static str, String) {
+fn entry_icon_key(path: &Path, meta: &fs::Metadata, file_type: fs::FileType) -> String {
     if file_type.is_symlink() {
-        let key = "symlink".to_string();
-        return (icon_for_key(&key), key);
+        return "symlink".to_string();
     }
     if meta.is_dir() {
-        let key = "directory".to_string();
-        return (icon_for_key(&key), key);
+        return "directory".to_string();
     }
-    if is_executable(meta) {
-        let key = "executable".to_string();
-        return (icon_for_key(&key), key);
+    if is_executable(path, meta) {
+        return "executable".to_string();
     }
 
-    let icon_key = icon_key_for(path);
-    (icon_for_key(&icon_key), icon_key)
+    icon_key_for(path)
 }
 
-fn is_executable(meta: &fs::Metadata) -> bool {
+#[cfg(unix)]
+fn is_executable(_path: &Path, meta: &fs::Metadata) -> bool {
     if !meta.is_file() {
         return false;
     }
     meta.permissions().mode() & 0o111 != 0
 }
 
+/// Windows has no execute bit; go by the extensions the shell runs directly
+#[cfg(windows)]
+fn is_executable(path: &Path, meta: &fs::Metadata) -> bool {
+    if !meta.is_file() {
+        return false;
+    }
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["exe", "bat", "cmd", "ps1"]
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
 fn display_name(path: &Path, is_dir: bool) -> String {
     let name = path
         .file_name()
@@ -1141,11 +4636,120 @@ fn display_name(path: &Path, is_dir: bool) -> String {
     }
 }
 
-fn sort_key(path: &Path) -> String {
+fn sort_children(children: &mut [Node], sort: SortOrder) {
+    children.sort_by(|a, b| {
+        let by_name = || {
+            if sort.case_sensitive {
+                sort_key(&a.path).cmp(sort_key(&b.path))
+            } else {
+                sort_key(&a.path)
+                    .to_lowercase()
+                    .cmp(&sort_key(&b.path).to_lowercase())
+            }
+        };
+        // Metadata was read in build_node, so this needs no extra syscalls
+        let order = match sort.mode {
+            SortMode::Name => by_name(),
+            SortMode::ModifiedDesc => b
+                .is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| b.modified_at.cmp(&a.modified_at))
+                .then_with(by_name),
+            SortMode::SizeDesc => b
+                .is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| b.len.cmp(&a.len))
+                .then_with(by_name),
+        };
+        if sort.reversed {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+}
+
+/// Re-sort every loaded directory, e.g. after the sort order changes
+fn sort_tree(node: &mut Node, sort: SortOrder) {
+    if let Some(children) = node.children.as_mut() {
+        sort_children(children, sort);
+        for child in children.iter_mut() {
+            sort_tree(child, sort);
+        }
+    }
+}
+
+fn sort_key(path: &Path) -> &str {
     path.file_name()
         .and_then(|name| name.to_str())
         .unwrap_or("")
-        .to_ascii_lowercase()
+}
+
+/// Whether names differing only in case are distinct files where `dir` lives.
+/// Looks up a case-flipped name of an existing entry without writing anything
+/// (a write would bump the mtime and wake up watchers, ours included); only when
+/// `dir` has no entry with letters does it probe with a file in the temp dir.
+fn filesystem_case_sensitive(dir: &Path) -> bool {
+    let entries = fs::read_dir(dir).into_iter().flatten().flatten();
+    let candidates = entries
+        .map(|entry| entry.path())
+        .chain(Some(dir.to_path_buf()));
+    for path in candidates {
+        if let Some(sensitive) = case_probe_existing(&path) {
+            return sensitive;
+        }
+    }
+
+    let name = format!(".texplore-case-{}", std::process::id());
+    let probe = |base: &Path| -> Option<bool> {
+        let lower = base.join(&name);
+        let upper = base.join(name.to_uppercase());
+        fs::File::create(&lower).ok()?;
+        let distinct = !upper.exists();
+        let _ = fs::remove_file(&lower);
+        Some(distinct)
+    };
+    probe(&env::temp_dir()).unwrap_or(!cfg!(target_os = "macos"))
+}
+
+/// Look up `path` under its case-flipped name: missing or another file means
+/// case matters; None when the name has no ASCII letters to flip
+fn case_probe_existing(path: &Path) -> Option<bool> {
+    let name = path.file_name()?.to_str()?;
+    let flipped: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_lowercase() {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect();
+    if flipped == name {
+        return None;
+    }
+
+    let flipped = path.with_file_name(flipped);
+    Some(!same_file(path, &flipped))
+}
+
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// No inode numbers in std on Windows; the canonical path carries the on-disk case
+#[cfg(windows)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
 }
 
 fn build_gitignore(root: &Path) -> Option<Gitignore> {
@@ -1158,6 +4762,16 @@ fn build_gitignore(root: &Path) -> Option<Gitignore> {
         let _ = builder.add(&root_ignore);
     }
 
+    // Per-clone and per-user excludes; unreadable files are skipped
+    let excludes = [git_info_exclude(&git_root), global_excludes_file()];
+    for path in excludes.iter().flatten() {
+        if path.is_file() {
+            if let Some(err) = builder.add(path) {
+                eprintln!("warn: {}", err);
+            }
+        }
+    }
+
     match builder.build() {
         Ok(ignore) => Some(ignore),
         Err(err) => {
@@ -1167,6 +4781,50 @@ fn build_gitignore(root: &Path) -> Option<Gitignore> {
     }
 }
 
+/// `.git/info/exclude`, resolved through git so linked worktrees find the shared one
+fn git_info_exclude(git_root: &Path) -> Option<PathBuf> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(git_root)
+        .args(["rev-parse", "--git-path", "info/exclude"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Some(if path.is_absolute() {
+        path
+    } else {
+        git_root.join(path)
+    })
+}
+
+/// `core.excludesfile`, or git's default `$XDG_CONFIG_HOME/git/ignore`
+fn global_excludes_file() -> Option<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let configured = std::process::Command::new("git")
+        .args(["config", "--get", "core.excludesfile"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    match configured {
+        Some(value) => match value.strip_prefix("~/") {
+            Some(rest) => home.map(|home| home.join(rest)),
+            None => Some(PathBuf::from(value)),
+        },
+        None => env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| home.map(|home| home.join(".config")))
+            .map(|dir| dir.join("git").join("ignore")),
+    }
+}
+
 fn icon_key_for(path: &Path) -> String {
     if let Some(stem) = path.file_name().and_then(|name| name.to_str()) {
         let stem_key = stem.to_string();
@@ -1333,6 +4991,25 @@ fn icon_key_for_suffix(suffix: &str) -> Option<String> {
     }
 }
 
+/// Every icon lookup goes through here so all views honor `--ascii-icons`
+fn icon_glyph(key: &str, ascii: bool) -> &'static str {
+    if ascii {
+        ascii_icon_for_key(key)
+    } else {
+        icon_for_key(key)
+    }
+}
+
+/// Single-byte markers for terminals without a Nerd Font
+fn ascii_icon_for_key(key: &str) -> &'static str {
+    match key {
+        "directory" => "/",
+        "symlink" => "@",
+        "executable" => "*",
+        _ => "-",
+    }
+}
+
 fn icon_for_key(key: &str) -> &'static str {
     match key {
         "directory" => "\u{f07b}",    // nf-fa-folder
@@ -1447,6 +5124,110 @@ fn color_for_key(key: &str) -> Color {
     }
 }
 
+/// Parsed `LS_COLORS`: file-type entries (`di`, `ln`, `ex`, `fi`) and `*.ext` rules
+struct LsColors {
+    types: HashMap<String, Style>,
+    /// Keyed by lowercased extension without the dot; may be compound (`tar.gz`)
+    extensions: HashMap<String, Style>,
+}
+
+impl LsColors {
+    fn from_env() -> Option<Self> {
+        let value = env::var("LS_COLORS").ok()?;
+        let mut types = HashMap::new();
+        let mut extensions = HashMap::new();
+        for rule in value.split(':') {
+            let (key, codes) = match rule.split_once('=') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            let style = match parse_sgr(codes) {
+                Some(style) => style,
+                None => continue,
+            };
+            if let Some(ext) = key.strip_prefix("*.") {
+                extensions.insert(ext.to_ascii_lowercase(), style);
+            } else if !key.starts_with('*') {
+                types.insert(key.to_string(), style);
+            }
+        }
+        (!types.is_empty() || !extensions.is_empty()).then_some(Self { types, extensions })
+    }
+
+    /// Like ls: directories, symlinks and executables by type, then the longest
+    /// matching extension, then `fi`
+    fn style_for(&self, entry: &VisibleEntry) -> Option<Style> {
+        let file_type = match entry.icon_key.as_str() {
+            "symlink" => Some("ln"),
+            "directory" => Some("di"),
+            "executable" => Some("ex"),
+            _ => None,
+        };
+        if let Some(file_type) = file_type {
+            return self.types.get(file_type).copied();
+        }
+        let name = entry.path.file_name()?.to_str()?.to_ascii_lowercase();
+        name.match_indices('.')
+            .filter(|(idx, _)| *idx > 0)
+            .find_map(|(idx, _)| self.extensions.get(&name[idx + 1..]))
+            .or_else(|| self.types.get("fi"))
+            .copied()
+    }
+}
+
+/// An SGR sequence like `01;34` or `38;5;208`; None when it sets nothing
+fn parse_sgr(codes: &str) -> Option<Style> {
+    let codes: Vec<u8> = codes
+        .split(';')
+        .filter_map(|code| code.parse().ok())
+        .collect();
+    let mut style = Style::default();
+    let mut set = false;
+    let mut idx = 0;
+    while idx < codes.len() {
+        let code = codes[idx];
+        idx += 1;
+        let extended = |idx: &mut usize| match codes.get(*idx) {
+            Some(5) => {
+                *idx += 2;
+                codes.get(*idx - 1).map(|&n| Color::Indexed(n))
+            }
+            Some(2) => {
+                *idx += 4;
+                match codes.get(*idx - 3..*idx) {
+                    Some(&[r, g, b]) => Some(Color::Rgb(r, g, b)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let next = match code {
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            40..=47 => style.bg(Color::Indexed(code - 40)),
+            100..=107 => style.bg(Color::Indexed(code - 100 + 8)),
+            38 => match extended(&mut idx) {
+                Some(color) => style.fg(color),
+                None => continue,
+            },
+            48 => match extended(&mut idx) {
+                Some(color) => style.bg(color),
+                None => continue,
+            },
+            _ => continue,
+        };
+        style = next;
+        set = true;
+    }
+    set.then_some(style)
+}
+
 fn color_for_status(status: &str) -> Color {
     let chars: Vec<char> = status.chars().collect();
     let x = chars.first().copied().unwrap_or(' ');
@@ -1458,6 +5239,9 @@ fn color_for_status(status: &str) -> Color {
     if x == 'D' || y == 'D' {
         return Color::Red;
     }
+    if x == 'R' || y == 'R' || x == 'C' || y == 'C' {
+        return Color::Magenta;
+    }
     if x == 'A' || y == 'A' {
         return Color::Green;
     }
@@ -1467,6 +5251,21 @@ fn color_for_status(status: &str) -> Color {
     Color::DarkGray
 }
 
+/// Format a byte count with base-1024 units and one decimal place (e.g. `1.2K`)
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit])
+}
+
 fn format_modified(meta: &fs::Metadata) -> String {
     match meta.modified() {
         Ok(time) => {
@@ -1497,54 +5296,75 @@ fn should_color() -> bool {
     env::var_os("NO_COLOR").is_none()
 }
 
-fn is_ignored(gitignore: &Option<Gitignore>, path: &Path, is_dir: bool) -> bool {
-    // Check root gitignore first
-    if let Some(ignore) = gitignore {
-        if ignore.matched_path_or_any_parents(path, is_dir).is_ignore() {
-            return true;
+fn is_ignored(
+    gitignore: &Option<Gitignore>,
+    nested: &[Arc<Gitignore>],
+    path: &Path,
+    is_dir: bool,
+) -> bool {
+    // The nearest .gitignore that mentions the path wins, like in git
+    for ignore in nested.iter().rev() {
+        match ignore.matched_path_or_any_parents(path, is_dir) {
+            m if m.is_ignore() => return true,
+            m if m.is_whitelist() => return false,
+            _ => {}
         }
     }
 
-    // Check for nested .gitignore files in parent directories
-    let file_name = match path.file_name().and_then(|n| n.to_str()) {
-        Some(name) => name,
-        None => return false,
-    };
+    match gitignore {
+        Some(ignore) => ignore.matched_path_or_any_parents(path, is_dir).is_ignore(),
+        None => false,
+    }
+}
 
-    // Walk up the directory tree looking for .gitignore files
-    let mut current = path.parent();
-    while let Some(dir) = current {
-        let gitignore_path = dir.join(".gitignore");
-        if gitignore_path.is_file() {
-            if let Ok(contents) = fs::read_to_string(&gitignore_path) {
-                for line in contents.lines() {
-                    let line = line.trim();
-                    // Skip comments and empty lines
-                    if line.is_empty() || line.starts_with('#') {
-                        continue;
-                    }
-                    // Handle /pattern (anchored to this directory)
-                    let pattern = line.trim_start_matches('/').trim_end_matches('/');
-                    if pattern == file_name {
-                        return true;
-                    }
-                }
-            }
+/// Matcher for `dir/.gitignore`, unless `dir` is the repo root (already in `gitignore`)
+fn dir_gitignore(dir: &Path, gitignore: &Option<Gitignore>) -> Option<Arc<Gitignore>> {
+    let root = gitignore.as_ref()?.path();
+    if dir == root || !dir.starts_with(root) {
+        return None;
+    }
+
+    let file = dir.join(".gitignore");
+    if !file.is_file() {
+        return None;
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    if let Some(err) = builder.add(&file) {
+        eprintln!("warn: {}", err);
+    }
+    match builder.build() {
+        Ok(ignore) => Some(Arc::new(ignore)),
+        Err(err) => {
+            eprintln!("warn: {}", err);
+            None
         }
-        current = dir.parent();
     }
+}
 
-    false
+/// Nested matchers from directories between the repo root and `dir` (exclusive)
+fn ancestor_gitignores(dir: &Path, gitignore: &Option<Gitignore>) -> Vec<Arc<Gitignore>> {
+    let mut ignores: Vec<Arc<Gitignore>> = dir
+        .ancestors()
+        .skip(1)
+        .filter_map(|ancestor| dir_gitignore(ancestor, gitignore))
+        .collect();
+    ignores.reverse();
+    ignores
 }
 
+#[derive(Clone)]
 struct GitCounts {
     staged: usize,
     unstaged: usize,
     untracked: usize,
 }
 
+#[derive(Clone)]
 struct GitStatus {
     map: HashMap<PathBuf, String>,
+    /// Rename sources (absolute) to the destination as git prints it; the
+    /// sources are listed as deleted placeholder entries (see `renamed_away`)
+    renamed: HashMap<PathBuf, String>,
     counts: GitCounts,
     ahead: usize,
     behind: usize,
@@ -1556,6 +5376,7 @@ fn load_git_status(root: &Path) -> GitStatus {
         None => {
             return GitStatus {
                 map: HashMap::new(),
+                renamed: HashMap::new(),
                 counts: GitCounts {
                     staged: 0,
                     unstaged: 0,
@@ -1580,6 +5401,7 @@ fn load_git_status(root: &Path) -> GitStatus {
         _ => {
             return GitStatus {
                 map: HashMap::new(),
+                renamed: HashMap::new(),
                 counts: GitCounts {
                     staged: 0,
                     unstaged: 0,
@@ -1591,13 +5413,36 @@ fn load_git_status(root: &Path) -> GitStatus {
         }
     };
 
+    let (map, renamed, counts) = parse_porcelain_z(&output.stdout, &git_root, root);
+    let (ahead, behind) = git_ahead_behind(&git_root);
+
+    GitStatus {
+        map,
+        renamed,
+        counts,
+        ahead,
+        behind,
+    }
+}
+
+/// Parse `git status --porcelain -z` output run at `git_root`, keeping the
+/// entries under `root`
+fn parse_porcelain_z(
+    bytes: &[u8],
+    git_root: &Path,
+    root: &Path,
+) -> (
+    HashMap<PathBuf, String>,
+    HashMap<PathBuf, String>,
+    GitCounts,
+) {
     let mut map = HashMap::new();
+    let mut renamed = HashMap::new();
     let mut counts = GitCounts {
         staged: 0,
         unstaged: 0,
         untracked: 0,
     };
-    let bytes = output.stdout;
     let mut idx = 0;
     while idx + 3 <= bytes.len() {
         let x = bytes[idx] as char;
@@ -1607,20 +5452,25 @@ fn load_git_status(root: &Path) -> GitStatus {
             idx += 1;
         }
 
-        let path1 = read_c_string(&bytes, &mut idx);
+        let path1 = read_c_string(bytes, &mut idx);
         if path1.as_os_str().is_empty() {
             continue;
         }
 
-        let mut path = path1.clone();
+        // With -z a rename or copy is `XY to\0from\0`
         if x == 'R' || x == 'C' || y == 'R' || y == 'C' {
-            let path2 = read_c_string(&bytes, &mut idx);
-            if !path2.as_os_str().is_empty() {
-                path = path2;
+            let from = read_c_string(bytes, &mut idx);
+            let from_abs = git_root.join(&from);
+            if (x == 'R' || y == 'R') && !from.as_os_str().is_empty() && from_abs.starts_with(root)
+            {
+                let x = if x == 'R' { 'D' } else { ' ' };
+                let y = if y == 'R' { 'D' } else { ' ' };
+                map.insert(from_abs.clone(), format_status(x, y));
+                renamed.insert(from_abs, path1.display().to_string());
             }
         }
 
-        let abs = git_root.join(&path);
+        let abs = git_root.join(&path1);
         if x == '?' && y == '?' {
             counts.untracked += 1;
         } else {
@@ -1637,14 +5487,7 @@ fn load_git_status(root: &Path) -> GitStatus {
         }
     }
 
-    let (ahead, behind) = git_ahead_behind(&git_root);
-
-    GitStatus {
-        map,
-        counts,
-        ahead,
-        behind,
-    }
+    (map, renamed, counts)
 }
 
 fn git_ahead_behind(git_root: &Path) -> (usize, usize) {
@@ -1723,10 +5566,11 @@ fn expand_changed_paths(
     root_path: &Path,
     gitignore: &Option<Gitignore>,
     git_status: &GitStatus,
+    sort: SortOrder,
 ) -> io::Result<()> {
     for path in git_status.map.keys() {
         if let Ok(rel) = path.strip_prefix(root_path) {
-            expand_path(root, rel, gitignore, git_status)?;
+            expand_path(root, rel, gitignore, git_status, sort)?;
         }
     }
 
@@ -1738,6 +5582,7 @@ fn expand_path(
     rel_path: &Path,
     gitignore: &Option<Gitignore>,
     git_status: &GitStatus,
+    sort: SortOrder,
 ) -> io::Result<()> {
     let mut components = rel_path.components();
     let first = match components.next() {
@@ -1748,7 +5593,7 @@ fn expand_path(
     if node.is_dir {
         node.expanded = true;
         if node.children.is_none() {
-            load_children(node, gitignore, git_status)?;
+            load_children(node, gitignore, git_status, sort)?;
         }
         if let Some(children) = node.children.as_mut() {
             if let Some(child) = children
@@ -1756,7 +5601,7 @@ fn expand_path(
                 .find(|child| child.path.file_name() == Some(first.as_os_str()))
             {
                 let rest = components.as_path();
-                expand_path(child, rest, gitignore, git_status)?;
+                expand_path(child, rest, gitignore, git_status, sort)?;
             }
         }
     }
@@ -1842,18 +5687,23 @@ fn apply_sgr(codes: &str, style: &mut TextStyle) {
             49 => style.bg = None,
             30..=37 | 90..=97 => style.fg = Some(ansi_color(codes_vec[i])),
             40..=47 | 100..=107 => style.bg = Some(ansi_color(codes_vec[i] - 10)),
-            38 => {
-                if let Some((color, consumed)) = parse_extended_color(&codes_vec[i + 1..]) {
+            // A truncated or unknown extended color (`38`, `38;5`, `48;2;1;2`) ends
+            // the sequence, so its leftover parameters aren't read as codes of their
+            // own (a stray `2` would turn on dim, a `0` would reset)
+            38 => match parse_extended_color(codes_vec.get(i + 1..).unwrap_or_default()) {
+                Some((color, consumed)) => {
                     style.fg = Some(color);
                     i += consumed;
                 }
-            }
-            48 => {
-                if let Some((color, consumed)) = parse_extended_color(&codes_vec[i + 1..]) {
+                None => break,
+            },
+            48 => match parse_extended_color(codes_vec.get(i + 1..).unwrap_or_default()) {
+                Some((color, consumed)) => {
                     style.bg = Some(color);
                     i += consumed;
                 }
-            }
+                None => break,
+            },
             _ => {}
         }
         i += 1;
@@ -1898,3 +5748,223 @@ fn ansi_color(code: u16) -> Color {
         _ => Color::White,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir holding `files` (a trailing
+    /// `/` makes a directory)
+    fn temp_tree(name: &str, files: &[&str]) -> PathBuf {
+        let root = env::temp_dir().join(format!("texplore-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in files {
+            let path = root.join(file);
+            if file.ends_with('/') {
+                fs::create_dir_all(&path).unwrap();
+            } else {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, "").unwrap();
+            }
+        }
+        fs::canonicalize(&root).unwrap()
+    }
+
+    fn test_app(root: PathBuf) -> App {
+        let config = Config {
+            refresh_interval: None,
+            ..Config::from_env()
+        };
+        let mut app = load_app(root, config, KeyMap::defaults(), None).unwrap();
+        app.refresh_visible();
+        app
+    }
+
+    fn position(app: &App, path: &Path) -> usize {
+        app.visible
+            .iter()
+            .position(|entry| entry.path == path)
+            .unwrap()
+    }
+
+    #[test]
+    fn apply_sgr_ignores_trailing_extended_color() {
+        let spans = parse_ansi_spans("\x1b[38mtext");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].text, "text");
+        assert!(spans[0].style == TextStyle::default());
+
+        let mut style = TextStyle::default();
+        apply_sgr("1;48", &mut style);
+        assert!(style.bold);
+        assert_eq!(style.bg, None);
+    }
+
+    #[test]
+    fn parse_porcelain_z_keeps_both_rename_paths() {
+        let root = Path::new("/repo");
+        let (map, renamed, counts) =
+            parse_porcelain_z(b"R  new.rs\0old.rs\0 M lib.rs\0", root, root);
+
+        assert_eq!(
+            map.get(&root.join("new.rs")).map(String::as_str),
+            Some("R ")
+        );
+        assert_eq!(
+            map.get(&root.join("old.rs")).map(String::as_str),
+            Some("D ")
+        );
+        assert_eq!(
+            renamed.get(&root.join("old.rs")).map(String::as_str),
+            Some("new.rs")
+        );
+        assert_eq!(
+            map.get(&root.join("lib.rs")).map(String::as_str),
+            Some(" M")
+        );
+        assert_eq!((counts.staged, counts.unstaged), (1, 1));
+    }
+
+    #[test]
+    fn focus_after_removal_moves_to_next_sibling() {
+        let root = temp_tree("focus", &["a", "b", "c"]);
+        let mut app = test_app(root.clone());
+        let removed = root.join("b");
+        let idx = position(&app, &removed);
+
+        let indices = app.visible[idx].indices.clone();
+        let parent = parent_at_mut(&mut app.root, &indices).unwrap();
+        parent
+            .children
+            .as_mut()
+            .unwrap()
+            .remove(*indices.last().unwrap());
+        focus_after_removal(&mut app, idx, &removed);
+
+        assert_eq!(app.visible[app.focus].path, root.join("c"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn resync_keeps_unchanged_directory_expanded() {
+        let root = temp_tree("resync", &["dir/inner", "file"]);
+        let mut app = test_app(root.clone());
+        let dir = root.join("dir");
+        let indices = app.visible[position(&app, &dir)].indices.clone();
+        let sort = app.sort_order();
+        let node = node_at_mut(&mut app.root, &indices).unwrap();
+        node.expanded = true;
+        load_children(node, &app.gitignore, &app.git_status, sort).unwrap();
+        app.refresh_visible();
+
+        resync(&mut app).unwrap();
+
+        let indices = app.visible[position(&app, &dir)].indices.clone();
+        assert!(node_at_mut(&mut app.root, &indices).unwrap().expanded);
+        position(&app, &dir.join("inner"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// A git repo with `*.log` and `build/` ignored at the root, and `keep.log`
+    /// re-included by `sub/.gitignore`
+    fn ignore_repo(name: &str) -> (PathBuf, Option<Gitignore>) {
+        let root = temp_tree(name, &["sub/"]);
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .arg(&root)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        fs::write(root.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+        fs::write(root.join("sub/.gitignore"), "!keep.log\n").unwrap();
+        let gitignore = build_gitignore(&root);
+        (root, gitignore)
+    }
+
+    #[test]
+    fn gitignore_matches_wildcards() {
+        let (root, gitignore) = ignore_repo("ignore-wildcards");
+
+        assert!(is_ignored(&gitignore, &[], &root.join("debug.log"), false));
+        assert!(is_ignored(
+            &gitignore,
+            &[],
+            &root.join("sub/debug.log"),
+            false
+        ));
+        assert!(is_ignored(&gitignore, &[], &root.join("build"), true));
+        assert!(is_ignored(
+            &gitignore,
+            &[],
+            &root.join("build/out.o"),
+            false
+        ));
+        assert!(!is_ignored(&gitignore, &[], &root.join("main.rs"), false));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn nested_gitignore_negation_overrides_root() {
+        let (root, gitignore) = ignore_repo("ignore-negation");
+        let nested: Vec<_> = dir_gitignore(&root.join("sub"), &gitignore)
+            .into_iter()
+            .collect();
+        assert_eq!(nested.len(), 1);
+
+        assert!(!is_ignored(
+            &gitignore,
+            &nested,
+            &root.join("sub/keep.log"),
+            false
+        ));
+        assert!(is_ignored(
+            &gitignore,
+            &nested,
+            &root.join("sub/other.log"),
+            false
+        ));
+        assert!(is_ignored(&gitignore, &[], &root.join("keep.log"), false));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn ascii_icons_are_single_byte() {
+        for key in [
+            "directory",
+            "symlink",
+            "executable",
+            "rust",
+            "markdown",
+            "",
+            "unknown",
+        ] {
+            assert_eq!(ascii_icon_for_key(key).len(), 1, "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn case_sensitivity_probe_does_not_write() {
+        let root = temp_tree("case", &["Readme.md"]);
+        let before = fs::metadata(&root).unwrap().modified().unwrap();
+
+        let sensitive = filesystem_case_sensitive(&root);
+
+        assert_eq!(fs::metadata(&root).unwrap().modified().unwrap(), before);
+        if cfg!(target_os = "linux") {
+            assert!(sensitive);
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn page_rows_follow_pane_and_mini_log() {
+        let root = temp_tree("page-rows", &["file"]);
+        let mut app = test_app(root.clone());
+        app.area = Rect::new(40, 0, 40, 20);
+        assert_eq!(tree_page_rows(&app), 18);
+
+        app.show_mini_log = true;
+        assert_eq!(tree_page_rows(&app), 18 - MINI_LOG_HEIGHT as isize);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,412 @@
+use anyhow::{Context, Result};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use std::fs;
+use std::io::stdout;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Action applied to a commit in the rebase TODO list
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Pick,
+    Squash,
+    Fixup,
+    Edit,
+    Drop,
+}
+
+impl Action {
+    fn keyword(&self) -> &'static str {
+        match self {
+            Action::Pick => "pick",
+            Action::Squash => "squash",
+            Action::Fixup => "fixup",
+            Action::Edit => "edit",
+            Action::Drop => "drop",
+        }
+    }
+
+    fn from_keyword(s: &str) -> Option<Self> {
+        match s {
+            "pick" | "p" => Some(Action::Pick),
+            "squash" | "s" => Some(Action::Squash),
+            "fixup" | "f" => Some(Action::Fixup),
+            "edit" | "e" => Some(Action::Edit),
+            "drop" | "d" => Some(Action::Drop),
+            _ => None,
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            Action::Pick => Action::Squash,
+            Action::Squash => Action::Fixup,
+            Action::Fixup => Action::Edit,
+            Action::Edit => Action::Drop,
+            Action::Drop => Action::Pick,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Action::Pick => Color::Green,
+            Action::Squash | Action::Fixup => Color::Yellow,
+            Action::Edit => Color::Cyan,
+            Action::Drop => Color::Red,
+        }
+    }
+}
+
+/// A single commit line from the TODO file
+struct TodoCommit {
+    action: Action,
+    hash: String,
+    subject: String,
+}
+
+/// Parse commit lines from a rebase TODO file.
+/// Returns None if the file uses commands the TUI can't represent (exec, merge, label...).
+fn parse_todo(content: &str) -> Option<Vec<TodoCommit>> {
+    let mut commits = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.splitn(3, ' ');
+        let action = Action::from_keyword(parts.next()?)?;
+        let hash = parts.next()?.to_string();
+        let subject = parts.next().unwrap_or("").to_string();
+
+        commits.push(TodoCommit {
+            action,
+            hash,
+            subject,
+        });
+    }
+
+    Some(commits)
+}
+
+struct EditorApp {
+    commits: Vec<TodoCommit>,
+    list_state: ListState,
+    message: Option<String>,
+}
+
+impl EditorApp {
+    fn new(commits: Vec<TodoCommit>) -> Self {
+        let mut list_state = ListState::default();
+        if !commits.is_empty() {
+            list_state.select(Some(0));
+        }
+        Self {
+            commits,
+            list_state,
+            message: None,
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.list_state.selected().unwrap_or(0)
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if self.commits.is_empty() {
+            return;
+        }
+        let last = self.commits.len() as isize - 1;
+        let next = (self.selected() as isize + delta).clamp(0, last);
+        self.list_state.select(Some(next as usize));
+    }
+
+    /// Move the selected commit up or down, keeping it selected
+    fn move_commit(&mut self, delta: isize) {
+        if self.commits.is_empty() {
+            return;
+        }
+        let i = self.selected();
+        let last = self.commits.len() as isize - 1;
+        let j = (i as isize + delta).clamp(0, last) as usize;
+        if i != j {
+            self.commits.swap(i, j);
+            self.list_state.select(Some(j));
+        }
+    }
+
+    fn set_action(&mut self, action: Action) {
+        let i = self.selected();
+        if let Some(commit) = self.commits.get_mut(i) {
+            commit.action = action;
+        }
+        self.message = None;
+    }
+
+    fn cycle_action(&mut self) {
+        let i = self.selected();
+        if let Some(commit) = self.commits.get_mut(i) {
+            commit.action = commit.action.next();
+        }
+        self.message = None;
+    }
+
+    /// Squash/fixup need a previous commit to fold into
+    fn validate(&self) -> Result<(), String> {
+        match self.commits.iter().find(|c| c.action != Action::Drop) {
+            Some(first) if matches!(first.action, Action::Squash | Action::Fixup) => Err(format!(
+                "Cannot {} {}: no previous commit to fold into",
+                first.action.keyword(),
+                first.hash
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn render_todo(&self) -> String {
+        self.commits
+            .iter()
+            .map(|c| format!("{} {} {}\n", c.action.keyword(), c.hash, c.subject))
+            .collect()
+    }
+}
+
+/// File to create when the user aborts, set by `ws worktree interactive-rebase`
+const ABORT_MARKER_ENV: &str = "WS_REBASE_ABORT_MARKER";
+
+/// Result of an editing session
+enum Outcome {
+    Write,
+    Abort,
+}
+
+fn main() -> Result<()> {
+    let todo_path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .context("Usage: ws-rebase-editor <git-rebase-todo>")?;
+
+    let content = fs::read_to_string(&todo_path).context("Failed to read rebase TODO file")?;
+
+    let commits = match parse_todo(&content) {
+        Some(commits) => commits,
+        None => return fallback_editor(&todo_path),
+    };
+
+    if commits.is_empty() {
+        // Nothing to edit, let git proceed with the file as-is
+        return Ok(());
+    }
+
+    let mut app = EditorApp::new(commits);
+
+    enable_raw_mode()?;
+    let mut stdout = stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run_editor_loop(&mut terminal, &mut app);
+
+    let _ = disable_raw_mode();
+    let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+
+    match result? {
+        Outcome::Write => {
+            fs::write(&todo_path, app.render_todo()).context("Failed to write rebase TODO")?;
+        }
+        Outcome::Abort => {
+            // An empty TODO list makes git abort the rebase cleanly; the marker
+            // tells `ws worktree interactive-rebase` that this was on purpose
+            fs::write(&todo_path, "").context("Failed to write rebase TODO")?;
+            if let Some(marker) = std::env::var_os(ABORT_MARKER_ENV) {
+                fs::write(marker, "").context("Failed to write abort marker")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Hand the TODO file to the user's regular editor for lists the TUI can't model
+fn fallback_editor(todo_path: &Path) -> Result<()> {
+    let editor = Command::new("git")
+        .args(["var", "GIT_EDITOR"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(todo_path)
+        .status()
+        .context("Failed to launch editor")?;
+
+    if !status.success() {
+        anyhow::bail!("Editor exited with an error");
+    }
+    Ok(())
+}
+
+fn run_editor_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut EditorApp,
+) -> Result<Outcome> {
+    loop {
+        terminal.draw(|frame| draw_editor(frame, app))?;
+
+        if event::poll(std::time::Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    if let Some(outcome) = handle_key(app, key) {
+                        return Ok(outcome);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn handle_key(app: &mut EditorApp, key: KeyEvent) -> Option<Outcome> {
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+    match key.code {
+        // Reorder (Shift+arrows, or J/K for terminals that swallow Shift)
+        KeyCode::Up if shift => app.move_commit(-1),
+        KeyCode::Down if shift => app.move_commit(1),
+        KeyCode::Char('K') => app.move_commit(-1),
+        KeyCode::Char('J') => app.move_commit(1),
+
+        // Navigation
+        KeyCode::Up | KeyCode::Char('k') => app.move_cursor(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.move_cursor(1),
+        KeyCode::Home | KeyCode::Char('g') => app.list_state.select(Some(0)),
+        KeyCode::End | KeyCode::Char('G') => {
+            app.list_state.select(Some(app.commits.len() - 1));
+        }
+
+        // Actions
+        KeyCode::Char('p') => app.set_action(Action::Pick),
+        KeyCode::Char('s') => app.set_action(Action::Squash),
+        KeyCode::Char('f') => app.set_action(Action::Fixup),
+        KeyCode::Char('e') => app.set_action(Action::Edit),
+        KeyCode::Char('d') => app.set_action(Action::Drop),
+        KeyCode::Char(' ') | KeyCode::Tab => app.cycle_action(),
+
+        // Finish
+        KeyCode::Enter | KeyCode::Char('w') => match app.validate() {
+            Ok(()) => return Some(Outcome::Write),
+            Err(msg) => app.message = Some(msg),
+        },
+        KeyCode::Esc | KeyCode::Char('q') => return Some(Outcome::Abort),
+        _ => {}
+    }
+
+    None
+}
+
+fn draw_editor(frame: &mut Frame, app: &mut EditorApp) {
+    let area = frame.area();
+
+    let chunks = Layout::vertical([
+        Constraint::Min(3),    // Commit list
+        Constraint::Length(1), // Message
+        Constraint::Length(1), // Footer
+    ])
+    .split(area);
+
+    let items: Vec<ListItem> = app
+        .commits
+        .iter()
+        .map(|commit| {
+            let dropped = commit.action == Action::Drop;
+            let subject_style = if dropped {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<7}", commit.action.keyword()),
+                    Style::default()
+                        .fg(commit.action.color())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{} ", commit.hash),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(commit.subject.as_str(), subject_style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Interactive Rebase (oldest first) "),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    frame.render_stateful_widget(list, chunks[0], &mut app.list_state);
+
+    if let Some(msg) = &app.message {
+        let message = Paragraph::new(Span::styled(
+            format!(" ✗ {}", msg),
+            Style::default().fg(Color::Red),
+        ));
+        frame.render_widget(message, chunks[1]);
+    }
+
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(Color::Cyan));
+    let footer = Paragraph::new(Line::from(vec![
+        key("j/k"),
+        Span::raw(" move  "),
+        key("Shift+↑/↓"),
+        Span::raw(" reorder  "),
+        key("p"),
+        Span::raw("ick "),
+        key("s"),
+        Span::raw("quash "),
+        key("f"),
+        Span::raw("ixup "),
+        key("e"),
+        Span::raw("dit "),
+        key("d"),
+        Span::raw("rop  "),
+        key("Enter"),
+        Span::raw(" apply  "),
+        key("q"),
+        Span::raw(" abort"),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(footer, chunks[2]);
+}
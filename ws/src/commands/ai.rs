@@ -1,4 +1,5 @@
 use crate::config::{AiTool, Config};
+use crate::git::RunLogged;
 use anyhow::{Context, Result};
 use colored::*;
 use crossterm::{
@@ -29,8 +30,9 @@ pub fn ai(tool_name: Option<String>) -> Result<()> {
     // Determine which tool to use
     let tool = if let Some(name) = tool_name {
         AiTool::from_str(&name).context(format!(
-            "Unknown AI tool: {}. Valid options: droid, claude, codex, gemini, copilot",
-            name
+            "Unknown AI tool: {}. Valid options: {}",
+            name,
+            AiTool::valid_options()
         ))?
     } else {
         // Show TUI selector
@@ -57,6 +59,7 @@ pub fn ai(tool_name: Option<String>) -> Result<()> {
     // Get current session name (works even in popup)
     let session_output = Command::new("tmux")
         .args(["display-message", "-p", "#{session_name}"])
+        .logged()
         .output()
         .context("Failed to get tmux session")?;
 
@@ -68,6 +71,7 @@ pub fn ai(tool_name: Option<String>) -> Result<()> {
     let session = if session.starts_with("popup") {
         let client_output = Command::new("tmux")
             .args(["display-message", "-p", "-t", "{last}", "#{session_name}"])
+            .logged()
             .output()
             .context("Failed to get client session")?;
         let client_session = String::from_utf8_lossy(&client_output.stdout)
@@ -92,6 +96,7 @@ pub fn ai(tool_name: Option<String>) -> Result<()> {
     // Check if target pane exists
     let check_pane = Command::new("tmux")
         .args(["has-session", "-t", &target])
+        .logged()
         .output();
 
     if check_pane.map(|o| !o.status.success()).unwrap_or(true) {
@@ -113,6 +118,7 @@ pub fn ai(tool_name: Option<String>) -> Result<()> {
     // Clear terminal and command line, then start new tool
     Command::new("tmux")
         .args(["send-keys", "-t", &target, "C-u", "clear", "Enter"])
+        .logged()
         .output()
         .context("Failed to clear terminal")?;
 
@@ -121,6 +127,7 @@ pub fn ai(tool_name: Option<String>) -> Result<()> {
 
     Command::new("tmux")
         .args(["send-keys", "-t", &target, tool.command(), "Enter"])
+        .logged()
         .output()
         .context("Failed to send new command")?;
 
@@ -139,6 +146,7 @@ fn kill_pane_processes(target: &str) -> Result<()> {
     // Get the pane's shell PID
     let pane_pid_output = Command::new("tmux")
         .args(["display-message", "-p", "-t", target, "#{pane_pid}"])
+        .logged()
         .output()
         .context("Failed to get pane PID")?;
 
@@ -151,7 +159,10 @@ fn kill_pane_processes(target: &str) -> Result<()> {
     }
 
     // Find child processes of the shell
-    let children_output = Command::new("pgrep").args(["-P", &pane_pid]).output();
+    let children_output = Command::new("pgrep")
+        .args(["-P", &pane_pid])
+        .logged()
+        .output();
 
     if let Ok(output) = children_output {
         let children = String::from_utf8_lossy(&output.stdout);
@@ -159,7 +170,10 @@ fn kill_pane_processes(target: &str) -> Result<()> {
             let child_pid = child_pid.trim();
             if !child_pid.is_empty() {
                 // Send SIGTERM first (graceful shutdown)
-                let _ = Command::new("kill").args(["-TERM", child_pid]).output();
+                let _ = Command::new("kill")
+                    .args(["-TERM", child_pid])
+                    .logged()
+                    .output();
             }
         }
     }
@@ -167,6 +181,7 @@ fn kill_pane_processes(target: &str) -> Result<()> {
     // Also send Ctrl+C as fallback (in case process ignores SIGTERM briefly)
     let _ = Command::new("tmux")
         .args(["send-keys", "-t", target, "C-c"])
+        .logged()
         .output();
 
     Ok(())
@@ -1,5 +1,5 @@
 use super::workspace::open;
-use crate::config::{AiTool, Config};
+use crate::config::{self as ws_config, AiTool, Config, Forge, MultiplexerTool};
 use crate::onboarding;
 use anyhow::{Context, Result};
 use colored::*;
@@ -22,6 +22,26 @@ pub fn config(key: Option<String>, value: Option<String>) -> Result<()> {
             };
             println!("           ({})", installed);
 
+            println!("  {} = {}", "multiplexer".cyan(), cfg.multiplexer);
+            println!("  {} = {}", "forge".cyan(), cfg.forge);
+            println!(
+                "  {} = {}",
+                "post_create_hook".cyan(),
+                cfg.post_create_hook.as_deref().unwrap_or("(none)")
+            );
+            println!("  {} = {}", "refresh_interval".cyan(), cfg.refresh_interval);
+            println!(
+                "  {} = {}",
+                "session_name_template".cyan(),
+                cfg.session_name_template()
+            );
+            println!("  {} = {}", "show_last_commit".cyan(), cfg.show_last_commit);
+            println!(
+                "  {} = {}",
+                "workspaces_dir".cyan(),
+                cfg.workspaces_dir()?.display()
+            );
+
             println!();
             println!("{}", "Available AI tools:".dimmed());
             for tool in AiTool::all() {
@@ -50,6 +70,29 @@ pub fn config(key: Option<String>, value: Option<String>) -> Result<()> {
             "ai_tool" => {
                 println!("{}", cfg.ai_tool);
             }
+            "multiplexer" => {
+                println!("{}", cfg.multiplexer);
+            }
+            "forge" => {
+                println!("{}", cfg.forge);
+            }
+            "post_create_hook" => {
+                if let Some(hook) = &cfg.post_create_hook {
+                    println!("{}", hook);
+                }
+            }
+            "refresh_interval" => {
+                println!("{}", cfg.refresh_interval);
+            }
+            "session_name_template" => {
+                println!("{}", cfg.session_name_template());
+            }
+            "show_last_commit" => {
+                println!("{}", cfg.show_last_commit);
+            }
+            "workspaces_dir" => {
+                println!("{}", cfg.workspaces_dir()?.display());
+            }
             _ => {
                 anyhow::bail!("Unknown setting: {}", k);
             }
@@ -59,8 +102,9 @@ pub fn config(key: Option<String>, value: Option<String>) -> Result<()> {
         (Some(k), Some(v)) => match k {
             "ai_tool" => {
                 let tool = AiTool::from_str(v).context(format!(
-                    "Unknown AI tool: {}. Valid options: droid, claude, codex, gemini, copilot",
-                    v
+                    "Unknown AI tool: {}. Valid options: {}",
+                    v,
+                    AiTool::valid_options()
                 ))?;
 
                 cfg.ai_tool = tool;
@@ -76,6 +120,129 @@ pub fn config(key: Option<String>, value: Option<String>) -> Result<()> {
                     );
                 }
             }
+            "multiplexer" => {
+                let options: Vec<&str> =
+                    MultiplexerTool::all().iter().map(|m| m.command()).collect();
+                let mux = MultiplexerTool::from_str(v).context(format!(
+                    "Unknown multiplexer: {}. Valid options: {}",
+                    v,
+                    options.join(", ")
+                ))?;
+
+                cfg.multiplexer = mux;
+                cfg.save()?;
+
+                println!("{} Set multiplexer to {}", "::".green().bold(), mux);
+
+                if which::which(mux.command()).is_err() {
+                    println!("{} Warning: {} is not installed", "::".yellow().bold(), mux);
+                }
+            }
+            "forge" => {
+                let options: Vec<&str> = Forge::all().iter().map(|f| f.name()).collect();
+                let forge = Forge::from_str(v).context(format!(
+                    "Unknown forge: {}. Valid options: {}",
+                    v,
+                    options.join(", ")
+                ))?;
+
+                cfg.forge = forge;
+                cfg.save()?;
+
+                println!("{} Set forge to {}", "::".green().bold(), forge);
+
+                if which::which(forge.binary()).is_err() {
+                    println!(
+                        "{} Warning: {} is not installed",
+                        "::".yellow().bold(),
+                        forge.binary()
+                    );
+                }
+            }
+            "post_create_hook" => {
+                cfg.post_create_hook = (!v.trim().is_empty()).then(|| v.to_string());
+                cfg.save()?;
+
+                match &cfg.post_create_hook {
+                    Some(hook) => {
+                        println!("{} Set post_create_hook to {}", "::".green().bold(), hook)
+                    }
+                    None => println!("{} Cleared post_create_hook", "::".green().bold()),
+                }
+            }
+            "session_name_template" => {
+                let template = v.trim();
+                if !template.is_empty()
+                    && !template.contains("{branch}")
+                    && !template.contains("{hash}")
+                {
+                    anyhow::bail!(
+                        "session_name_template must contain {{branch}} or {{hash}}, otherwise every worktree gets the same session"
+                    );
+                }
+
+                cfg.session_name_template = (!template.is_empty()).then(|| template.to_string());
+                cfg.save()?;
+
+                println!(
+                    "{} Set session_name_template to {}",
+                    "::".green().bold(),
+                    cfg.session_name_template()
+                );
+                println!(
+                    "{} Running sessions keep their old names; ws sync kills the stale ones",
+                    "::".blue().bold()
+                );
+            }
+            "refresh_interval" => {
+                cfg.refresh_interval = v
+                    .parse()
+                    .context("refresh_interval must be a number of seconds (0 disables it)")?;
+                cfg.save()?;
+
+                println!(
+                    "{} Set refresh_interval to {}s",
+                    "::".green().bold(),
+                    cfg.refresh_interval
+                );
+            }
+            "show_last_commit" => {
+                cfg.show_last_commit = match v {
+                    "true" => true,
+                    "false" => false,
+                    _ => anyhow::bail!("show_last_commit must be true or false"),
+                };
+                cfg.save()?;
+
+                println!(
+                    "{} Set show_last_commit to {}",
+                    "::".green().bold(),
+                    cfg.show_last_commit
+                );
+            }
+            "workspaces_dir" => {
+                let dir = ws_config::expand_tilde(v);
+                if !dir.is_absolute() {
+                    anyhow::bail!("workspaces_dir must be an absolute path (or start with ~)");
+                }
+
+                cfg.workspaces_dir = Some(dir.clone());
+                cfg.save()?;
+
+                println!(
+                    "{} Set workspaces_dir to {}",
+                    "::".green().bold(),
+                    dir.display()
+                );
+
+                if !ws_config::is_writable_dir(&dir) {
+                    println!(
+                        "{} Warning: {} is not writable; new worktrees will fail there",
+                        "::".yellow().bold(),
+                        dir.display()
+                    );
+                }
+            }
             _ => {
                 anyhow::bail!("Unknown setting: {}", k);
             }
@@ -118,11 +285,8 @@ pub fn init() -> Result<()> {
         let git_name = result.git_tool.name().to_string();
         let explorer_name = result.explorer_tool.name().to_string();
 
-        let config = Config {
-            ai_tool: result.ai_tool,
-            git_tool: result.git_tool,
-            explorer_tool: result.explorer_tool,
-        };
+        let config =
+            Config::with_wizard_choices(result.ai_tool, result.git_tool, result.explorer_tool);
         config.save()?;
 
         println!();
@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::git::RunLogged;
 use anyhow::Result;
 use colored::*;
 use std::process::Command;
@@ -74,7 +75,7 @@ pub fn doctor(install: bool) -> Result<()> {
     }
 
     // Check the configured AI tool's binary (not a hardcoded one)
-    let ai_tool = Config::load().unwrap_or_default().ai_tool;
+    let ai_tool = Config::load_for_current_repo().unwrap_or_default().ai_tool;
     let ai_missing = which::which(ai_tool.binary()).is_err();
     let ai_status = if ai_missing {
         all_ok = false;
@@ -122,6 +123,7 @@ pub fn doctor(install: bool) -> Result<()> {
                 println!("  Installing {}...", dep.name);
                 let result = Command::new("brew")
                     .args(["install", dep.brew_name])
+                    .logged()
                     .status();
 
                 match result {
@@ -0,0 +1,480 @@
+use crate::git::{self, RunLogged};
+use anyhow::{Context, Result};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use std::collections::HashMap;
+use std::io::stdout;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Number of commits loaded per worktree
+const LOG_LIMIT: &str = "200";
+
+/// A commit line in the right-hand log
+struct LogEntry {
+    hash: String,
+    subject: String,
+    author: String,
+    date: String,
+}
+
+/// Which side receives j/k
+#[derive(Clone, Copy, PartialEq)]
+enum Focus {
+    Worktrees,
+    Log,
+}
+
+struct LogApp {
+    worktrees: Vec<git::Worktree>,
+    main_root: PathBuf,
+    worktree_state: ListState,
+    log_state: ListState,
+    /// Logs are loaded lazily, keyed by worktree index
+    logs: HashMap<usize, Vec<LogEntry>>,
+    focus: Focus,
+    /// `git show` output and scroll offset
+    show: Option<(String, Vec<String>, u16)>,
+    /// Commit waiting for y/n before it is cherry-picked
+    pending_pick: Option<String>,
+    message: Option<(String, bool)>,
+}
+
+impl LogApp {
+    fn new(worktrees: Vec<git::Worktree>, main_root: PathBuf) -> Self {
+        let mut worktree_state = ListState::default();
+        worktree_state.select(Some(0));
+        let mut log_state = ListState::default();
+        log_state.select(Some(0));
+
+        let mut app = Self {
+            worktrees,
+            main_root,
+            worktree_state,
+            log_state,
+            logs: HashMap::new(),
+            focus: Focus::Worktrees,
+            show: None,
+            pending_pick: None,
+            message: None,
+        };
+        app.load_log();
+        app
+    }
+
+    fn selected_worktree(&self) -> usize {
+        self.worktree_state.selected().unwrap_or(0)
+    }
+
+    fn current_log(&self) -> &[LogEntry] {
+        self.logs
+            .get(&self.selected_worktree())
+            .map(|log| log.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn selected_commit(&self) -> Option<&LogEntry> {
+        self.current_log()
+            .get(self.log_state.selected().unwrap_or(0))
+    }
+
+    fn load_log(&mut self) {
+        let idx = self.selected_worktree();
+        if self.logs.contains_key(&idx) {
+            return;
+        }
+        let log = match self.worktrees.get(idx) {
+            Some(wt) => read_log(&wt.path),
+            None => Vec::new(),
+        };
+        self.logs.insert(idx, log);
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let (state, len) = match self.focus {
+            Focus::Worktrees => (&mut self.worktree_state, self.worktrees.len()),
+            Focus::Log => {
+                let len = self
+                    .logs
+                    .get(&self.worktree_state.selected().unwrap_or(0))
+                    .map_or(0, |log| log.len());
+                (&mut self.log_state, len)
+            }
+        };
+        if len == 0 {
+            return;
+        }
+        let current = state.selected().unwrap_or(0) as isize;
+        state.select(Some((current + delta).clamp(0, len as isize - 1) as usize));
+
+        if self.focus == Focus::Worktrees {
+            self.log_state.select(Some(0));
+            self.load_log();
+        }
+    }
+
+    fn open_show(&mut self) {
+        let (hash, path) = match (
+            self.selected_commit(),
+            self.worktrees.get(self.selected_worktree()),
+        ) {
+            (Some(commit), Some(wt)) => (commit.hash.clone(), wt.path.clone()),
+            _ => return,
+        };
+
+        let output = Command::new("git")
+            .current_dir(&path)
+            .args(["show", "--stat", "--patch", "--no-color", &hash])
+            .logged()
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                let lines = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(|l| l.replace('\t', "    "))
+                    .collect();
+                self.show = Some((hash, lines, 0));
+            }
+            Ok(output) => {
+                let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                self.message = Some((format!("git show failed: {}", error), false));
+            }
+            Err(e) => self.message = Some((format!("git show failed: {}", e), false)),
+        }
+    }
+
+    /// Cherry-pick into the main worktree, aborting on conflicts so it stays clean
+    fn cherry_pick(&mut self, hash: &str) {
+        let output = Command::new("git")
+            .current_dir(&self.main_root)
+            .args(["cherry-pick", hash])
+            .logged()
+            .output();
+        self.message = Some(match output {
+            Ok(output) if output.status.success() => {
+                // The main worktree's log changed
+                if let Some(idx) = self
+                    .worktrees
+                    .iter()
+                    .position(|wt| wt.path == self.main_root)
+                {
+                    self.logs.remove(&idx);
+                    self.load_log();
+                }
+                (
+                    format!("Cherry-picked {} into the main worktree", hash),
+                    true,
+                )
+            }
+            Ok(output) => {
+                let _ = Command::new("git")
+                    .current_dir(&self.main_root)
+                    .args(["cherry-pick", "--abort"])
+                    .logged()
+                    .output();
+                let error = String::from_utf8_lossy(&output.stderr);
+                let first = error.lines().next().unwrap_or("").trim().to_string();
+                (format!("Cherry-pick failed (aborted): {}", first), false)
+            }
+            Err(e) => (format!("Cherry-pick failed: {}", e), false),
+        });
+    }
+}
+
+fn read_log(path: &Path) -> Vec<LogEntry> {
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["log", "--format=%h%x1f%s%x1f%an%x1f%ar", "-n", LOG_LIMIT])
+        .logged()
+        .output();
+    let stdout = match output {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\x1f');
+            Some(LogEntry {
+                hash: parts.next()?.to_string(),
+                subject: parts.next()?.to_string(),
+                author: parts.next()?.to_string(),
+                date: parts.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Browse the git log of every worktree in a two-pane TUI
+pub fn git_log_tui() -> Result<()> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let main_root = git::get_main_worktree_root(Some(&git_root))?;
+    let worktrees = git::list_worktrees(&git_root)?;
+    if worktrees.is_empty() {
+        anyhow::bail!("No worktrees found");
+    }
+
+    let mut app = LogApp::new(worktrees, main_root);
+
+    enable_raw_mode()?;
+    let mut stdout = stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run_log_loop(&mut terminal, &mut app);
+
+    let _ = disable_raw_mode();
+    let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+
+    result
+}
+
+fn run_log_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut LogApp,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| draw_log(frame, app))?;
+
+        if event::poll(std::time::Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && handle_key(app, key.code) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Returns true when the TUI should exit
+fn handle_key(app: &mut LogApp, code: KeyCode) -> bool {
+    if let Some(hash) = app.pending_pick.take() {
+        if code == KeyCode::Char('y') {
+            app.cherry_pick(&hash);
+        } else {
+            app.message = Some((String::from("Cherry-pick canceled"), false));
+        }
+        return false;
+    }
+
+    if let Some((_, lines, scroll)) = app.show.as_mut() {
+        let max = lines.len().saturating_sub(1) as u16;
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => app.show = None,
+            KeyCode::Down | KeyCode::Char('j') => *scroll = (*scroll + 1).min(max),
+            KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ') => *scroll = (*scroll + 20).min(max),
+            KeyCode::PageUp => *scroll = scroll.saturating_sub(20),
+            KeyCode::Char('g') => *scroll = 0,
+            KeyCode::Char('G') => *scroll = max,
+            _ => {}
+        }
+        return false;
+    }
+
+    app.message = None;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => return true,
+        KeyCode::Down | KeyCode::Char('j') => app.move_cursor(1),
+        KeyCode::Up | KeyCode::Char('k') => app.move_cursor(-1),
+        KeyCode::Tab => {
+            app.focus = match app.focus {
+                Focus::Worktrees => Focus::Log,
+                Focus::Log => Focus::Worktrees,
+            }
+        }
+        KeyCode::Right | KeyCode::Char('l') => app.focus = Focus::Log,
+        KeyCode::Left | KeyCode::Char('h') => app.focus = Focus::Worktrees,
+        KeyCode::Enter => match app.focus {
+            Focus::Worktrees => app.focus = Focus::Log,
+            Focus::Log => app.open_show(),
+        },
+        KeyCode::Char('c') if app.focus == Focus::Log => {
+            let on_main = app
+                .worktrees
+                .get(app.selected_worktree())
+                .is_some_and(|wt| wt.path == app.main_root);
+            match app.selected_commit().map(|c| c.hash.clone()) {
+                Some(_) if on_main => {
+                    app.message = Some((
+                        String::from("Commit is already in the main worktree"),
+                        false,
+                    ))
+                }
+                Some(hash) => {
+                    app.message = Some((
+                        format!("Cherry-pick {} into the main worktree? y/n", hash),
+                        true,
+                    ));
+                    app.pending_pick = Some(hash);
+                }
+                None => {}
+            }
+        }
+        _ => {}
+    }
+    false
+}
+
+fn pane_block(title: String, focused: bool) -> Block<'static> {
+    let color = if focused {
+        Color::Cyan
+    } else {
+        Color::DarkGray
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(title)
+}
+
+fn draw_log(frame: &mut Frame, app: &mut LogApp) {
+    let area = frame.area();
+
+    let rows = Layout::vertical([
+        Constraint::Min(3),    // Panes
+        Constraint::Length(1), // Message
+        Constraint::Length(1), // Footer
+    ])
+    .split(area);
+    let cols =
+        Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).split(rows[0]);
+
+    let items: Vec<ListItem> = app
+        .worktrees
+        .iter()
+        .map(|wt| {
+            let style = if wt.path == app.main_root {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Span::styled(wt.branch.clone(), style))
+        })
+        .collect();
+    let highlight = Style::default()
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD);
+    let worktree_list = List::new(items)
+        .block(pane_block(
+            String::from(" Worktrees "),
+            app.focus == Focus::Worktrees,
+        ))
+        .highlight_style(highlight)
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(worktree_list, cols[0], &mut app.worktree_state);
+
+    let branch = app
+        .worktrees
+        .get(app.selected_worktree())
+        .map(|wt| wt.branch.clone())
+        .unwrap_or_default();
+    let log_items: Vec<ListItem> = app
+        .current_log()
+        .iter()
+        .map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", entry.hash),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(entry.subject.clone()),
+                Span::styled(
+                    format!("  {}, {}", entry.author, entry.date),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let log_list = List::new(log_items)
+        .block(pane_block(
+            format!(" git log: {} ", branch),
+            app.focus == Focus::Log,
+        ))
+        .highlight_style(highlight)
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(log_list, cols[1], &mut app.log_state);
+
+    if let Some((hash, lines, scroll)) = app.show.as_ref() {
+        let text: Vec<Line> = lines.iter().map(|l| show_line(l)).collect();
+        frame.render_widget(Clear, rows[0]);
+        frame.render_widget(
+            Paragraph::new(text)
+                .scroll((*scroll, 0))
+                .block(pane_block(format!(" git show {} ", hash), true)),
+            rows[0],
+        );
+    }
+
+    if let Some((msg, ok)) = &app.message {
+        let (symbol, color) = if *ok {
+            ("::", Color::Cyan)
+        } else {
+            ("✗", Color::Red)
+        };
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                format!(" {} {}", symbol, msg),
+                Style::default().fg(color),
+            )),
+            rows[1],
+        );
+    }
+
+    let key = |k: &'static str| Span::styled(k, Style::default().fg(Color::Cyan));
+    let footer = if app.show.is_some() {
+        Line::from(vec![
+            key("j/k"),
+            Span::raw(" scroll  "),
+            key("g/G"),
+            Span::raw(" top/bottom  "),
+            key("q"),
+            Span::raw(" close"),
+        ])
+    } else {
+        Line::from(vec![
+            key("j/k"),
+            Span::raw(" move  "),
+            key("h/l/Tab"),
+            Span::raw(" switch pane  "),
+            key("Enter"),
+            Span::raw(" show  "),
+            key("c"),
+            Span::raw(" cherry-pick to main  "),
+            key("q"),
+            Span::raw(" quit"),
+        ])
+    };
+    frame.render_widget(Paragraph::new(footer).alignment(Alignment::Center), rows[2]);
+}
+
+/// Diff-aware coloring for `git show` lines
+fn show_line(line: &str) -> Line<'_> {
+    let style = if line.starts_with("+++") || line.starts_with("---") {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else if line.starts_with('+') {
+        Style::default().fg(Color::Green)
+    } else if line.starts_with('-') {
+        Style::default().fg(Color::Red)
+    } else if line.starts_with("@@") {
+        Style::default().fg(Color::Cyan)
+    } else if line.starts_with("commit ") {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    Line::from(Span::styled(line, style))
+}
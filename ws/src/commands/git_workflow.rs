@@ -1,14 +1,18 @@
 use super::workspace::open;
-use super::{get_session_name, get_workspaces_dir};
-use crate::git;
-use crate::tmux;
+use super::{format_utc_timestamp, get_session_name, get_workspaces_dir};
+use crate::config::{Config, Forge};
+use crate::deps::Dependencies;
+use crate::forge;
+use crate::git::{self, RunLogged};
+use crate::mux;
 use anyhow::{Context, Result};
 use colored::*;
 use std::io::{self, BufRead, Write};
 use std::process::Command;
 
-/// Clone a repository and set up workspace structure
-pub fn clone_repo(url: &str) -> Result<()> {
+/// Clone a repository and set up workspace structure: `<repo>/main` is the
+/// clone itself, or with `bare` a worktree of `<repo>/.bare`
+pub fn clone_repo(url: &str, depth: Option<u32>, bare: bool) -> Result<()> {
     // Extract repo name from URL
     let repo_name = url
         .trim_end_matches('/')
@@ -35,16 +39,39 @@ pub fn clone_repo(url: &str) -> Result<()> {
     // Clone the repository
     println!("{} Cloning {}...", "::".blue().bold(), url);
     let main_dir = repo_dir.join("main");
+    let depth_args: Vec<String> = depth
+        .map(|n| vec!["--depth".to_string(), n.to_string()])
+        .unwrap_or_default();
+
+    let cloned = if bare {
+        clone_bare(url, &repo_dir, &main_dir, &depth_args)
+    } else {
+        // --depth implies --single-branch, which would hide the branches
+        // `ws review` and `ws new --from` need
+        let single_branch_args: &[&str] = if depth.is_some() {
+            &["--no-single-branch"]
+        } else {
+            &[]
+        };
+        let result = Command::new("git")
+            .arg("clone")
+            .args(&depth_args)
+            .args(single_branch_args)
+            .args([url, main_dir.to_str().unwrap()])
+            .logged()
+            .status()
+            .context("Failed to run git clone")?;
+        if result.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to clone repository"))
+        }
+    };
 
-    let result = Command::new("git")
-        .args(["clone", url, main_dir.to_str().unwrap()])
-        .status()
-        .context("Failed to run git clone")?;
-
-    if !result.success() {
+    if let Err(e) = cloned {
         // Clean up on failure
         let _ = std::fs::remove_dir_all(&repo_dir);
-        anyhow::bail!("Failed to clone repository");
+        return Err(e);
     }
 
     println!("{} Cloned to {}", "::".green().bold(), main_dir.display());
@@ -53,54 +80,93 @@ pub fn clone_repo(url: &str) -> Result<()> {
     open(Some(main_dir.display().to_string()))
 }
 
-/// Create a pull request from current worktree
-pub fn pr_create() -> Result<()> {
-    // Check if gh is installed
-    if which::which("gh").is_err() {
-        anyhow::bail!("GitHub CLI (gh) is required. Install with: brew install gh");
+/// Bare clone into `<repo>/.bare`, point `<repo>/.git` at it, and add the
+/// default branch as the `main` worktree
+fn clone_bare(
+    url: &str,
+    repo_dir: &std::path::Path,
+    main_dir: &std::path::Path,
+    depth_args: &[String],
+) -> Result<()> {
+    let bare_dir = repo_dir.join(".bare");
+    let result = Command::new("git")
+        .args(["clone", "--bare"])
+        .args(depth_args)
+        .args([url, bare_dir.to_str().unwrap()])
+        .logged()
+        .status()
+        .context("Failed to run git clone --bare")?;
+    if !result.success() {
+        anyhow::bail!("Failed to clone repository");
     }
 
-    let git_root = git::get_root(None).context("Not in a git repository")?;
+    // git commands run from <repo> itself then find the bare repository
+    std::fs::write(repo_dir.join(".git"), "gitdir: ./.bare\n")
+        .context("Failed to write .git file")?;
+
+    // Bare clones map no remote-tracking branches, which worktrees rely on
+    git::run_git(
+        repo_dir,
+        &[
+            "config",
+            "remote.origin.fetch",
+            "+refs/heads/*:refs/remotes/origin/*",
+        ],
+    )?;
+    // A shallow bare clone only has the default branch; fetch the rest just as shallow
+    let mut fetch = vec!["fetch", "origin"];
+    fetch.extend(depth_args.iter().map(String::as_str));
+    git::run_git(repo_dir, &fetch)?;
+    // Set origin/HEAD so the default branch is detected like in a normal clone
+    git::run_git(repo_dir, &["remote", "set-head", "origin", "--auto"])?;
+
+    let default_branch = git::run_git(repo_dir, &["symbolic-ref", "--short", "HEAD"])?
+        .trim()
+        .to_string();
+    git::run_git(
+        repo_dir,
+        &[
+            "worktree",
+            "add",
+            main_dir.to_str().unwrap(),
+            &default_branch,
+        ],
+    )?;
+    // The bare clone's branches track nothing, so `git pull` in main would fail
+    git::run_git(
+        repo_dir,
+        &[
+            "branch",
+            &format!("--set-upstream-to=origin/{}", default_branch),
+            &default_branch,
+        ],
+    )?;
 
-    // Run gh pr create interactively
-    println!("{} Creating pull request...", "::".blue().bold());
+    Ok(())
+}
 
-    let status = Command::new("gh")
-        .current_dir(&git_root)
-        .args(["pr", "create", "--web"])
-        .status()
-        .context("Failed to run gh pr create")?;
+/// Create a pull request from current worktree
+pub fn pr_create() -> Result<()> {
+    let forge = forge::current();
+    forge::require(forge)?;
 
-    if !status.success() {
-        anyhow::bail!("Failed to create pull request");
-    }
+    let git_root = git::get_root(None).context("Not in a git repository")?;
 
-    Ok(())
+    // Open the forge's create form in the browser
+    println!("{} Creating pull request...", "::".blue().bold());
+    forge::create_web(forge, &git_root)
 }
 
 /// List PRs for branches with worktrees
 pub fn pr_list() -> Result<()> {
-    // Check if gh is installed
-    if which::which("gh").is_err() {
-        anyhow::bail!("GitHub CLI (gh) is required. Install with: brew install gh");
-    }
+    let forge = forge::current();
+    forge::require(forge)?;
 
     let git_root = git::get_root(None).context("Not in a git repository")?;
     let worktrees = git::list_worktrees(&git_root)?;
 
     // Get all open PRs
-    let output = Command::new("gh")
-        .current_dir(&git_root)
-        .args(["pr", "list", "--json", "number,title,headRefName,state,url"])
-        .output()
-        .context("Failed to run gh pr list")?;
-
-    if !output.status.success() {
-        anyhow::bail!("Failed to list pull requests");
-    }
-
-    let prs: Vec<serde_json::Value> =
-        serde_json::from_slice(&output.stdout).unwrap_or_else(|_| Vec::new());
+    let prs = forge::list(forge, &git_root, None, 30)?;
 
     // Build set of worktree branches
     let worktree_branches: std::collections::HashSet<String> =
@@ -111,23 +177,18 @@ pub fn pr_list() -> Result<()> {
 
     let mut found = false;
     for pr in prs {
-        let branch = pr["headRefName"].as_str().unwrap_or("");
-        if worktree_branches.contains(branch) {
+        if worktree_branches.contains(&pr.branch) {
             found = true;
-            let number = pr["number"].as_u64().unwrap_or(0);
-            let title = pr["title"].as_str().unwrap_or("");
-            let state = pr["state"].as_str().unwrap_or("");
-            let url = pr["url"].as_str().unwrap_or("");
 
-            let state_color = match state {
+            let state_color = match pr.state.as_str() {
                 "OPEN" => "●".green(),
                 "MERGED" => "●".magenta(),
                 "CLOSED" => "●".red(),
                 _ => "○".white(),
             };
 
-            println!("  {} #{} {}", state_color, number, title);
-            println!("    {} → {}", branch.cyan(), url.dimmed());
+            println!("  {} #{} {}", state_color, pr.number, pr.title);
+            println!("    {} → {}", pr.branch.cyan(), pr.url.dimmed());
             println!();
         }
     }
@@ -139,42 +200,90 @@ pub fn pr_list() -> Result<()> {
     Ok(())
 }
 
-/// Review a pull request in a new worktree
-pub fn review(pr_number: u32) -> Result<()> {
-    // Check if gh is installed
-    if which::which("gh").is_err() {
-        anyhow::bail!("GitHub CLI (gh) is required. Install with: brew install gh");
-    }
+/// Parse a PR reference: `123`, `#123`, `!123`, or a GitHub/GitLab PR URL
+pub fn parse_pr_ref(input: &str) -> Result<u32> {
+    let input = input.trim();
+    let number = if input.contains("://") {
+        let (_, number) = parse_pr_url(input).with_context(|| {
+            format!(
+                "Not a pull request URL: {} (expected .../pull/<n> or .../-/merge_requests/<n>)",
+                input
+            )
+        })?;
+        number
+    } else {
+        input.trim_start_matches(['#', '!'])
+    };
+
+    number
+        .parse::<u32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| {
+            format!(
+                "Invalid PR reference: {} (use 123, #123, or a PR URL)",
+                input
+            )
+        })
+}
 
-    let git_root = git::get_root(None).context("Not in a git repository")?;
+/// Split a PR URL into the repository path (`owner/repo`) and the PR number
+fn parse_pr_url(url: &str) -> Option<(String, &str)> {
+    let rest = url.split_once("://")?.1;
+    let rest = rest.split(['?', '#']).next()?;
+    let segments: Vec<&str> = rest.split('/').skip(1).filter(|s| !s.is_empty()).collect();
+    let idx = segments
+        .iter()
+        .position(|s| *s == "pull" || *s == "pulls" || *s == "merge_requests")?;
+    let number = segments.get(idx + 1)?;
+    let repo = segments[..idx]
+        .iter()
+        .filter(|s| **s != "-")
+        .copied()
+        .collect::<Vec<_>>()
+        .join("/");
+    (!repo.is_empty()).then_some((repo, *number))
+}
 
-    // Get PR info
-    println!("{} Fetching PR #{}...", "::".blue().bold(), pr_number);
+/// Repository path (`owner/repo`) of a remote URL, for https and scp-style URLs
+fn remote_repo_path(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/').trim_end_matches(".git");
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map(|(_, path)| path).unwrap_or(""),
+        None => url.split_once(':').map(|(_, path)| path).unwrap_or(url),
+    };
+    path.trim_matches('/').to_string()
+}
 
-    let output = Command::new("gh")
-        .current_dir(&git_root)
-        .args([
-            "pr",
-            "view",
-            &pr_number.to_string(),
-            "--json",
-            "headRefName,title",
-        ])
-        .output()
-        .context("Failed to get PR info")?;
+/// Review a pull request in a new worktree
+pub fn review(pr_ref: &str) -> Result<()> {
+    let pr_number = parse_pr_ref(pr_ref)?;
+    let forge = forge::current();
+    forge::require(forge)?;
 
-    if !output.status.success() {
-        anyhow::bail!("Failed to get PR info. Make sure PR #{} exists.", pr_number);
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+
+    // A pasted URL must point at this repository
+    if let Some((repo, _)) = parse_pr_url(pr_ref.trim()) {
+        if let Some(origin) = git::get_remote_url(&git_root, "origin") {
+            let origin_repo = remote_repo_path(&origin);
+            if !origin_repo.eq_ignore_ascii_case(&repo) {
+                anyhow::bail!(
+                    "{} is a PR in {}, but origin is {}",
+                    pr_ref.trim(),
+                    repo,
+                    origin_repo
+                );
+            }
+        }
     }
 
-    let pr: serde_json::Value =
-        serde_json::from_slice(&output.stdout).context("Failed to parse PR info")?;
+    // Get PR info
+    println!("{} Fetching PR #{}...", "::".blue().bold(), pr_number);
 
-    let branch = pr["headRefName"]
-        .as_str()
-        .context("PR has no branch")?
-        .to_string();
-    let title = pr["title"].as_str().unwrap_or("").to_string();
+    let pr = forge::view(forge, &git_root, pr_number)?;
+    let branch = pr.branch;
+    let title = pr.title;
 
     println!("{} PR: {}", "::".blue().bold(), title);
     println!("{} Branch: {}", "::".blue().bold(), branch);
@@ -191,22 +300,40 @@ pub fn review(pr_number: u32) -> Result<()> {
     // Fetch the PR branch
     println!("{} Fetching PR branch...", "::".blue().bold());
 
-    let fetch_result = Command::new("gh")
-        .current_dir(&git_root)
-        .args(["pr", "checkout", &pr_number.to_string(), "--detach"])
-        .status();
-
-    // We just need the ref to exist, checkout to detach is fine
-    if fetch_result.is_err() {
-        // Try alternative: fetch the branch directly
-        let _ = Command::new("git")
-            .current_dir(&git_root)
-            .args([
-                "fetch",
-                "origin",
-                &format!("pull/{}/head:{}", pr_number, branch),
-            ])
-            .status();
+    match forge {
+        Forge::GitHub => {
+            let fetch_result = Command::new("gh")
+                .current_dir(&git_root)
+                .args(["pr", "checkout", &pr_number.to_string(), "--detach"])
+                .logged()
+                .status();
+
+            // We just need the ref to exist, checkout to detach is fine
+            if fetch_result.is_err() {
+                // Try alternative: fetch the branch directly
+                let _ = Command::new("git")
+                    .current_dir(&git_root)
+                    .args([
+                        "fetch",
+                        "origin",
+                        &format!("pull/{}/head:{}", pr_number, branch),
+                    ])
+                    .logged()
+                    .status();
+            }
+        }
+        Forge::GitLab => {
+            // glab mr checkout would switch the main worktree, so fetch the MR ref instead
+            let _ = Command::new("git")
+                .current_dir(&git_root)
+                .args([
+                    "fetch",
+                    "origin",
+                    &format!("merge-requests/{}/head:{}", pr_number, branch),
+                ])
+                .logged()
+                .status();
+        }
     }
 
     // Create worktree for the PR branch
@@ -230,6 +357,7 @@ pub fn review(pr_number: u32) -> Result<()> {
     let result = Command::new("git")
         .current_dir(&git_root)
         .args(["worktree", "add", wt_path.to_str().unwrap(), &branch])
+        .logged()
         .output()?;
 
     if !result.status.success() {
@@ -242,6 +370,7 @@ pub fn review(pr_number: u32) -> Result<()> {
                 wt_path.to_str().unwrap(),
                 &format!("origin/{}", branch),
             ])
+            .logged()
             .output()?;
 
         if !result.status.success() {
@@ -260,7 +389,7 @@ pub fn review(pr_number: u32) -> Result<()> {
 }
 
 /// Garbage collect merged branches and their worktrees
-pub fn gc(force: bool) -> Result<()> {
+pub fn gc(force: bool, dry_run: bool, preserve_groups: &[String]) -> Result<()> {
     let git_root = git::get_root(None).context("Not in a git repository")?;
     let worktrees = git::list_worktrees(&git_root)?;
     let default_branch = git::get_default_branch(Some(&git_root));
@@ -269,6 +398,7 @@ pub fn gc(force: bool) -> Result<()> {
     let output = Command::new("git")
         .current_dir(&git_root)
         .args(["branch", "--merged", &format!("origin/{}", default_branch)])
+        .logged()
         .output()
         .context("Failed to list merged branches")?;
 
@@ -294,13 +424,61 @@ pub fn gc(force: bool) -> Result<()> {
         })
         .collect();
 
+    // Keep branches that belong to a preserved group
+    let (to_delete, preserved): (Vec<_>, Vec<_>) = if preserve_groups.is_empty() {
+        (to_delete, Vec::new())
+    } else {
+        let config = Config::load_for_repo(&git_root)?;
+        let mut members = Vec::new();
+        for group in preserve_groups {
+            match config.groups.get(group) {
+                Some(branches) => members.extend(branches.iter().map(|b| Config::resolve_alias(b))),
+                None => anyhow::bail!("No group named {}", group),
+            }
+        }
+        to_delete
+            .into_iter()
+            .partition(|wt| !members.contains(&wt.branch))
+    };
+
+    for wt in &preserved {
+        println!(
+            "{} Keeping {} (in a preserved group)",
+            "::".yellow().bold(),
+            wt.branch
+        );
+    }
+
+    // Keep merged branches that an unmerged branch still depends on
+    let mut deps = git::get_repo_name(Some(&git_root))
+        .and_then(|name| Dependencies::load(&name))
+        .unwrap_or_default();
+    let (to_delete, blocked): (Vec<_>, Vec<_>) = to_delete.into_iter().partition(|wt| {
+        !deps.dependents_of(&wt.branch).iter().any(|dependent| {
+            !merged_branches.contains(*dependent) && git::branch_exists(&git_root, dependent)
+        })
+    });
+
+    for wt in &blocked {
+        println!(
+            "{} Keeping {} (required by {})",
+            "::".yellow().bold(),
+            wt.branch,
+            deps.dependents_of(&wt.branch).join(", ")
+        );
+    }
+
     if to_delete.is_empty() {
         println!("{} No merged worktrees to clean up!", "::".green().bold());
+        if dry_run {
+            return Ok(());
+        }
 
         // Still prune any dangling worktree refs
         let _ = Command::new("git")
             .current_dir(&git_root)
             .args(["worktree", "prune"])
+            .logged()
             .status();
 
         return Ok(());
@@ -314,6 +492,15 @@ pub fn gc(force: bool) -> Result<()> {
     }
     println!();
 
+    if dry_run {
+        println!(
+            "{} Dry run, nothing deleted ({} worktree(s) would be removed)",
+            "::".blue().bold(),
+            to_delete.len()
+        );
+        return Ok(());
+    }
+
     if !force {
         print!(
             "Delete {} worktree(s) and their branches? [y/N]: ",
@@ -332,11 +519,12 @@ pub fn gc(force: bool) -> Result<()> {
     }
 
     let mut deleted = 0;
+    let mut deps_changed = false;
     for wt in to_delete {
         // Kill tmux session if exists
         if let Ok(session) = get_session_name(&wt.path) {
-            if tmux::session_exists(&session) {
-                let _ = tmux::kill_session(&session);
+            if mux::backend().session_exists(&session) {
+                let _ = mux::backend().kill_session(&session);
             }
         }
 
@@ -355,13 +543,19 @@ pub fn gc(force: bool) -> Result<()> {
         let _ = git::delete_branch(&git_root, &wt.branch, true);
 
         println!("{} Deleted {}", "::".green().bold(), wt.branch);
+        deps_changed |= deps.forget(&wt.branch);
         deleted += 1;
     }
 
+    if deps_changed {
+        let _ = deps.save();
+    }
+
     // Prune worktree refs
     let _ = Command::new("git")
         .current_dir(&git_root)
         .args(["worktree", "prune"])
+        .logged()
         .status();
 
     println!();
@@ -373,3 +567,337 @@ pub fn gc(force: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// A version field to bump in a project file
+struct VersionBump {
+    path: String,
+    old: String,
+    content: String,
+}
+
+/// Prepare a release: worktree, version bump, changelog, commit, push, and PR
+pub fn create_release(version: &str, dry_run: bool) -> Result<()> {
+    let version = version.trim_start_matches('v');
+    if !version.starts_with(|c: char| c.is_ascii_digit())
+        || !version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+')
+    {
+        anyhow::bail!("Invalid version: {} (expected e.g. 1.2.3)", version);
+    }
+
+    if !dry_run && which::which("gh").is_err() {
+        anyhow::bail!("GitHub CLI (gh) is required. Install with: brew install gh");
+    }
+
+    let git_root = git::get_main_worktree_root(None).context("Not in a git repository")?;
+    let default_branch = git::get_default_branch(Some(&git_root));
+    let base = format!("origin/{}", default_branch);
+    let branch = format!("release/{}", version);
+
+    let _ = Command::new("git")
+        .current_dir(&git_root)
+        .args(["fetch", "origin", &default_branch, "--tags"])
+        .logged()
+        .output();
+
+    if dry_run {
+        println!(
+            "{} Dry run: release {} from {}",
+            "::".blue().bold(),
+            version,
+            base
+        );
+
+        // Read files from the base branch so nothing on disk changes
+        let read = |path: &str| -> Option<String> {
+            let output = Command::new("git")
+                .current_dir(&git_root)
+                .args(["show", &format!("{}:{}", base, path)])
+                .logged()
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+        };
+
+        println!();
+        println!("  {} Create worktree for '{}'", "○".yellow(), branch);
+        for bump in plan_version_bumps(&read, version) {
+            println!(
+                "  {} {}: {} → {}",
+                "○".yellow(),
+                bump.path,
+                bump.old,
+                version
+            );
+        }
+        let entry = changelog_entry(&git_root, &base, version);
+        println!("  {} CHANGELOG.md entry:", "○".yellow());
+        println!();
+        for line in entry.lines() {
+            println!("      {}", line);
+        }
+        println!("  {} Commit, push '{}', open PR", "○".yellow(), branch);
+        return Ok(());
+    }
+
+    let repo_name = git_root
+        .file_name()
+        .context("Invalid git root")?
+        .to_string_lossy()
+        .to_string();
+    let wt_path = get_workspaces_dir()?
+        .join(&repo_name)
+        .join(git::sanitize_branch(&branch));
+
+    if wt_path.exists() {
+        anyhow::bail!("Worktree already exists at {}", wt_path.display());
+    }
+
+    println!(
+        "{} Creating worktree '{}' from '{}'...",
+        "::".blue().bold(),
+        branch,
+        default_branch
+    );
+    std::fs::create_dir_all(wt_path.parent().context("Invalid worktree path")?)?;
+    git::create_worktree(&git_root, &branch, &default_branch, &wt_path)?;
+
+    let read = |path: &str| std::fs::read_to_string(wt_path.join(path)).ok();
+    let bumps = plan_version_bumps(&read, version);
+    if bumps.is_empty() {
+        println!(
+            "{} No Cargo.toml, package.json, or pyproject.toml version found",
+            "::".yellow().bold()
+        );
+    }
+    for bump in &bumps {
+        std::fs::write(wt_path.join(&bump.path), &bump.content)
+            .context(format!("Failed to update {}", bump.path))?;
+        println!(
+            "{} {}: {} → {}",
+            "::".green().bold(),
+            bump.path,
+            bump.old,
+            version
+        );
+    }
+
+    let entry = changelog_entry(&wt_path, "HEAD", version);
+    let changelog_path = wt_path.join("CHANGELOG.md");
+    let changelog = std::fs::read_to_string(&changelog_path).unwrap_or_default();
+    std::fs::write(&changelog_path, insert_changelog_entry(&changelog, &entry))
+        .context("Failed to update CHANGELOG.md")?;
+    println!("{} Updated CHANGELOG.md", "::".green().bold());
+
+    let message = format!("Release {}", version);
+    for args in [
+        vec!["add", "-A"],
+        vec!["commit", "-m", &message],
+        vec!["push", "-u", "origin", &branch],
+    ] {
+        let status = Command::new("git")
+            .current_dir(&wt_path)
+            .args(&args)
+            .logged()
+            .status()
+            .context("Failed to run git")?;
+        if !status.success() {
+            anyhow::bail!("git {} failed in {}", args[0], wt_path.display());
+        }
+    }
+
+    let status = Command::new("gh")
+        .current_dir(&wt_path)
+        .args([
+            "pr",
+            "create",
+            "--base",
+            &default_branch,
+            "--head",
+            &branch,
+            "--title",
+            &message,
+            "--body",
+            &entry,
+        ])
+        .logged()
+        .status()
+        .context("Failed to run gh pr create")?;
+
+    if !status.success() {
+        anyhow::bail!("Failed to create pull request");
+    }
+
+    println!(
+        "{} Release {} prepared in {}",
+        "::".green().bold(),
+        version,
+        wt_path.display()
+    );
+    Ok(())
+}
+
+/// Find version fields to bump, reading files through `read` (relative paths)
+fn plan_version_bumps(read: &dyn Fn(&str) -> Option<String>, version: &str) -> Vec<VersionBump> {
+    let mut bumps = Vec::new();
+    let mut push = |path: String, bumped: Option<(String, String)>| {
+        if let Some((old, content)) = bumped {
+            bumps.push(VersionBump { path, old, content });
+        }
+    };
+
+    if let Some(cargo) = read("Cargo.toml") {
+        // Workspace members keep their own versions unless they inherit one
+        for member in cargo_workspace_members(&cargo) {
+            let path = format!("{}/Cargo.toml", member);
+            if let Some(content) = read(&path) {
+                push(path, bump_toml_version(&content, &["package"], version));
+            }
+        }
+        push(
+            "Cargo.toml".to_string(),
+            bump_toml_version(&cargo, &["package", "workspace.package"], version),
+        );
+    }
+
+    if let Some(package) = read("package.json") {
+        push(
+            "package.json".to_string(),
+            bump_json_version(&package, version),
+        );
+    }
+
+    if let Some(pyproject) = read("pyproject.toml") {
+        push(
+            "pyproject.toml".to_string(),
+            bump_toml_version(&pyproject, &["project", "tool.poetry"], version),
+        );
+    }
+
+    bumps
+}
+
+/// Member paths from a `[workspace] members = [...]` list (globs are skipped)
+fn cargo_workspace_members(cargo: &str) -> Vec<String> {
+    let start = match cargo.find("members") {
+        Some(i) => i,
+        None => return Vec::new(),
+    };
+    let list = &cargo[start..];
+    let list = match (list.find('['), list.find(']')) {
+        (Some(open), Some(close)) if open < close => &list[open + 1..close],
+        _ => return Vec::new(),
+    };
+
+    list.split(',')
+        .map(|m| m.trim().trim_matches('"').to_string())
+        .filter(|m| !m.is_empty() && !m.contains('*'))
+        .collect()
+}
+
+/// Replace the first `version = "..."` inside one of `sections`, keeping formatting
+fn bump_toml_version(content: &str, sections: &[&str], version: &str) -> Option<(String, String)> {
+    let mut section = String::new();
+    let mut old = None;
+    let mut out = String::with_capacity(content.len());
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && !trimmed.starts_with("[[") {
+            section = trimmed.trim_matches(|c| c == '[' || c == ']').to_string();
+        }
+
+        if old.is_none() && sections.contains(&section.as_str()) {
+            if let Some((key, value)) = trimmed.split_once('=') {
+                let value = value.trim();
+                if key.trim() == "version" && value.starts_with('"') {
+                    old = Some(value.trim_matches('"').to_string());
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    let newline = if line.ends_with('\n') { "\n" } else { "" };
+                    out.push_str(&format!("{}version = \"{}\"{}", indent, version, newline));
+                    continue;
+                }
+            }
+        }
+        out.push_str(line);
+    }
+
+    old.map(|old| (old, out))
+}
+
+/// Replace the first `"version": "..."` in a package.json
+fn bump_json_version(content: &str, version: &str) -> Option<(String, String)> {
+    let key = content.find("\"version\"")?;
+    let colon = key + content[key..].find(':')?;
+    let open = colon + content[colon..].find('"')?;
+    let close = open + 1 + content[open + 1..].find('"')?;
+
+    let old = content[open + 1..close].to_string();
+    let updated = format!(
+        "{}\"{}\"{}",
+        &content[..open],
+        version,
+        &content[close + 1..]
+    );
+    Some((old, updated))
+}
+
+/// Build a changelog entry from commits since the last tag reachable from `rev`
+fn changelog_entry(dir: &std::path::Path, rev: &str, version: &str) -> String {
+    let last_tag = Command::new("git")
+        .current_dir(dir)
+        .args(["describe", "--tags", "--abbrev=0", rev])
+        .logged()
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+
+    let range = match &last_tag {
+        Some(tag) => format!("{}..{}", tag, rev),
+        None => rev.to_string(),
+    };
+
+    let log = Command::new("git")
+        .current_dir(dir)
+        .args(["log", "--no-merges", "--pretty=format:- %s", &range])
+        .logged()
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let date = format_utc_timestamp(now)[..10].to_string();
+
+    let mut entry = format!("## [{}] - {}\n\n", version, date);
+    if log.trim().is_empty() {
+        entry.push_str("- No changes\n");
+    } else {
+        entry.push_str(log.trim_end());
+        entry.push('\n');
+    }
+    entry
+}
+
+/// Insert an entry below the changelog title (or create the file content)
+fn insert_changelog_entry(changelog: &str, entry: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("# Changelog\n\n{}", entry);
+    }
+
+    // Put the entry before the first existing release heading
+    match changelog.find("\n## ") {
+        Some(i) => format!("{}\n{}\n{}", &changelog[..i], entry, &changelog[i + 1..]),
+        None if changelog.starts_with("# ") => {
+            format!("{}\n\n{}", changelog.trim_end(), entry)
+        }
+        None => format!("{}\n{}", entry, changelog),
+    }
+}
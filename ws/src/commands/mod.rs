@@ -1,34 +1,61 @@
 mod ai;
 mod config;
 mod doctor;
+mod git_log;
 mod git_workflow;
 mod layout;
 mod status;
 mod update;
 mod workspace;
+mod worktree;
 
 pub use ai::ai;
 pub use config::{config, init};
 pub use doctor::doctor;
-pub use git_workflow::{clone_repo, gc, pr_create, pr_list, review};
+pub use git_log::git_log_tui;
+pub use git_workflow::{clone_repo, create_release, gc, pr_create, pr_list, review};
 pub use layout::layout;
 pub use status::{dashboard, status, StatusAction};
 pub use update::update;
 pub use workspace::{delete, new, open, reload, select, sync};
+pub use worktree::{
+    alias_add, alias_list, alias_remove, blame_history, checklist, copy_changes, depend_add,
+    depend_list, depend_remove, duplicate, fix_permissions, foreach, group_list, group_remove,
+    group_set, handoff, interactive_rebase, lint, migrate, open_all, performance, performance_mark,
+    remote_diff, standup, summary, time_travel,
+};
 
-use crate::git;
+use crate::config::Config;
+use crate::git::{self, RunLogged};
 use anyhow::{Context, Result};
 use std::path::Path;
 
-/// Generate session name from directory
+/// Generate session name from directory, using the configured session_name_template
 pub(crate) fn get_session_name(dir: &Path) -> Result<String> {
     let repo_name = dir
         .file_name()
         .context("Invalid directory")?
         .to_string_lossy();
     let branch = git::get_branch(dir)?;
-    let branch_safe = git::sanitize_branch(&branch);
-    Ok(format!("{}-{}", repo_name, branch_safe))
+    let config = Config::load_for_repo(dir).unwrap_or_default();
+    let template = config.session_name_template();
+
+    let mut name = template
+        .replace("{repo}", &repo_name)
+        .replace("{branch}", &git::sanitize_branch(&branch));
+    if template.contains("{hash}") {
+        let abs = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+        name = name.replace("{hash}", &path_hash(&abs));
+    }
+    Ok(name)
+}
+
+/// Short, stable hash of a path (FNV-1a, so it does not change between builds)
+fn path_hash(path: &Path) -> String {
+    let hash = path.to_string_lossy().bytes().fold(0x811c9dc5u32, |h, b| {
+        (h ^ b as u32).wrapping_mul(0x01000193)
+    });
+    format!("{:08x}", hash)
 }
 
 /// Generate window title for Ghostty tab: "repo/worktree [branch]"
@@ -56,10 +83,9 @@ pub(crate) fn get_window_title(dir: &Path) -> Result<String> {
     }
 }
 
-/// Get the workspaces directory (~/.ws/workspaces/)
+/// Get the workspaces directory (`workspaces_dir` in the config, default ~/.ws/workspaces/)
 pub(crate) fn get_workspaces_dir() -> Result<std::path::PathBuf> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    let ws_dir = home.join(".ws").join("workspaces");
+    let ws_dir = crate::config::Config::load()?.workspaces_dir()?;
 
     // Create directory if it doesn't exist
     if !ws_dir.exists() {
@@ -68,3 +94,73 @@ pub(crate) fn get_workspaces_dir() -> Result<std::path::PathBuf> {
 
     Ok(ws_dir)
 }
+
+/// Copy text to the system clipboard through `arboard`, falling back to
+/// pbcopy (macOS) or xclip when it can't be initialized
+pub(crate) fn copy_to_clipboard(
+    clipboard: &mut Option<arboard::Clipboard>,
+    text: &str,
+) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    if clipboard.is_none() {
+        *clipboard = arboard::Clipboard::new().ok();
+    }
+    if let Some(clipboard) = clipboard.as_mut() {
+        if clipboard.set_text(text).is_ok() {
+            return Ok(());
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("pbcopy");
+
+    #[cfg(not(target_os = "macos"))]
+    let mut cmd = {
+        let mut cmd = Command::new("xclip");
+        cmd.args(["-selection", "clipboard"]);
+        cmd
+    };
+
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .logged()
+        .spawn()
+        .context("Failed to run clipboard command")?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    if !child.wait()?.success() {
+        anyhow::bail!("Failed to copy to clipboard");
+    }
+    Ok(())
+}
+
+/// Format a unix timestamp as an RFC 3339 UTC timestamp (YYYY-MM-DDTHH:MM:SSZ)
+pub(crate) fn format_utc_timestamp(ts: i64) -> String {
+    let days = ts.div_euclid(86_400);
+    let secs = ts.rem_euclid(86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
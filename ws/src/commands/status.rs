@@ -1,6 +1,8 @@
 use super::get_session_name;
-use crate::git;
-use crate::tmux;
+use crate::config::{Config, Forge};
+use crate::forge;
+use crate::git::{self, RunLogged};
+use crate::mux;
 use anyhow::{Context, Result};
 use crossterm::{
     event::{
@@ -19,6 +21,7 @@ use ratatui::{
     },
     Frame, Terminal,
 };
+use serde::Serialize;
 use std::io::stdout;
 use std::path::PathBuf;
 use std::time::Instant;
@@ -62,13 +65,15 @@ impl WorkspaceMetrics {
         let num_commits = std::process::Command::new("git")
             .current_dir(git_root)
             .args(["rev-list", "--count", "HEAD"])
+            .logged()
             .output()
             .ok()
             .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok())
             .unwrap_or(100);
 
         // Count active sessions for this repo
-        let active_sessions = crate::tmux::get_active_sessions()
+        let active_sessions = crate::mux::backend()
+            .get_active_sessions()
             .iter()
             .filter(|s| s.starts_with(&format!("{}-", repo_name)))
             .count()
@@ -260,13 +265,32 @@ pub enum StatusAction {
 }
 
 /// Entry representing a worktree with its session info
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 struct WorktreeEntry {
     session: String,
     branch: String,
     path: PathBuf,
     is_main: bool,
     has_session: bool,
+    /// Uncommitted changes and upstream distance; None until the background scan reports
+    git_state: Option<GitState>,
+}
+
+/// Working tree state of a worktree, gathered off the UI thread
+#[derive(Clone, Serialize)]
+struct GitState {
+    dirty: bool,
+    ahead: u32,
+    behind: u32,
+    /// Only gathered when `show_last_commit` is on
+    last_commit: Option<LastCommit>,
+}
+
+#[derive(Clone, Serialize)]
+struct LastCommit {
+    subject: String,
+    /// Relative, e.g. "2 days ago"
+    date: String,
 }
 
 /// Input mode for the status app
@@ -280,6 +304,13 @@ enum InputMode {
     Help,
 }
 
+/// Worktree and session data collected by `StatusApp::collect`
+struct Snapshot {
+    entries: Vec<WorktreeEntry>,
+    orphaned_sessions: Vec<String>,
+    orphaned_worktrees: Vec<String>,
+}
+
 /// Result from a background task
 enum TaskResult {
     DeleteWorktree {
@@ -303,11 +334,42 @@ struct StatusApp {
     input_buffer: String,
     message: Option<(String, bool)>, // (message, is_error)
     task_receiver: Option<std::sync::mpsc::Receiver<TaskResult>>,
+    git_state_receiver: Option<std::sync::mpsc::Receiver<Vec<(PathBuf, GitState)>>>,
+    show_last_commit: bool,
+    /// Auto-refresh period (`refresh_interval` in the config); None disables it
+    refresh_interval: Option<std::time::Duration>,
+    last_refresh: Instant,
+    snapshot_receiver: Option<std::sync::mpsc::Receiver<Option<Snapshot>>>,
+    /// Opened on first copy and kept alive, since X11 selections vanish with their owner
+    clipboard: Option<arboard::Clipboard>,
     is_busy: bool,
 }
 
+/// Snapshot printed by `ws status --json`
+#[derive(Serialize)]
+struct StatusReport<'a> {
+    repo: &'a str,
+    worktrees: &'a [WorktreeEntry],
+    orphaned_sessions: &'a [String],
+    orphaned_worktrees: &'a [String],
+}
+
 impl StatusApp {
     fn new() -> Result<Self> {
+        let app = Self::load()?;
+
+        // Fetch from origin to ensure we're in sync (run in background, don't block)
+        let _ = std::process::Command::new("git")
+            .current_dir(&app.git_root)
+            .args(["fetch", "--all", "--prune", "-q"])
+            .logged()
+            .spawn();
+
+        Ok(app)
+    }
+
+    /// Collect worktree and session data without fetching
+    fn load() -> Result<Self> {
         let git_root = git::get_root(None).context("Not in a git repository")?;
         let repo_name = git_root
             .file_name()
@@ -315,12 +377,7 @@ impl StatusApp {
             .to_string_lossy()
             .to_string();
 
-        // Fetch from origin to ensure we're in sync (run in background, don't block)
-        let _ = std::process::Command::new("git")
-            .current_dir(&git_root)
-            .args(["fetch", "--all", "--prune", "-q"])
-            .spawn();
-
+        let config = Config::load_for_repo(&git_root).unwrap_or_default();
         let mut app = Self {
             entries: Vec::new(),
             table_state: TableState::default(),
@@ -334,6 +391,13 @@ impl StatusApp {
             input_buffer: String::new(),
             message: None,
             task_receiver: None,
+            git_state_receiver: None,
+            show_last_commit: config.show_last_commit,
+            refresh_interval: (config.refresh_interval > 0)
+                .then(|| std::time::Duration::from_secs(config.refresh_interval)),
+            last_refresh: Instant::now(),
+            snapshot_receiver: None,
+            clipboard: None,
             is_busy: false,
         };
         app.refresh();
@@ -342,11 +406,16 @@ impl StatusApp {
 
     /// Refresh worktree and session data
     fn refresh(&mut self) {
-        let worktrees = match git::list_worktrees(&self.git_root) {
-            Ok(wt) => wt,
-            Err(_) => return,
-        };
-        let active_sessions = tmux::get_active_sessions();
+        if let Some(snapshot) = Self::collect(&self.git_root, &self.repo_name) {
+            self.apply_snapshot(snapshot);
+        }
+    }
+
+    /// Gather worktree and session data (None if worktrees can't be listed); runs
+    /// off the UI thread for auto-refresh
+    fn collect(git_root: &std::path::Path, repo_name: &str) -> Option<Snapshot> {
+        let worktrees = git::list_worktrees(git_root).ok()?;
+        let active_sessions = mux::backend().get_active_sessions();
 
         let mut entries: Vec<WorktreeEntry> = Vec::new();
         let mut worktree_sessions: std::collections::HashSet<String> =
@@ -355,7 +424,7 @@ impl StatusApp {
         for wt in &worktrees {
             if let Ok(session_name) = get_session_name(&wt.path) {
                 let has_session = active_sessions.contains(&session_name);
-                let is_main = wt.path == self.git_root;
+                let is_main = wt.path == git_root;
 
                 if has_session {
                     worktree_sessions.insert(session_name.clone());
@@ -367,16 +436,15 @@ impl StatusApp {
                     path: wt.path.clone(),
                     is_main,
                     has_session,
+                    git_state: None,
                 });
             }
         }
 
         // Find orphaned sessions: sessions that belong to this repo but don't have worktrees
         // Sessions are named: {worktree_dir_name}-{sanitized_branch}
-        let repo_prefix = format!("{}-", self.repo_name);
-        let workspaces_dir = super::get_workspaces_dir()
-            .ok()
-            .map(|p| p.join(&self.repo_name));
+        let repo_prefix = format!("{}-", repo_name);
+        let workspaces_dir = super::get_workspaces_dir().ok().map(|p| p.join(repo_name));
 
         // Get all directories in the workspaces folder (existing worktree dirs)
         let workspace_dirs: std::collections::HashSet<String> = workspaces_dir
@@ -392,8 +460,9 @@ impl StatusApp {
 
         // Get all remote branches to help identify orphaned sessions
         let remote_branches: std::collections::HashSet<String> = std::process::Command::new("git")
-            .current_dir(&self.git_root)
+            .current_dir(git_root)
             .args(["branch", "-r", "--format=%(refname:short)"])
+            .logged()
             .output()
             .ok()
             .map(|o| {
@@ -405,7 +474,7 @@ impl StatusApp {
             })
             .unwrap_or_default();
 
-        self.orphaned_sessions = active_sessions
+        let orphaned_sessions = active_sessions
             .iter()
             .filter(|session| {
                 // Skip if this session is already tracked by a worktree
@@ -439,10 +508,10 @@ impl StatusApp {
             .cloned()
             .collect();
 
-        self.orphaned_worktrees = worktrees
+        let orphaned_worktrees = worktrees
             .iter()
             .filter(|wt| {
-                if wt.path == self.git_root {
+                if wt.path == git_root {
                     return false;
                 }
                 if let Ok(name) = get_session_name(&wt.path) {
@@ -454,13 +523,67 @@ impl StatusApp {
             .map(|wt| wt.branch.clone())
             .collect();
 
-        // Preserve selection if possible
+        Some(Snapshot {
+            entries,
+            orphaned_sessions,
+            orphaned_worktrees,
+        })
+    }
+
+    /// Swap in freshly collected data, keeping the selection and known git states
+    fn apply_snapshot(&mut self, snapshot: Snapshot) {
+        let Snapshot {
+            mut entries,
+            orphaned_sessions,
+            orphaned_worktrees,
+        } = snapshot;
+        self.orphaned_sessions = orphaned_sessions;
+        self.orphaned_worktrees = orphaned_worktrees;
+        self.last_refresh = Instant::now();
+
+        // Keep the last known state until the new scan reports
+        for entry in &mut entries {
+            entry.git_state = self
+                .entries
+                .iter()
+                .find(|e| e.path == entry.path)
+                .and_then(|e| e.git_state.clone());
+        }
+
+        // Dirty and ahead/behind state costs a git call per worktree, so gather it in the background
+        let paths: Vec<PathBuf> = entries.iter().map(|e| e.path.clone()).collect();
+        let show_last_commit = self.show_last_commit;
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.git_state_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let states = paths
+                .into_iter()
+                .filter_map(|path| {
+                    let summary = git::status_summary(&path).ok()?;
+                    let state = GitState {
+                        dirty: summary.dirty,
+                        ahead: summary.ahead,
+                        behind: summary.behind,
+                        last_commit: show_last_commit
+                            .then(|| git::last_commit(&path))
+                            .flatten()
+                            .map(|(subject, date)| LastCommit { subject, date }),
+                    };
+                    Some((path, state))
+                })
+                .collect();
+            let _ = tx.send(states);
+        });
+
+        // Preserve selection if possible: the same worktree, else the same row
         let old_selection = self.table_state.selected();
+        let old_path = self.selected_entry().map(|e| e.path.clone());
         self.entries = entries;
 
         if !self.entries.is_empty() {
-            let new_selection = old_selection
-                .map(|i| i.min(self.entries.len() - 1))
+            let new_selection = old_path
+                .and_then(|path| self.entries.iter().position(|e| e.path == path))
+                .or_else(|| old_selection.map(|i| i.min(self.entries.len() - 1)))
                 .or(Some(0));
             self.table_state.select(new_selection);
         } else {
@@ -478,8 +601,67 @@ impl StatusApp {
             .and_then(|i| self.entries.get(i))
     }
 
+    /// Apply worktree states from the background scan, blocking until it reports if `wait`
+    fn apply_git_states(&mut self, wait: bool) {
+        let receiver = match &self.git_state_receiver {
+            Some(receiver) => receiver,
+            None => return,
+        };
+        let states = if wait {
+            receiver.recv().ok()
+        } else {
+            match receiver.try_recv() {
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                result => result.ok(),
+            }
+        };
+        self.git_state_receiver = None;
+
+        for (path, state) in states.unwrap_or_default() {
+            if let Some(entry) = self.entries.iter_mut().find(|e| e.path == path) {
+                entry.git_state = Some(state);
+            }
+        }
+    }
+
+    /// Re-collect worktree data in the background every `refresh_interval`, and
+    /// swap it in once ready
+    fn poll_auto_refresh(&mut self) {
+        if let Some(receiver) = &self.snapshot_receiver {
+            match receiver.try_recv() {
+                Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                result => {
+                    self.snapshot_receiver = None;
+                    match result.ok().flatten() {
+                        Some(snapshot) => self.apply_snapshot(snapshot),
+                        None => self.last_refresh = Instant::now(),
+                    }
+                }
+            }
+            return;
+        }
+
+        let due = self
+            .refresh_interval
+            .is_some_and(|interval| self.last_refresh.elapsed() >= interval);
+        // Don't swap rows out from under a popup or a running task
+        if !due || self.is_busy || self.input_mode != InputMode::Normal {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.snapshot_receiver = Some(rx);
+        let git_root = self.git_root.clone();
+        let repo_name = self.repo_name.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(Self::collect(&git_root, &repo_name));
+        });
+    }
+
     /// Check for completed background tasks
     fn poll_tasks(&mut self) {
+        self.apply_git_states(false);
+        self.poll_auto_refresh();
         if let Some(ref receiver) = self.task_receiver {
             match receiver.try_recv() {
                 Ok(result) => {
@@ -548,6 +730,7 @@ impl StatusApp {
                 wt_path.to_str().unwrap(),
                 &base,
             ])
+            .logged()
             .output();
 
         match output {
@@ -566,7 +749,7 @@ impl StatusApp {
     }
 
     fn exec_delete_worktree(&mut self, path: PathBuf, force: bool) {
-        use std::process::{Command, Stdio};
+        use std::process::Command;
         use std::sync::mpsc;
         use std::thread;
 
@@ -585,24 +768,15 @@ impl StatusApp {
             .map(|e| e.branch.clone())
             .unwrap_or_default();
 
-        // Kill tmux session if exists (but not if we're inside it)
+        // Kill the session if it exists (but not if we're inside it)
         if let Ok(session) = get_session_name(&path) {
-            if tmux::session_exists(&session) {
-                // Check if we're currently in this session
-                let current_session = Command::new("tmux")
-                    .args(["display-message", "-p", "#{session_name}"])
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::null())
-                    .output()
-                    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-                    .unwrap_or_default();
-
-                if current_session == session {
+            if mux::backend().session_exists(&session) {
+                if mux::backend().current_session().as_deref() == Some(session.as_str()) {
                     self.message =
                         Some(("Cannot delete current session from TUI".to_string(), true));
                     return;
                 }
-                let _ = tmux::kill_session(&session);
+                let _ = mux::backend().kill_session(&session);
             }
         }
 
@@ -627,6 +801,7 @@ impl StatusApp {
             let output = Command::new("git")
                 .current_dir(&git_root)
                 .args(&args)
+                .logged()
                 .output();
 
             let result = match output {
@@ -635,6 +810,7 @@ impl StatusApp {
                     let _ = Command::new("git")
                         .current_dir(&git_root)
                         .args(["branch", "-d", &branch_clone])
+                        .logged()
                         .output();
                     TaskResult::DeleteWorktree {
                         branch: branch_clone,
@@ -670,8 +846,8 @@ impl StatusApp {
         };
 
         // Kill existing session
-        if tmux::session_exists(&session) {
-            if let Err(e) = tmux::kill_session(&session) {
+        if mux::backend().session_exists(&session) {
+            if let Err(e) = mux::backend().kill_session(&session) {
                 self.message = Some((format!("Error killing session: {}", e), true));
                 return;
             }
@@ -683,7 +859,7 @@ impl StatusApp {
             Err(_) => session.clone(),
         };
 
-        match tmux::create_session_with_title(&session, &path, &window_title) {
+        match mux::backend().create_session_with_title(&session, &path, &window_title) {
             Ok(_) => {
                 self.message = Some((format!("Reloaded session '{}'", session), false));
                 self.refresh();
@@ -701,7 +877,8 @@ impl StatusApp {
             if !entry.has_session && !entry.is_main {
                 let window_title =
                     super::get_window_title(&entry.path).unwrap_or_else(|_| entry.session.clone());
-                if tmux::create_session_with_title(&entry.session, &entry.path, &window_title)
+                if mux::backend()
+                    .create_session_with_title(&entry.session, &entry.path, &window_title)
                     .is_ok()
                 {
                     created += 1;
@@ -730,12 +907,14 @@ impl StatusApp {
             let output = Command::new("git")
                 .current_dir(&self.git_root)
                 .args(["worktree", "remove", path.to_str().unwrap()])
+                .logged()
                 .output();
 
             if output.map(|o| o.status.success()).unwrap_or(false) {
                 let _ = Command::new("git")
                     .current_dir(&self.git_root)
                     .args(["branch", "-d", &branch])
+                    .logged()
                     .output();
                 deleted += 1;
             }
@@ -743,7 +922,7 @@ impl StatusApp {
 
         // Kill orphaned sessions
         for session in &self.orphaned_sessions {
-            let _ = tmux::kill_session(session);
+            let _ = mux::backend().kill_session(session);
         }
 
         self.message = Some((
@@ -762,6 +941,7 @@ impl StatusApp {
         let output = Command::new("git")
             .current_dir(&self.git_root)
             .args(["branch", "--merged", &format!("origin/{}", default_branch)])
+            .logged()
             .output();
 
         let merged_branches: std::collections::HashSet<String> = match output {
@@ -787,8 +967,8 @@ impl StatusApp {
             if !entry.is_main && merged_branches.contains(&entry.branch) {
                 // Kill session
                 if let Ok(session) = get_session_name(&entry.path) {
-                    if tmux::session_exists(&session) {
-                        let _ = tmux::kill_session(&session);
+                    if mux::backend().session_exists(&session) {
+                        let _ = mux::backend().kill_session(&session);
                     }
                 }
 
@@ -801,12 +981,14 @@ impl StatusApp {
                         "--force",
                         entry.path.to_str().unwrap(),
                     ])
+                    .logged()
                     .output();
 
                 // Delete branch
                 let _ = Command::new("git")
                     .current_dir(&self.git_root)
                     .args(["branch", "-D", &entry.branch])
+                    .logged()
                     .output();
 
                 deleted += 1;
@@ -817,6 +999,7 @@ impl StatusApp {
         let _ = Command::new("git")
             .current_dir(&self.git_root)
             .args(["worktree", "prune"])
+            .logged()
             .output();
 
         if deleted > 0 {
@@ -830,7 +1013,7 @@ impl StatusApp {
     fn exec_doctor(&mut self) {
         use crate::config::Config;
 
-        let cfg = match Config::load() {
+        let cfg = match Config::load_for_repo(&self.git_root) {
             Ok(c) => c,
             Err(e) => {
                 self.message = Some((format!("Error loading config: {}", e), true));
@@ -937,6 +1120,29 @@ impl StatusApp {
         }
     }
 
+    fn copy_selected_branch(&mut self) {
+        let branch = self.selected_entry().map(|e| e.branch.clone());
+        self.copy_branch(branch.as_deref());
+    }
+
+    /// Copy a branch name to the system clipboard and report it in the footer
+    fn copy_branch(&mut self, branch: Option<&str>) {
+        let branch = match branch {
+            Some(branch) => branch,
+            None => {
+                self.message = Some(("Nothing selected to copy".to_string(), true));
+                return;
+            }
+        };
+
+        self.message = Some(
+            match super::copy_to_clipboard(&mut self.clipboard, branch) {
+                Ok(()) => (format!("Copied {} to clipboard", branch), false),
+                Err(e) => (format!("Could not copy {}: {}", branch, e), true),
+            },
+        );
+    }
+
     fn reload_selected(&mut self) {
         if let Some(entry) = self.selected_entry() {
             let path = entry.path.clone();
@@ -974,15 +1180,11 @@ impl StatusApp {
             // Navigation
             KeyCode::Up | KeyCode::Char('k') => self.previous_item(),
             KeyCode::Down | KeyCode::Char('j') => self.next_item(),
-            KeyCode::Home | KeyCode::Char('g') => {
-                if !self.entries.is_empty() {
-                    self.table_state.select(Some(0));
-                }
+            KeyCode::Home | KeyCode::Char('g') if !self.entries.is_empty() => {
+                self.table_state.select(Some(0));
             }
-            KeyCode::End | KeyCode::Char('G') => {
-                if !self.entries.is_empty() {
-                    self.table_state.select(Some(self.entries.len() - 1));
-                }
+            KeyCode::End | KeyCode::Char('G') if !self.entries.is_empty() => {
+                self.table_state.select(Some(self.entries.len() - 1));
             }
 
             // Worktree actions
@@ -992,6 +1194,7 @@ impl StatusApp {
             KeyCode::Char('n') => self.start_new_worktree(),
             KeyCode::Char('d') | KeyCode::Char('D') => self.start_delete(),
             KeyCode::Char('r') | KeyCode::Char('R') => self.reload_selected(),
+            KeyCode::Char('y') => self.copy_selected_branch(),
 
             // Sync & cleanup
             KeyCode::Char('s') => self.show_sync_menu(),
@@ -1134,13 +1337,17 @@ fn draw_status(frame: &mut Frame, app: &mut StatusApp) {
     frame.render_widget(Paragraph::new(title), chunks[0]);
 
     // Main table
-    let header = Row::new(vec![
+    let mut header = vec![
         RatCell::from("").style(Style::default()),
         RatCell::from("Session").style(Style::default().fg(RatColor::Cyan)),
         RatCell::from("Branch").style(Style::default().fg(RatColor::Cyan)),
-        RatCell::from("Path").style(Style::default().fg(RatColor::Cyan)),
-    ])
-    .height(1);
+        RatCell::from("State").style(Style::default().fg(RatColor::Cyan)),
+    ];
+    if app.show_last_commit {
+        header.push(RatCell::from("Last commit").style(Style::default().fg(RatColor::Cyan)));
+    }
+    header.push(RatCell::from("Path").style(Style::default().fg(RatColor::Cyan)));
+    let header = Row::new(header).height(1);
 
     let rows: Vec<Row> = app
         .entries
@@ -1155,7 +1362,7 @@ fn draw_status(frame: &mut Frame, app: &mut StatusApp) {
             let main_marker = if entry.is_main { " (main)" } else { "" };
             let dim_style = Style::default().fg(RatColor::DarkGray);
 
-            Row::new(vec![
+            let mut cells = vec![
                 RatCell::from(status).style(status_style),
                 RatCell::from(entry.session.as_str()).style(if entry.has_session {
                     Style::default()
@@ -1163,33 +1370,48 @@ fn draw_status(frame: &mut Frame, app: &mut StatusApp) {
                     dim_style
                 }),
                 RatCell::from(format!("{}{}", entry.branch, main_marker)),
-                RatCell::from(entry.path.display().to_string()).style(dim_style),
-            ])
+                git_state_cell(entry),
+            ];
+            if app.show_last_commit {
+                cells.push(last_commit_cell(entry));
+            }
+            cells.push(RatCell::from(entry.path.display().to_string()).style(dim_style));
+            Row::new(cells)
         })
         .collect();
 
-    let table = RatTable::new(
-        rows,
-        [
+    let widths = if app.show_last_commit {
+        vec![
             Constraint::Length(2),
+            Constraint::Percentage(20),
+            Constraint::Percentage(18),
+            Constraint::Length(10),
+            Constraint::Percentage(32),
             Constraint::Percentage(30),
+        ]
+    } else {
+        vec![
+            Constraint::Length(2),
+            Constraint::Percentage(28),
             Constraint::Percentage(25),
-            Constraint::Percentage(45),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Worktrees & Sessions ")
-            .border_style(Style::default().fg(RatColor::DarkGray)),
-    )
-    .row_highlight_style(
-        Style::default()
-            .bg(RatColor::DarkGray)
-            .add_modifier(Modifier::BOLD),
-    )
-    .highlight_symbol("> ");
+            Constraint::Length(10),
+            Constraint::Percentage(40),
+        ]
+    };
+    let table = RatTable::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Worktrees & Sessions ")
+                .border_style(Style::default().fg(RatColor::DarkGray)),
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(RatColor::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
 
     frame.render_stateful_widget(table, chunks[1], &mut app.table_state);
 
@@ -1327,6 +1549,57 @@ fn worktree_footer_spans(has_orphans: bool) -> Vec<Span<'static>> {
     }
 }
 
+/// Table cell for a worktree's state: ● when dirty, ↑ahead ↓behind its upstream
+fn git_state_cell(entry: &WorktreeEntry) -> RatCell<'static> {
+    let state = match &entry.git_state {
+        Some(state) => state,
+        None => return RatCell::from("…").style(Style::default().fg(RatColor::DarkGray)),
+    };
+
+    let mut spans = vec![if state.dirty {
+        Span::styled("●", Style::default().fg(RatColor::Yellow))
+    } else {
+        Span::styled("○", Style::default().fg(RatColor::DarkGray))
+    }];
+    if state.ahead > 0 {
+        spans.push(Span::styled(
+            format!(" ↑{}", state.ahead),
+            Style::default().fg(RatColor::Green),
+        ));
+    }
+    if state.behind > 0 {
+        spans.push(Span::styled(
+            format!(" ↓{}", state.behind),
+            Style::default().fg(RatColor::Red),
+        ));
+    }
+    RatCell::from(Line::from(spans))
+}
+
+/// Table cell for a worktree's last commit: truncated subject and relative date
+fn last_commit_cell(entry: &WorktreeEntry) -> RatCell<'static> {
+    let dim_style = Style::default().fg(RatColor::DarkGray);
+    let commit = match entry
+        .git_state
+        .as_ref()
+        .and_then(|s| s.last_commit.as_ref())
+    {
+        Some(commit) => commit,
+        None => return RatCell::from("").style(dim_style),
+    };
+
+    let subject: String = commit.subject.chars().take(30).collect();
+    let subject = if commit.subject.chars().count() > 30 {
+        format!("{}...", subject)
+    } else {
+        subject
+    };
+    RatCell::from(Line::from(vec![
+        Span::raw(subject),
+        Span::styled(format!(" ({})", commit.date), dim_style),
+    ]))
+}
+
 /// Build PR action footer spans
 fn pr_footer_spans(worktree_exists: bool) -> Vec<Span<'static>> {
     let status = if worktree_exists {
@@ -1349,6 +1622,10 @@ fn pr_footer_spans(worktree_exists: bool) -> Vec<Span<'static>> {
     spans.extend(vec![
         Span::styled("Enter", Style::default().fg(RatColor::Cyan)),
         Span::raw(" review "),
+        Span::styled("b", Style::default().fg(RatColor::Cyan)),
+        Span::raw("rowser "),
+        Span::styled("y", Style::default().fg(RatColor::Cyan)),
+        Span::raw("ank "),
         Span::styled("q", Style::default().fg(RatColor::Cyan)),
         Span::raw("uit "),
         Span::styled("?", Style::default().fg(RatColor::Cyan)),
@@ -1610,6 +1887,10 @@ fn draw_help_popup(frame: &mut Frame) {
             Span::styled("  r/R ", Style::default().fg(RatColor::Cyan)),
             Span::raw("Reload session (kill & recreate)"),
         ]),
+        Line::from(vec![
+            Span::styled("  y ", Style::default().fg(RatColor::Cyan)),
+            Span::raw("Copy branch name (worktree or PR)"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             "Sync & Cleanup",
@@ -1684,8 +1965,21 @@ fn draw_help_popup(frame: &mut Frame) {
     frame.render_widget(help, inner);
 }
 
-/// Show interactive status dashboard with worktrees and sessions
-pub fn status() -> Result<StatusAction> {
+/// Show interactive status dashboard with worktrees and sessions, or print them as JSON
+pub fn status(json: bool) -> Result<StatusAction> {
+    if json {
+        let mut app = StatusApp::load()?;
+        app.apply_git_states(true);
+        let report = StatusReport {
+            repo: &app.repo_name,
+            worktrees: &app.entries,
+            orphaned_sessions: &app.orphaned_sessions,
+            orphaned_worktrees: &app.orphaned_worktrees,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(StatusAction::None);
+    }
+
     let mut app = StatusApp::new()?;
 
     // Setup terminal
@@ -1746,6 +2040,7 @@ struct PrEntry {
     number: u32,
     title: String,
     branch: String,
+    url: String,
     checks: CheckStatus,
 }
 
@@ -1757,7 +2052,10 @@ struct DashboardApp {
     prs: Vec<PrEntry>,
     pr_table_state: TableState,
     focus: DashboardFocus,
-    gh_available: bool,
+    forge: Forge,
+    forge_available: bool,
+    last_pr_refresh: Instant,
+    pr_receiver: Option<std::sync::mpsc::Receiver<Vec<PrEntry>>>,
     // Areas for mouse click detection
     pr_area: Option<Rect>,
     worktree_area: Option<Rect>,
@@ -1774,12 +2072,13 @@ impl DashboardApp {
         let status = StatusApp::new()?;
         let metrics = WorkspaceMetrics::from_git_root(&status.git_root);
 
-        // Check if gh is available
-        let gh_available = which::which("gh").is_ok();
+        // Check if the forge CLI (gh or glab) is available
+        let forge = forge::current();
+        let forge_available = forge::is_installed(forge);
 
-        // Fetch PRs if gh is available
-        let prs = if gh_available {
-            Self::fetch_prs(&status.git_root)
+        // Fetch PRs if it is
+        let prs = if forge_available {
+            Self::fetch_prs(forge, &status.git_root)
         } else {
             Vec::new()
         };
@@ -1796,7 +2095,10 @@ impl DashboardApp {
             prs,
             pr_table_state,
             focus: DashboardFocus::Worktrees,
-            gh_available,
+            forge,
+            forge_available,
+            last_pr_refresh: Instant::now(),
+            pr_receiver: None,
             pr_area: None,
             worktree_area: None,
         })
@@ -1837,39 +2139,68 @@ impl DashboardApp {
         }
     }
 
-    fn fetch_prs(git_root: &std::path::Path) -> Vec<PrEntry> {
-        let output = std::process::Command::new("gh")
-            .current_dir(git_root)
-            .args([
-                "pr",
-                "list",
-                "--json",
-                "number,title,headRefName,statusCheckRollup",
-                "--limit",
-                "10",
-            ])
-            .output();
-
-        match output {
-            Ok(o) if o.status.success() => {
-                if let Ok(prs) = serde_json::from_slice::<Vec<serde_json::Value>>(&o.stdout) {
-                    prs.iter()
-                        .filter_map(|pr| {
-                            let checks = Self::parse_check_status(&pr["statusCheckRollup"]);
-                            Some(PrEntry {
-                                number: pr["number"].as_u64()? as u32,
-                                title: pr["title"].as_str()?.to_string(),
-                                branch: pr["headRefName"].as_str()?.to_string(),
-                                checks,
-                            })
-                        })
-                        .collect()
-                } else {
-                    Vec::new()
+    /// Re-fetch PRs in the background on the status refresh interval, keeping
+    /// the selected PR selected
+    fn poll_pr_refresh(&mut self) {
+        if let Some(receiver) = &self.pr_receiver {
+            match receiver.try_recv() {
+                Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                result => {
+                    self.pr_receiver = None;
+                    self.last_pr_refresh = Instant::now();
+                    if let Ok(prs) = result {
+                        self.apply_prs(prs);
+                    }
                 }
             }
-            _ => Vec::new(),
+            return;
+        }
+
+        let due = self
+            .status
+            .refresh_interval
+            .is_some_and(|interval| self.last_pr_refresh.elapsed() >= interval);
+        if !due || !self.forge_available {
+            return;
         }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.pr_receiver = Some(rx);
+        let forge = self.forge;
+        let git_root = self.status.git_root.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(Self::fetch_prs(forge, &git_root));
+        });
+    }
+
+    fn apply_prs(&mut self, prs: Vec<PrEntry>) {
+        let old_number = self.selected_pr().map(|pr| pr.number);
+        let old_selection = self.pr_table_state.selected();
+        self.prs = prs;
+
+        let new_selection = if self.prs.is_empty() {
+            None
+        } else {
+            old_number
+                .and_then(|number| self.prs.iter().position(|pr| pr.number == number))
+                .or_else(|| old_selection.map(|i| i.min(self.prs.len() - 1)))
+                .or(Some(0))
+        };
+        self.pr_table_state.select(new_selection);
+    }
+
+    fn fetch_prs(forge: Forge, git_root: &std::path::Path) -> Vec<PrEntry> {
+        forge::list(forge, git_root, None, 10)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|pr| PrEntry {
+                number: pr.number as u32,
+                checks: Self::parse_check_status(&pr.checks),
+                title: pr.title,
+                branch: pr.branch,
+                url: pr.url,
+            })
+            .collect()
     }
 
     fn parse_check_status(rollup: &serde_json::Value) -> CheckStatus {
@@ -1931,7 +2262,7 @@ impl DashboardApp {
     }
 
     fn toggle_focus(&mut self) {
-        if self.gh_available && !self.prs.is_empty() {
+        if self.forge_available && !self.prs.is_empty() {
             self.focus = match self.focus {
                 DashboardFocus::Worktrees => DashboardFocus::PullRequests,
                 DashboardFocus::PullRequests => DashboardFocus::Worktrees,
@@ -1957,6 +2288,21 @@ impl DashboardApp {
         self.pr_table_state.select(Some(prev));
     }
 
+    /// Open the selected PR in the browser, staying in the dashboard
+    fn open_selected_pr_in_browser(&mut self) {
+        let (number, url) = match self.selected_pr() {
+            Some(pr) => (pr.number, pr.url.clone()),
+            None => return,
+        };
+
+        self.status.message = Some(
+            match forge::open_web(self.forge, &self.status.git_root, number) {
+                Ok(()) => (format!("Opened #{} in browser", number), false),
+                Err(e) => (format!("Could not open {}: {}", url, e), true),
+            },
+        );
+    }
+
     fn selected_pr(&self) -> Option<&PrEntry> {
         self.pr_table_state.selected().and_then(|i| self.prs.get(i))
     }
@@ -2014,7 +2360,7 @@ fn draw_dashboard_content(frame: &mut Frame, app: &mut DashboardApp, area: Rect)
     frame.render_widget(block, area);
 
     // Layout: PRs (if available) | Worktrees | Footer
-    let has_prs = app.gh_available && !app.prs.is_empty();
+    let has_prs = app.forge_available && !app.prs.is_empty();
     let chunks = if has_prs {
         Layout::vertical([
             Constraint::Length(app.prs.len().min(5) as u16 + 3), // PR table (max 5 rows + header + borders)
@@ -2096,12 +2442,16 @@ fn draw_dashboard_content(frame: &mut Frame, app: &mut DashboardApp, area: Rect)
     }
 
     // Draw worktrees table
-    let header = Row::new(vec![
+    let mut header = vec![
         RatCell::from("").style(Style::default()),
         RatCell::from("Session").style(Style::default().fg(RatColor::Cyan)),
         RatCell::from("Branch").style(Style::default().fg(RatColor::Cyan)),
-    ])
-    .height(1);
+        RatCell::from("State").style(Style::default().fg(RatColor::Cyan)),
+    ];
+    if app.status.show_last_commit {
+        header.push(RatCell::from("Last commit").style(Style::default().fg(RatColor::Cyan)));
+    }
+    let header = Row::new(header).height(1);
 
     let rows: Vec<Row> = app
         .status
@@ -2117,7 +2467,7 @@ fn draw_dashboard_content(frame: &mut Frame, app: &mut DashboardApp, area: Rect)
             let main_marker = if entry.is_main { " (main)" } else { "" };
             let dim_style = Style::default().fg(RatColor::DarkGray);
 
-            Row::new(vec![
+            let mut cells = vec![
                 RatCell::from(status).style(status_style),
                 RatCell::from(entry.session.as_str()).style(if entry.has_session {
                     Style::default()
@@ -2125,7 +2475,12 @@ fn draw_dashboard_content(frame: &mut Frame, app: &mut DashboardApp, area: Rect)
                     dim_style
                 }),
                 RatCell::from(format!("{}{}", entry.branch, main_marker)),
-            ])
+                git_state_cell(entry),
+            ];
+            if app.status.show_last_commit {
+                cells.push(last_commit_cell(entry));
+            }
+            Row::new(cells)
         })
         .collect();
 
@@ -2135,41 +2490,64 @@ fn draw_dashboard_content(frame: &mut Frame, app: &mut DashboardApp, area: Rect)
         Style::default().fg(RatColor::DarkGray)
     };
 
-    let table = RatTable::new(
-        rows,
-        [
+    let widths = if app.status.show_last_commit {
+        vec![
             Constraint::Length(2),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Length(10),
             Constraint::Percentage(50),
-            Constraint::Percentage(50),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Worktrees & Sessions ")
-            .border_style(worktree_block_style),
-    )
-    .row_highlight_style(
-        Style::default()
-            .bg(RatColor::DarkGray)
-            .add_modifier(Modifier::BOLD),
-    )
-    .highlight_symbol("> ");
+        ]
+    } else {
+        vec![
+            Constraint::Length(2),
+            Constraint::Percentage(45),
+            Constraint::Percentage(40),
+            Constraint::Length(10),
+        ]
+    };
+    let table = RatTable::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Worktrees & Sessions ")
+                .border_style(worktree_block_style),
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(RatColor::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
 
     frame.render_stateful_widget(table, worktree_area_rect, &mut app.status.table_state);
 
-    // Dynamic footer based on focus (reuses shared footer spans)
-    let footer_text = match app.focus {
-        DashboardFocus::PullRequests => {
-            // Check if selected PR's branch has a worktree
-            let worktree_exists = app
-                .selected_pr()
-                .map(|pr| app.status.entries.iter().any(|e| e.branch == pr.branch))
-                .unwrap_or(false);
-            pr_footer_spans(worktree_exists)
+    // Dynamic footer based on focus (reuses shared footer spans); a message takes precedence
+    let footer_text = if let Some((msg, is_error)) = &app.status.message {
+        vec![
+            Span::styled(
+                if *is_error { " ✗ " } else { " ✓ " },
+                Style::default().fg(if *is_error {
+                    RatColor::Red
+                } else {
+                    RatColor::Green
+                }),
+            ),
+            Span::raw(msg.clone()),
+        ]
+    } else {
+        match app.focus {
+            DashboardFocus::PullRequests => {
+                // Check if selected PR's branch has a worktree
+                let worktree_exists = app
+                    .selected_pr()
+                    .map(|pr| app.status.entries.iter().any(|e| e.branch == pr.branch))
+                    .unwrap_or(false);
+                pr_footer_spans(worktree_exists)
+            }
+            DashboardFocus::Worktrees => worktree_footer_spans(app.status.has_orphans()),
         }
-        DashboardFocus::Worktrees => worktree_footer_spans(app.status.has_orphans()),
     };
 
     let footer = Paragraph::new(Line::from(footer_text));
@@ -2206,6 +2584,7 @@ fn run_dashboard_loop(
     while !app.status.should_exit {
         // Check for completed background tasks
         app.status.poll_tasks();
+        app.poll_pr_refresh();
 
         app.update_plasma();
         terminal.draw(|frame| draw_dashboard(frame, app))?;
@@ -2213,23 +2592,35 @@ fn run_dashboard_loop(
         if event::poll(std::time::Duration::from_millis(50))? {
             match event::read()? {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // The footer shows a message until the next key
+                    app.status.message = None;
+
                     // Handle dashboard-specific keys first
                     match key.code {
                         KeyCode::Tab => {
                             app.toggle_focus();
                             continue;
                         }
-                        KeyCode::Char('j') | KeyCode::Down => {
-                            if app.focus == DashboardFocus::PullRequests {
-                                app.next_pr();
-                                continue;
-                            }
+                        KeyCode::Char('j') | KeyCode::Down
+                            if app.focus == DashboardFocus::PullRequests =>
+                        {
+                            app.next_pr();
+                            continue;
                         }
-                        KeyCode::Char('k') | KeyCode::Up => {
-                            if app.focus == DashboardFocus::PullRequests {
-                                app.prev_pr();
-                                continue;
-                            }
+                        KeyCode::Char('k') | KeyCode::Up
+                            if app.focus == DashboardFocus::PullRequests =>
+                        {
+                            app.prev_pr();
+                            continue;
+                        }
+                        KeyCode::Char('b') if app.focus == DashboardFocus::PullRequests => {
+                            app.open_selected_pr_in_browser();
+                            continue;
+                        }
+                        KeyCode::Char('y') if app.focus == DashboardFocus::PullRequests => {
+                            let branch = app.selected_pr().map(|pr| pr.branch.clone());
+                            app.status.copy_branch(branch.as_deref());
+                            continue;
                         }
                         KeyCode::Enter if app.focus == DashboardFocus::PullRequests => {
                             if let Some(pr) = app.selected_pr() {
@@ -1,3 +1,4 @@
+use crate::git::RunLogged;
 use anyhow::{Context, Result};
 use colored::*;
 use std::process::Command;
@@ -21,18 +22,19 @@ pub fn update() -> Result<()> {
 
     // Update brew
     println!("{} Updating Homebrew...", "::".blue().bold());
-    let _ = Command::new("brew").args(["update"]).status();
+    let _ = Command::new("brew").args(["update"]).logged().status();
 
     // Upgrade ws and texplore
     println!("{} Upgrading ws and texplore...", "::".blue().bold());
     let status = Command::new("brew")
         .args(["upgrade", "ws", "texplore"])
+        .logged()
         .status()
         .context("Failed to run brew upgrade")?;
 
     if status.success() {
         // Check new version
-        let output = Command::new("ws").args(["--version"]).output();
+        let output = Command::new("ws").args(["--version"]).logged().output();
         if let Ok(out) = output {
             let new_version = String::from_utf8_lossy(&out.stdout);
             println!("{} Updated to: {}", "::".green().bold(), new_version.trim());
@@ -1,7 +1,7 @@
 use super::{get_session_name, get_window_title, get_workspaces_dir};
 use crate::config::Config;
-use crate::git;
-use crate::tmux;
+use crate::git::{self, RunLogged};
+use crate::mux;
 use anyhow::{Context, Result};
 use colored::*;
 use std::io::{self, BufRead, Write};
@@ -10,7 +10,7 @@ use std::process::{Command, Stdio};
 
 /// Warn if configured panel tools are not installed
 fn warn_missing_tools() -> Result<()> {
-    let cfg = Config::load()?;
+    let cfg = Config::load_for_current_repo()?;
     let mut warnings = Vec::new();
 
     if !cfg.is_ai_tool_installed() {
@@ -41,12 +41,22 @@ pub fn open(target: Option<String>) -> Result<()> {
             // If it's an existing path, use it directly
             if path.exists() {
                 path
+            } else if let Some(branches) = Config::load_for_current_repo()
+                .ok()
+                .and_then(|config| config.groups.get(&t).cloned())
+            {
+                // A group name: pick one of its branches
+                return match select_group_branch(&t, &branches)? {
+                    Some(branch) => open(Some(branch)),
+                    None => Ok(()),
+                };
             } else {
                 // Try to find it as a worktree by branch/name
                 let git_root = git::get_root(None).context("Not in a git repository")?;
-                match git::find_worktree(&git_root, &t)? {
-                    Some(wt) => wt.path,
-                    None => {
+                match lookup_worktree(&git_root, &t)? {
+                    WorktreeLookup::Found(path) => path,
+                    WorktreeLookup::Cancelled => return Ok(()),
+                    WorktreeLookup::NotFound => {
                         // Ask user if they want to create the worktree
                         let default_branch = git::get_default_branch(Some(&git_root));
                         println!("{} Worktree '{}' not found.", "::".yellow().bold(), t);
@@ -59,7 +69,7 @@ pub fn open(target: Option<String>) -> Result<()> {
 
                         if input.is_empty() || input == "y" || input == "yes" {
                             // Create the worktree and return its path
-                            return new(&t, &default_branch);
+                            return new(&t, &default_branch, true);
                         } else {
                             anyhow::bail!("Aborted");
                         }
@@ -72,13 +82,13 @@ pub fn open(target: Option<String>) -> Result<()> {
 
     let session = get_session_name(&dir)?;
 
-    if tmux::session_exists(&session) {
+    if mux::backend().session_exists(&session) {
         println!(
             "{} Attaching to existing session: {}",
             "::".blue().bold(),
             session
         );
-        tmux::attach(&session)?;
+        mux::backend().attach(&session)?;
         return Ok(());
     }
 
@@ -87,15 +97,142 @@ pub fn open(target: Option<String>) -> Result<()> {
 
     let window_title = get_window_title(&dir)?;
     println!("{} Creating workspace: {}", "::".blue().bold(), session);
-    tmux::create_session_with_title(&session, &dir, &window_title)?;
-    tmux::attach(&session)?;
+    mux::backend().create_session_with_title(&session, &dir, &window_title)?;
+    mux::backend().attach(&session)?;
 
     Ok(())
 }
 
+/// How a worktree named on the command line resolved
+enum WorktreeLookup {
+    Found(PathBuf),
+    /// Several partial matches and the picker was closed without a choice
+    Cancelled,
+    NotFound,
+}
+
+/// Exact branch or directory name, else a single partial match, else an fzf
+/// pick among the partial matches
+fn lookup_worktree(git_root: &Path, query: &str) -> Result<WorktreeLookup> {
+    let matches = match git::find_worktree(git_root, query)? {
+        Some(wt) => vec![wt],
+        None => git::find_worktree_fuzzy(git_root, query)?,
+    };
+    Ok(match matches.as_slice() {
+        [] => WorktreeLookup::NotFound,
+        [wt] => WorktreeLookup::Found(wt.path.clone()),
+        _ => match select_worktree_from(&matches, query)? {
+            Some(path) => WorktreeLookup::Found(path),
+            None => WorktreeLookup::Cancelled,
+        },
+    })
+}
+
+/// Pick one of several matching worktrees with fzf (None if cancelled)
+fn select_worktree_from(candidates: &[git::Worktree], query: &str) -> Result<Option<PathBuf>> {
+    if which::which("fzf").is_err() {
+        anyhow::bail!(
+            "'{}' matches several worktrees ({}); fzf is required to pick one. Install it with: brew install fzf",
+            query,
+            candidates
+                .iter()
+                .map(|wt| wt.branch.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let active_sessions = mux::backend().get_active_sessions();
+    let options: Vec<(String, String)> = candidates
+        .iter()
+        .map(|wt| {
+            let session_name = get_session_name(&wt.path).unwrap_or_default();
+            let status = if active_sessions.contains(&session_name) {
+                "●"
+            } else {
+                " "
+            };
+            (
+                format!("{} {}", status, wt.branch),
+                wt.path.display().to_string(),
+            )
+        })
+        .collect();
+
+    let header = format!("Worktrees matching '{}' (● = active session)", query);
+    Ok(fzf_pick(&header, &options)?.map(PathBuf::from))
+}
+
+/// Show `(label, value)` options in fzf and return the picked value (None if cancelled)
+fn fzf_pick(header: &str, options: &[(String, String)]) -> Result<Option<String>> {
+    if which::which("fzf").is_err() {
+        anyhow::bail!(
+            "fzf is required for interactive selection. Install it with: brew install fzf"
+        );
+    }
+
+    let mut fzf = Command::new("fzf")
+        .args([
+            "--ansi",
+            "--no-sort",
+            &format!("--header={}", header),
+            "--delimiter=|",
+            "--with-nth=1",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .logged()
+        .spawn()
+        .context("Failed to start fzf")?;
+
+    {
+        let stdin = fzf.stdin.as_mut().context("Failed to get fzf stdin")?;
+        for (label, value) in options {
+            writeln!(stdin, "{}|{}", label, value)?;
+        }
+    }
+
+    let output = fzf.wait_with_output()?;
+    if !output.status.success() {
+        // User cancelled
+        return Ok(None);
+    }
+
+    let selected = String::from_utf8_lossy(&output.stdout);
+    Ok(selected
+        .trim()
+        .split('|')
+        .nth(1)
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string()))
+}
+
+/// Pick a branch from a worktree group with fzf (None if cancelled)
+fn select_group_branch(group: &str, branches: &[String]) -> Result<Option<String>> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let active_sessions = mux::backend().get_active_sessions();
+
+    let options: Vec<(String, String)> = branches
+        .iter()
+        .map(|branch| {
+            let active = git::find_worktree(&git_root, branch)
+                .ok()
+                .flatten()
+                .and_then(|wt| get_session_name(&wt.path).ok())
+                .map(|session| active_sessions.contains(&session))
+                .unwrap_or(false);
+            let status = if active { "●" } else { " " };
+            (format!("{} {}", status, branch), branch.clone())
+        })
+        .collect();
+
+    fzf_pick(&format!("Group {} (● = active session)", group), &options)
+}
+
 /// Create new worktree and open workspace
-pub fn new(branch: &str, base: &str) -> Result<()> {
+pub fn new(branch: &str, base: &str, run_hook: bool) -> Result<()> {
     let git_root = git::get_root(None).context("Not in a git repository")?;
+    let branch = &crate::config::Config::resolve_alias(branch);
 
     let repo_name = git_root
         .file_name()
@@ -138,9 +275,37 @@ pub fn new(branch: &str, base: &str) -> Result<()> {
         wt_path.display()
     );
 
+    if run_hook {
+        if let Some(hook) = Config::load_for_repo(&git_root)?.post_create_hook {
+            run_post_create_hook(&hook, &wt_path)?;
+        }
+    }
+
     open(Some(wt_path.display().to_string()))
 }
 
+/// Run the configured post-create hook in a new worktree, failing if it exits non-zero
+fn run_post_create_hook(hook: &str, wt_path: &Path) -> Result<()> {
+    println!("{} Running post-create hook: {}", "::".blue().bold(), hook);
+
+    let status = Command::new("sh")
+        .current_dir(wt_path)
+        .args(["-c", hook])
+        .logged()
+        .status()
+        .context("Failed to run post_create_hook")?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "post_create_hook failed ({}). The worktree was kept at {}; \
+             fix the hook or re-run with `ws new --no-hook`",
+            status,
+            wt_path.display()
+        );
+    }
+    Ok(())
+}
+
 /// Interactive worktree selector with fzf
 pub fn select(direct_path: Option<PathBuf>) -> Result<()> {
     // If direct path provided, just open it
@@ -150,17 +315,10 @@ pub fn select(direct_path: Option<PathBuf>) -> Result<()> {
 
     let git_root = git::get_root(None).context("Not in a git repository")?;
     let worktrees = git::list_worktrees(&git_root)?;
-    let active_sessions = tmux::get_active_sessions();
-
-    // Check if fzf is available
-    if which::which("fzf").is_err() {
-        anyhow::bail!(
-            "fzf is required for interactive selection. Install it with: brew install fzf"
-        );
-    }
+    let active_sessions = mux::backend().get_active_sessions();
 
     // Build options for fzf
-    let mut options: Vec<String> = worktrees
+    let mut options: Vec<(String, String)> = worktrees
         .iter()
         .map(|wt| {
             let session_name = get_session_name(&wt.path).unwrap_or_default();
@@ -169,55 +327,24 @@ pub fn select(direct_path: Option<PathBuf>) -> Result<()> {
             } else {
                 " "
             };
-            format!("{} {}|{}", status, wt.branch, wt.path.display())
+            (
+                format!("{} {}", status, wt.branch),
+                wt.path.display().to_string(),
+            )
         })
         .collect();
 
-    options.push("+ Create new worktree...|__CREATE__".to_string());
+    options.push((
+        "+ Create new worktree...".to_string(),
+        "__CREATE__".to_string(),
+    ));
 
-    // Run fzf
-    let mut fzf = Command::new("fzf")
-        .args([
-            "--ansi",
-            "--no-sort",
-            "--header=Select worktree (● = active session)",
-            "--delimiter=|",
-            "--with-nth=1",
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .context("Failed to start fzf")?;
-
-    {
-        let stdin = fzf.stdin.as_mut().context("Failed to get fzf stdin")?;
-        for opt in &options {
-            writeln!(stdin, "{}", opt)?;
-        }
-    }
-
-    let output = fzf.wait_with_output()?;
-
-    if !output.status.success() {
+    match fzf_pick("Select worktree (● = active session)", &options)? {
+        Some(path) if path == "__CREATE__" => interactive_create(),
+        Some(path) => open(Some(path)),
         // User cancelled
-        return Ok(());
-    }
-
-    let selected = String::from_utf8_lossy(&output.stdout);
-    let selected = selected.trim();
-
-    if selected.is_empty() {
-        return Ok(());
-    }
-
-    // Extract path from selection
-    let path = selected.split('|').nth(1).context("Invalid selection")?;
-
-    if path == "__CREATE__" {
-        return interactive_create();
+        None => Ok(()),
     }
-
-    open(Some(path.to_string()))
 }
 
 fn interactive_create() -> Result<()> {
@@ -250,7 +377,7 @@ fn interactive_create() -> Result<()> {
         base
     };
 
-    new(branch, base)
+    new(branch, base, true)
 }
 
 /// Reload tmux session for a worktree (kill and recreate with current config)
@@ -262,9 +389,10 @@ pub fn reload(target: Option<String>) -> Result<()> {
                 path
             } else {
                 let git_root = git::get_root(None).context("Not in a git repository")?;
-                match git::find_worktree(&git_root, &t)? {
-                    Some(wt) => wt.path,
-                    None => {
+                match lookup_worktree(&git_root, &t)? {
+                    WorktreeLookup::Found(path) => path,
+                    WorktreeLookup::Cancelled => return Ok(()),
+                    WorktreeLookup::NotFound => {
                         // Worktree not found, ask if user wants to create it
                         print!("Worktree '{}' not found. Create it? [y/N]: ", t);
                         io::stdout().flush()?;
@@ -272,7 +400,7 @@ pub fn reload(target: Option<String>) -> Result<()> {
                         io::stdin().lock().read_line(&mut input)?;
                         if input.trim().eq_ignore_ascii_case("y") {
                             let base = git::get_default_branch(Some(&git_root));
-                            return new(&t, &base);
+                            return new(&t, &base, true);
                         } else {
                             anyhow::bail!("Worktree not found: {}", t);
                         }
@@ -286,9 +414,9 @@ pub fn reload(target: Option<String>) -> Result<()> {
     let session = get_session_name(&dir)?;
 
     // Kill existing session if it exists
-    if tmux::session_exists(&session) {
+    if mux::backend().session_exists(&session) {
         println!("{} Killing session: {}", "::".blue().bold(), session);
-        tmux::kill_session(&session)?;
+        mux::backend().kill_session(&session)?;
     }
 
     // Warn about missing tools
@@ -297,14 +425,14 @@ pub fn reload(target: Option<String>) -> Result<()> {
     // Recreate the session with current config
     let window_title = get_window_title(&dir)?;
     println!("{} Recreating workspace: {}", "::".blue().bold(), session);
-    tmux::create_session_with_title(&session, &dir, &window_title)?;
-    tmux::attach(&session)?;
+    mux::backend().create_session_with_title(&session, &dir, &window_title)?;
+    mux::backend().attach(&session)?;
 
     Ok(())
 }
 
 /// Delete worktree, tmux session, and local branch
-pub fn delete(target: &str, force: bool) -> Result<()> {
+pub fn delete(target: &str, force: bool, remote: bool) -> Result<()> {
     let target_path = Path::new(target);
 
     // Get the main worktree root (original repo), not the linked worktree's root
@@ -329,10 +457,17 @@ pub fn delete(target: &str, force: bool) -> Result<()> {
 
     let session_name = get_session_name(&worktree.path)?;
 
+    // Look up the upstream now; it is gone once the local branch is deleted
+    let upstream = if remote && !is_detached {
+        git::get_upstream(&git_root, &branch_name)
+    } else {
+        None
+    };
+
     // Kill tmux session if it exists
-    if tmux::session_exists(&session_name) {
+    if mux::backend().session_exists(&session_name) {
         println!("{} Killing session: {}", "::".blue().bold(), session_name);
-        tmux::kill_session(&session_name)?;
+        mux::backend().kill_session(&session_name)?;
     }
 
     // Remove the worktree
@@ -355,14 +490,66 @@ pub fn delete(target: &str, force: bool) -> Result<()> {
         branch_name
     );
 
+    if remote {
+        match upstream {
+            _ if is_detached => println!(
+                "{} Detached worktree, no remote branch to delete",
+                "::".yellow().bold()
+            ),
+            None => println!(
+                "{} '{}' has no upstream, no remote branch to delete",
+                "::".yellow().bold(),
+                branch_name
+            ),
+            // Worktrees created from origin/<base> track the base branch; never delete that
+            Some((remote_name, remote_branch))
+                if remote_branch != branch_name
+                    || remote_branch == git::get_default_branch(Some(&git_root)) =>
+            {
+                println!(
+                    "{} '{}' tracks {}/{}, not deleting it",
+                    "::".yellow().bold(),
+                    branch_name,
+                    remote_name,
+                    remote_branch
+                )
+            }
+            Some((remote_name, remote_branch)) => {
+                delete_remote(&git_root, &remote_name, &remote_branch, force)?
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Delete a branch on its remote, asking first unless forced
+fn delete_remote(git_root: &Path, remote: &str, branch: &str, force: bool) -> Result<()> {
+    if !force {
+        print!("Delete remote branch {}/{}? [y/N]: ", remote, branch);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("{} Kept {}/{}", "::".yellow().bold(), remote, branch);
+            return Ok(());
+        }
+    }
+
+    println!(
+        "{} Deleting remote branch: {}/{}",
+        "::".blue().bold(),
+        remote,
+        branch
+    );
+    git::delete_remote_branch(git_root, remote, branch)
+}
+
 /// Sync tmux sessions with worktrees
 pub fn sync(create_missing: bool, delete_unused: bool) -> Result<()> {
     let git_root = git::get_root(None).context("Not in a git repository")?;
     let worktrees = git::list_worktrees(&git_root)?;
-    let active_sessions = tmux::get_active_sessions();
+    let active_sessions = mux::backend().get_active_sessions();
 
     // Get repo name for session matching
     let repo_name = git_root
@@ -412,7 +599,7 @@ pub fn sync(create_missing: bool, delete_unused: bool) -> Result<()> {
         println!("{}", "Orphaned sessions (no worktree):".bold());
         for session in &orphaned {
             println!("  {} {}", "✗".red(), session);
-            tmux::kill_session(session)?;
+            mux::backend().kill_session(session)?;
             println!("    {}", "killed".dimmed());
             killed += 1;
         }
@@ -447,7 +634,7 @@ pub fn sync(create_missing: bool, delete_unused: bool) -> Result<()> {
                     session_name.dimmed()
                 );
                 let window_title = get_window_title(&wt.path)?;
-                tmux::create_session_with_title(&session_name, &wt.path, &window_title)?;
+                mux::backend().create_session_with_title(&session_name, &wt.path, &window_title)?;
                 println!("    {}", "created".green());
                 created += 1;
             } else {
@@ -0,0 +1,2057 @@
+use crate::config::{AiTool, Config, MultiplexerTool};
+use crate::deps::Dependencies;
+use crate::git::{self, RunLogged};
+use crate::mux;
+use crate::perf::PerfLog;
+use crate::scripts::Scripts;
+use crate::tmux;
+use crate::zellij;
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Find the worktree for a branch name, path, or worktree directory name
+fn resolve_worktree(target: &str) -> Result<git::Worktree> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    git::find_worktree(&git_root, target)?.context(format!("Worktree not found: {}", target))
+}
+
+/// Locate the ws-rebase-editor binary (next to ws first, then on PATH)
+fn rebase_editor_path() -> Result<PathBuf> {
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|p| p.to_path_buf()))
+    {
+        let candidate = dir.join("ws-rebase-editor");
+        if candidate.is_file() {
+            return Ok(candidate);
+        }
+    }
+
+    which::which("ws-rebase-editor")
+        .context("ws-rebase-editor not found. Install it alongside the ws binary")
+}
+
+/// Run an interactive rebase in a worktree using the ws-rebase-editor TUI
+pub fn interactive_rebase(branch: &str, onto: Option<&str>) -> Result<()> {
+    let worktree = resolve_worktree(branch)?;
+    let base = match onto {
+        Some(base) => base.to_string(),
+        None => format!("origin/{}", git::get_default_branch(Some(&worktree.path))),
+    };
+    let editor = rebase_editor_path()?;
+
+    println!(
+        "{} Rebasing '{}' onto {}...",
+        "::".blue().bold(),
+        worktree.branch,
+        base
+    );
+
+    // The editor creates this file when the user quits without saving
+    let abort_marker = std::env::temp_dir().join(format!("ws-rebase-abort-{}", std::process::id()));
+    let _ = std::fs::remove_file(&abort_marker);
+
+    // GIT_SEQUENCE_EDITOR is run through the shell, so quote the path. stderr is
+    // captured for the error message; the editor draws on stdout.
+    let output = Command::new("git")
+        .current_dir(&worktree.path)
+        .args(["rebase", "-i", &base])
+        .env("GIT_SEQUENCE_EDITOR", format!("'{}'", editor.display()))
+        .env("WS_REBASE_ABORT_MARKER", &abort_marker)
+        .stderr(std::process::Stdio::piped())
+        .logged()
+        .spawn()
+        .and_then(|child| child.wait_with_output())
+        .context("Failed to run git rebase")?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let aborted = abort_marker.exists();
+    let _ = std::fs::remove_file(&abort_marker);
+
+    // Aborting empties the TODO, which git rejects with "Nothing to do"
+    if aborted {
+        println!("{} Rebase aborted, nothing changed", "::".yellow().bold());
+        return Ok(());
+    }
+
+    let in_progress = rebase_in_progress(&worktree.path);
+    if !output.status.success() {
+        if !in_progress {
+            anyhow::bail!("git rebase failed: {}", stderr);
+        }
+        anyhow::bail!(
+            "Rebase stopped. Resolve conflicts in {} and run 'git rebase --continue'\n{}",
+            worktree.path.display(),
+            stderr
+        );
+    }
+    if !stderr.is_empty() {
+        eprintln!("{}", stderr);
+    }
+
+    // An `edit` action leaves the rebase paused with a zero exit code
+    if in_progress {
+        println!(
+            "{} Rebase paused for editing. Amend and run 'git rebase --continue' in {}",
+            "::".yellow().bold(),
+            worktree.path.display()
+        );
+    } else {
+        println!("{} Rebase complete", "::".green().bold());
+    }
+
+    Ok(())
+}
+
+/// Whether an interactive rebase is underway (`.git/rebase-merge` exists)
+fn rebase_in_progress(path: &Path) -> bool {
+    Command::new("git")
+        .current_dir(path)
+        .args(["rev-parse", "--git-path", "rebase-merge"])
+        .logged()
+        .output()
+        .ok()
+        .map(|o| path.join(String::from_utf8_lossy(&o.stdout).trim()))
+        .is_some_and(|dir| dir.exists())
+}
+
+/// Load the dependency file for the current repository
+fn load_deps() -> Result<(PathBuf, Dependencies)> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let repo_name = git::get_repo_name(Some(&git_root))?;
+    Ok((git_root, Dependencies::load(&repo_name)?))
+}
+
+/// Declare that `branch` depends on `on` (stacked branches)
+pub fn depend_add(branch: &str, on: &str) -> Result<()> {
+    if branch == on {
+        anyhow::bail!("A branch cannot depend on itself");
+    }
+
+    let (git_root, mut deps) = load_deps()?;
+
+    if deps.depends_on(on, branch) {
+        anyhow::bail!(
+            "'{}' already depends on '{}', this would create a cycle",
+            on,
+            branch
+        );
+    }
+
+    for name in [branch, on] {
+        if !git::branch_exists(&git_root, name) {
+            println!(
+                "{} Branch '{}' does not exist locally",
+                "::".yellow().bold(),
+                name
+            );
+        }
+    }
+
+    if !deps.add(branch, on) {
+        println!(
+            "{} '{}' already depends on '{}'",
+            "::".blue().bold(),
+            branch,
+            on
+        );
+        return Ok(());
+    }
+
+    deps.save()?;
+    println!(
+        "{} '{}' now depends on '{}'",
+        "::".green().bold(),
+        branch,
+        on
+    );
+    Ok(())
+}
+
+/// Remove a declared dependency
+pub fn depend_remove(branch: &str, on: &str) -> Result<()> {
+    let (_, mut deps) = load_deps()?;
+
+    if !deps.remove(branch, on) {
+        anyhow::bail!("'{}' does not depend on '{}'", branch, on);
+    }
+
+    deps.save()?;
+    println!(
+        "{} Removed dependency '{}' -> '{}'",
+        "::".green().bold(),
+        branch,
+        on
+    );
+    Ok(())
+}
+
+/// Print the dependency graph as a tree rooted at base branches
+pub fn depend_list() -> Result<()> {
+    let (git_root, deps) = load_deps()?;
+
+    if deps.deps.is_empty() {
+        println!("{} No branch dependencies declared", "::".blue().bold());
+        return Ok(());
+    }
+
+    // Roots are bases that don't depend on anything themselves
+    let mut roots: Vec<&str> = deps
+        .deps
+        .values()
+        .flatten()
+        .map(|b| b.as_str())
+        .filter(|b| !deps.deps.contains_key(*b))
+        .collect();
+    roots.sort();
+    roots.dedup();
+
+    println!("{}", "Branch dependencies:".bold());
+    println!();
+    for root in roots {
+        print_dep_node(&deps, &git_root, root, 0);
+    }
+
+    Ok(())
+}
+
+fn print_dep_node(deps: &Dependencies, git_root: &Path, branch: &str, depth: usize) {
+    let name = if git::branch_exists(git_root, branch) {
+        branch.normal()
+    } else {
+        format!("{} (missing)", branch).dimmed()
+    };
+
+    if depth == 0 {
+        println!("  {} {}", "●".cyan(), name);
+    } else {
+        println!("  {}└─ {}", "   ".repeat(depth - 1), name);
+    }
+
+    for dependent in deps.dependents_of(branch) {
+        print_dep_node(deps, git_root, dependent, depth + 1);
+    }
+}
+
+/// Write a handoff note for a branch, edit it in $EDITOR, and post it on the PR
+pub fn handoff(branch: &str, to: &str) -> Result<()> {
+    if which::which("gh").is_err() {
+        anyhow::bail!("GitHub CLI (gh) is required. Install with: brew install gh");
+    }
+
+    let worktree = resolve_worktree(branch)?;
+    let to = to.trim_start_matches('@');
+
+    println!(
+        "{} Gathering handoff info for '{}'...",
+        "::".blue().bold(),
+        worktree.branch
+    );
+
+    let output = Command::new("gh")
+        .current_dir(&worktree.path)
+        .args([
+            "pr",
+            "view",
+            &worktree.branch,
+            "--json",
+            "number,title,body,url",
+        ])
+        .logged()
+        .output()
+        .context("Failed to run gh pr view")?;
+
+    if !output.status.success() {
+        anyhow::bail!("No pull request found for '{}'", worktree.branch);
+    }
+
+    let pr: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse PR info")?;
+    let number = pr["number"].as_u64().context("PR has no number")?;
+
+    let mut doc = format!("# Handoff: `{}` → @{}\n\n", worktree.branch, to);
+
+    doc.push_str("## Pull Request\n\n");
+    doc.push_str(&format!(
+        "#{} {}\n\n",
+        number,
+        pr["title"].as_str().unwrap_or("")
+    ));
+    let body = pr["body"].as_str().unwrap_or("").trim();
+    if !body.is_empty() {
+        doc.push_str(body);
+        doc.push_str("\n\n");
+    }
+
+    doc.push_str("## Recent Commits\n\n");
+    let log = Command::new("git")
+        .current_dir(&worktree.path)
+        .args(["log", "-10", "--oneline"])
+        .logged()
+        .output()
+        .context("Failed to run git log")?;
+    for line in String::from_utf8_lossy(&log.stdout).lines() {
+        doc.push_str(&format!("- {}\n", line));
+    }
+    doc.push('\n');
+
+    let todos = changed_file_todos(&worktree.path);
+    if !todos.is_empty() {
+        doc.push_str("## TODOs in Changed Files\n\n");
+        for todo in todos {
+            doc.push_str(&format!("- {}\n", todo));
+        }
+        doc.push('\n');
+    }
+
+    let notes_path = worktree.path.join(".ws").join("handoff-notes.md");
+    if let Ok(notes) = std::fs::read_to_string(&notes_path) {
+        if !notes.trim().is_empty() {
+            doc.push_str("## Notes\n\n");
+            doc.push_str(notes.trim());
+            doc.push('\n');
+        }
+    }
+
+    let doc_path = std::env::temp_dir().join(format!(
+        "ws-handoff-{}.md",
+        git::sanitize_branch(&worktree.branch)
+    ));
+    std::fs::write(&doc_path, &doc).context("Failed to write handoff note")?;
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(&doc_path)
+        .logged()
+        .status()
+        .context("Failed to launch editor")?;
+
+    if !status.success() {
+        anyhow::bail!("Editor exited with an error");
+    }
+
+    let edited = std::fs::read_to_string(&doc_path).context("Failed to read handoff note")?;
+    if edited.trim().is_empty() {
+        println!(
+            "{} Handoff note is empty, nothing posted",
+            "::".yellow().bold()
+        );
+        return Ok(());
+    }
+
+    let status = Command::new("gh")
+        .current_dir(&worktree.path)
+        .args(["pr", "comment", &number.to_string(), "--body-file"])
+        .arg(&doc_path)
+        .logged()
+        .status()
+        .context("Failed to run gh pr comment")?;
+
+    if !status.success() {
+        anyhow::bail!(
+            "Failed to post handoff comment. The note is saved at {}",
+            doc_path.display()
+        );
+    }
+
+    let _ = std::fs::remove_file(&doc_path);
+    println!(
+        "{} Handoff for '{}' posted on PR #{}",
+        "::".green().bold(),
+        worktree.branch,
+        number
+    );
+    Ok(())
+}
+
+/// Collect TODO/FIXME lines from files changed relative to the default branch
+fn changed_file_todos(worktree_path: &Path) -> Vec<String> {
+    let base = format!("origin/{}", git::get_default_branch(Some(worktree_path)));
+    let output = Command::new("git")
+        .current_dir(worktree_path)
+        .args(["diff", "--name-only", &format!("{}...HEAD", base)])
+        .logged()
+        .output();
+
+    let files = match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).to_string(),
+        _ => return Vec::new(),
+    };
+
+    let mut todos = Vec::new();
+    for file in files.lines() {
+        let content = match std::fs::read_to_string(worktree_path.join(file)) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        for (i, line) in content.lines().enumerate() {
+            if line.contains("TODO") || line.contains("FIXME") {
+                todos.push(format!("`{}:{}` {}", file, i + 1, line.trim()));
+            }
+        }
+    }
+    todos
+}
+
+/// Print a standup summary of recent commits, PRs, and TODOs across worktrees
+pub fn standup(since: &str, format: &str, clipboard: bool) -> Result<()> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let worktrees = git::list_worktrees(&git_root)?;
+
+    let email = Command::new("git")
+        .current_dir(&git_root)
+        .args(["config", "user.email"])
+        .logged()
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|e| !e.is_empty())
+        .context("git user.email is not set")?;
+
+    // Commits per worktree, deduplicated across branches that share history
+    let mut seen = std::collections::HashSet::new();
+    let mut commits = Vec::new();
+    let mut todos = Vec::new();
+    for wt in &worktrees {
+        let output = Command::new("git")
+            .current_dir(&wt.path)
+            .args([
+                "log",
+                &format!("--since={}", since),
+                &format!("--author={}", email),
+                "--oneline",
+            ])
+            .logged()
+            .output()
+            .context("Failed to run git log")?;
+
+        let mut active = false;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let hash = line.split(' ').next().unwrap_or("").to_string();
+            if seen.insert(hash) {
+                commits.push((wt.branch.clone(), line.to_string()));
+                active = true;
+            }
+        }
+
+        if active && wt.path != git_root {
+            todos.extend(changed_file_todos(&wt.path));
+        }
+    }
+
+    let prs = standup_prs(&git_root, since);
+
+    let (heading, bullet, todo_bullet): (fn(&str) -> String, &str, &str) = match format {
+        "slack" => (|h| format!("*{}*", h), "•", "•"),
+        "notion" => (|h| format!("### {}", h), "-", "- [ ]"),
+        _ => (|h| format!("{}:", h), "-", "-"),
+    };
+
+    let mut out = String::new();
+    out.push_str(&heading(&format!("Commits since {}", since)));
+    out.push('\n');
+    if commits.is_empty() {
+        out.push_str(&format!("{} No commits\n", bullet));
+    }
+    for (branch, line) in &commits {
+        out.push_str(&format!("{} [{}] {}\n", bullet, branch, line));
+    }
+
+    if !prs.is_empty() {
+        out.push('\n');
+        out.push_str(&heading("Pull requests"));
+        out.push('\n');
+        for pr in &prs {
+            out.push_str(&format!("{} {}\n", bullet, pr));
+        }
+    }
+
+    if !todos.is_empty() {
+        out.push('\n');
+        out.push_str(&heading("TODOs"));
+        out.push('\n');
+        for todo in &todos {
+            out.push_str(&format!("{} {}\n", todo_bullet, todo));
+        }
+    }
+
+    print!("{}", out);
+
+    if clipboard {
+        super::copy_to_clipboard(&mut None, &out)?;
+        println!();
+        println!("{} Copied to clipboard", "::".green().bold());
+    }
+
+    Ok(())
+}
+
+/// PRs authored by the current user that were updated since the given date
+fn standup_prs(git_root: &Path, since: &str) -> Vec<String> {
+    if which::which("gh").is_err() {
+        return Vec::new();
+    }
+
+    // `git rev-parse --since=<date>` resolves git's date syntax to --max-age=<unix time>
+    let cutoff = Command::new("git")
+        .current_dir(git_root)
+        .args(["rev-parse", &format!("--since={}", since)])
+        .logged()
+        .output()
+        .ok()
+        .and_then(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .trim()
+                .strip_prefix("--max-age=")
+                .and_then(|ts| ts.parse::<i64>().ok())
+        })
+        .map(super::format_utc_timestamp);
+
+    let output = match Command::new("gh")
+        .current_dir(git_root)
+        .args([
+            "pr",
+            "list",
+            "--author",
+            "@me",
+            "--state",
+            "all",
+            "--json",
+            "number,title,state,updatedAt",
+        ])
+        .logged()
+        .output()
+    {
+        Ok(o) if o.status.success() => o,
+        _ => return Vec::new(),
+    };
+
+    let prs: Vec<serde_json::Value> =
+        serde_json::from_slice(&output.stdout).unwrap_or_else(|_| Vec::new());
+
+    prs.iter()
+        .filter(|pr| match &cutoff {
+            // Both are RFC 3339 UTC timestamps, so they compare lexically
+            Some(cutoff) => pr["updatedAt"].as_str().unwrap_or("") >= cutoff.as_str(),
+            None => true,
+        })
+        .map(|pr| {
+            format!(
+                "#{} {} ({})",
+                pr["number"].as_u64().unwrap_or(0),
+                pr["title"].as_str().unwrap_or(""),
+                pr["state"].as_str().unwrap_or("").to_lowercase()
+            )
+        })
+        .collect()
+}
+
+/// Run the project's linters in a worktree, exiting with the linter's exit code
+pub fn lint(branch: Option<&str>, fix: bool) -> Result<()> {
+    let path = match branch {
+        Some(branch) => resolve_worktree(branch)?.path,
+        None => git::get_root(None).context("Not in a git repository")?,
+    };
+
+    // (marker file, program, args, fix args)
+    let linters: [(&str, &str, &[&str], &[&str]); 3] = [
+        (
+            "Cargo.toml",
+            "cargo",
+            &["clippy"],
+            &["--fix", "--allow-dirty", "--allow-staged"],
+        ),
+        ("package.json", "npx", &["eslint", "."], &["--fix"]),
+        ("pyproject.toml", "ruff", &["check"], &["--fix"]),
+    ];
+
+    let mut ran = 0;
+    let mut exit_code = 0;
+    for (marker, program, args, fix_args) in linters {
+        if !path.join(marker).exists() {
+            continue;
+        }
+
+        let mut cmd_args: Vec<&str> = args.to_vec();
+        if fix {
+            cmd_args.extend_from_slice(fix_args);
+        }
+
+        println!(
+            "{} Running {} {}...",
+            "::".blue().bold(),
+            program,
+            cmd_args.join(" ")
+        );
+
+        let status = Command::new(program)
+            .current_dir(&path)
+            .args(&cmd_args)
+            .logged()
+            .status()
+            .context(format!("Failed to run {}", program))?;
+
+        ran += 1;
+        if !status.success() && exit_code == 0 {
+            exit_code = status.code().unwrap_or(1);
+        }
+    }
+
+    if ran == 0 {
+        anyhow::bail!(
+            "No supported project found in {} (Cargo.toml, package.json, pyproject.toml)",
+            path.display()
+        );
+    }
+
+    if exit_code != 0 {
+        println!("{} Lint failed", "::".red().bold());
+        std::process::exit(exit_code);
+    }
+
+    println!("{} Lint passed", "::".green().bold());
+    Ok(())
+}
+
+/// Show session startup percentiles from ~/.ws/perf.toml
+pub fn performance() -> Result<()> {
+    let log = PerfLog::load()?;
+
+    if log.sessions.is_empty() {
+        println!(
+            "{} No sessions recorded yet. Timings are saved when ws creates a tmux session",
+            "::".blue().bold()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Session startup over the last {} sessions",
+            log.sessions.len()
+        )
+        .bold()
+    );
+    println!();
+    println!(
+        "  {:<16} {:>8} {:>8} {:>8} {:>8}",
+        "", "P50", "P90", "P99", "samples"
+    );
+
+    let metrics: [(&str, Vec<u64>); 3] = [
+        (
+            "tmux new-session",
+            log.sessions.iter().map(|s| s.create_ms).collect(),
+        ),
+        (
+            "first keypress",
+            log.sessions.iter().filter_map(|s| s.first_key_ms).collect(),
+        ),
+        (
+            "tool startup",
+            log.sessions.iter().filter_map(|s| s.tool_ms).collect(),
+        ),
+    ];
+
+    for (label, mut values) in metrics {
+        if values.is_empty() {
+            println!(
+                "  {:<16} {}",
+                label,
+                "no data (needs a shell hook)".dimmed()
+            );
+            continue;
+        }
+
+        values.sort_unstable();
+        println!(
+            "  {:<16} {:>8} {:>8} {:>8} {:>8}",
+            label,
+            format_ms(percentile(&values, 50)),
+            format_ms(percentile(&values, 90)),
+            format_ms(percentile(&values, 99)),
+            values.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Record a startup milestone for a session
+pub fn performance_mark(metric: &str, session: Option<&str>) -> Result<()> {
+    let session = match session {
+        Some(session) => session.to_string(),
+        None => tmux::get_current_session().context("Not inside a tmux session")?,
+    };
+    PerfLog::mark(&session, metric)?;
+    Ok(())
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn format_ms(ms: u64) -> String {
+    if ms >= 1000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}ms", ms)
+    }
+}
+
+/// Copy uncommitted changes (including untracked files) from another worktree into this one
+pub fn copy_changes(source: &str, files: Option<&str>) -> Result<()> {
+    let target = git::get_root(None).context("Not in a git repository")?;
+    let source = resolve_worktree(source)?;
+
+    if source.path == target {
+        anyhow::bail!("Source and current worktree are the same");
+    }
+
+    let pathspec: Vec<&str> = files.into_iter().collect();
+
+    // Tracked changes, staged and unstaged
+    let output = Command::new("git")
+        .current_dir(&source.path)
+        .args(["diff", "--binary", "HEAD", "--"])
+        .args(&pathspec)
+        .logged()
+        .output()
+        .context("Failed to run git diff")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut patch = output.stdout;
+
+    // Untracked files, diffed against /dev/null so they apply as new files
+    let untracked = Command::new("git")
+        .current_dir(&source.path)
+        .args(["ls-files", "--others", "--exclude-standard", "--"])
+        .args(&pathspec)
+        .logged()
+        .output()
+        .context("Failed to list untracked files")?;
+    for file in String::from_utf8_lossy(&untracked.stdout).lines() {
+        let output = Command::new("git")
+            .current_dir(&source.path)
+            .args(["diff", "--no-index", "--binary", "/dev/null", file])
+            .logged()
+            .output()
+            .context("Failed to run git diff --no-index")?;
+        patch.extend_from_slice(&output.stdout);
+    }
+
+    if patch.is_empty() {
+        println!(
+            "{} No uncommitted changes in '{}'",
+            "::".yellow().bold(),
+            source.branch
+        );
+        return Ok(());
+    }
+
+    // Check first so nothing is applied when any file conflicts
+    let check = run_git_apply(&target, &patch, true)?;
+    if !check.status.success() {
+        let stderr = String::from_utf8_lossy(&check.stderr);
+        let mut conflicts: Vec<&str> = stderr
+            .lines()
+            .filter_map(|line| {
+                let line = line.strip_prefix("error: ")?;
+                let file = line
+                    .strip_prefix("patch failed: ")
+                    .map(|rest| rest.rsplit_once(':').map_or(rest, |(file, _)| file))
+                    .or_else(|| line.split_once(": ").map(|(file, _)| file))?;
+                Some(file)
+            })
+            .collect();
+        conflicts.dedup();
+
+        println!("{} Changes do not apply cleanly:", "::".red().bold());
+        for file in &conflicts {
+            println!("  {} {}", "✗".red(), file);
+        }
+        anyhow::bail!("No changes were applied");
+    }
+
+    let apply = run_git_apply(&target, &patch, false)?;
+    if !apply.status.success() {
+        anyhow::bail!(
+            "git apply failed: {}",
+            String::from_utf8_lossy(&apply.stderr).trim()
+        );
+    }
+
+    let stat = run_git_apply_stat(&target, &patch)?;
+    print!("{}", stat);
+    println!(
+        "{} Copied changes from '{}'",
+        "::".green().bold(),
+        source.branch
+    );
+    Ok(())
+}
+
+fn run_git_apply(dir: &Path, patch: &[u8], check: bool) -> Result<std::process::Output> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir).arg("apply");
+    if check {
+        cmd.arg("--check");
+    }
+    pipe_to(cmd, patch)
+}
+
+fn run_git_apply_stat(dir: &Path, patch: &[u8]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir).args(["apply", "--stat"]);
+    let output = pipe_to(cmd, patch)?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Run a command with `input` on stdin and capture its output
+fn pipe_to(mut cmd: Command, input: &[u8]) -> Result<std::process::Output> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .logged()
+        .spawn()
+        .context("Failed to run git apply")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input)?;
+    }
+    child.wait_with_output().context("Failed to run git apply")
+}
+
+/// Reset executable bits in a worktree to the modes committed in HEAD
+pub fn fix_permissions(branch: Option<&str>, dry_run: bool) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = match branch {
+        Some(branch) => resolve_worktree(branch)?.path,
+        None => git::get_root(None).context("Not in a git repository")?,
+    };
+
+    // Raw diff lines look like ":100644 100755 <sha> <sha> M\tpath"; only the mode is
+    // restored so uncommitted content changes are kept. Windows-mounted filesystems
+    // get core.fileMode=false, which would hide every mode change, so force it on.
+    let output = Command::new("git")
+        .current_dir(&path)
+        .args([
+            "-c",
+            "core.fileMode=true",
+            "diff",
+            "--raw",
+            "--no-renames",
+            "HEAD",
+        ])
+        .logged()
+        .output()
+        .context("Failed to run git diff")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut mismatched = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let (meta, file) = match line.split_once('\t') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let modes: Vec<&str> = meta.trim_start_matches(':').split(' ').collect();
+        if modes.len() < 2 {
+            continue;
+        }
+        let (committed, current) = (modes[0], modes[1]);
+        let is_regular = |mode: &str| mode == "100644" || mode == "100755";
+        if committed != current && is_regular(committed) && is_regular(current) {
+            mismatched.push((file.to_string(), committed == "100755"));
+        }
+    }
+
+    if mismatched.is_empty() {
+        println!("{} All file permissions match HEAD", "::".green().bold());
+        return Ok(());
+    }
+
+    let mut fixed = 0;
+    for (file, executable) in &mismatched {
+        let mode = if *executable { "+x" } else { "-x" };
+        if dry_run {
+            println!("  {} {} {}", "○".yellow(), mode, file);
+            continue;
+        }
+
+        let full_path = path.join(file);
+        let result = std::fs::metadata(&full_path).and_then(|meta| {
+            let mut perms = meta.permissions();
+            let bits = perms.mode();
+            // Mirror the read bits, like git does on checkout
+            perms.set_mode(if *executable {
+                bits | ((bits & 0o444) >> 2)
+            } else {
+                bits & !0o111
+            });
+            std::fs::set_permissions(&full_path, perms)
+        });
+
+        match result {
+            Ok(()) => {
+                println!("  {} {} {}", "✓".green(), mode, file);
+                fixed += 1;
+            }
+            Err(e) => println!("  {} {}: {}", "✗".red(), file, e),
+        }
+    }
+
+    println!();
+    if dry_run {
+        println!(
+            "{} {} file(s) would be fixed",
+            "::".blue().bold(),
+            mismatched.len()
+        );
+    } else {
+        println!("{} Fixed {} file(s)", "::".green().bold(), fixed);
+    }
+    Ok(())
+}
+
+/// Cap on the diff sent to the AI tool, in bytes (prompts are passed as an argument)
+const SUMMARY_MAX_DIFF_BYTES: usize = 60_000;
+
+/// Generate a PR description for a branch with the configured AI tool
+pub fn summary(branch: &str, ai_tool: Option<&str>, create_pr: bool) -> Result<()> {
+    let worktree = resolve_worktree(branch)?;
+    let base = format!("origin/{}", git::get_default_branch(Some(&worktree.path)));
+
+    let tool = match ai_tool {
+        Some(name) => AiTool::from_str(name).context(format!(
+            "Unknown AI tool: {}. Valid options: {}",
+            name,
+            AiTool::valid_options()
+        ))?,
+        None => Config::load_for_repo(&worktree.path)?.ai_tool,
+    };
+    let (program, args) = tool.prompt_command().context(format!(
+        "{} does not support non-interactive prompts. Use --ai-tool to pick another",
+        tool.name()
+    ))?;
+    if which::which(program).is_err() {
+        anyhow::bail!("{} not found. Install: {}", program, tool.install_hint());
+    }
+
+    let log = git::run_git(
+        &worktree.path,
+        &["log", "--oneline", &format!("{}..HEAD", base)],
+    )?;
+    if log.trim().is_empty() {
+        anyhow::bail!("No commits on {} since {}", worktree.branch, base);
+    }
+    let files = git::run_git(
+        &worktree.path,
+        &["diff", "--stat", &format!("{}...HEAD", base)],
+    )?;
+    let mut diff = git::run_git(&worktree.path, &["diff", &format!("{}...HEAD", base)])?;
+    if diff.len() > SUMMARY_MAX_DIFF_BYTES {
+        let mut end = SUMMARY_MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        diff.truncate(end);
+        diff.push_str("\n[diff truncated]\n");
+    }
+
+    let prompt = format!(
+        "Summarize these changes as a GitHub PR description in markdown. \
+         Start with a short overview, then list the notable changes. \
+         Output only the description.\n\n\
+         ## Commits\n\n{}\n## Changed files\n\n{}\n## Diff\n\n{}",
+        log, files, diff
+    );
+
+    eprintln!(
+        "{} Summarizing {} with {}...",
+        "::".blue().bold(),
+        worktree.branch,
+        tool.name()
+    );
+    let output = Command::new(program)
+        .current_dir(&worktree.path)
+        .args(args)
+        .arg(&prompt)
+        .logged()
+        .output()
+        .context(format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let description = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if description.is_empty() {
+        anyhow::bail!("{} returned an empty summary", program);
+    }
+    println!("{}", description);
+
+    if create_pr {
+        if which::which("gh").is_err() {
+            anyhow::bail!("GitHub CLI (gh) is required. Install with: brew install gh");
+        }
+
+        eprintln!("{} Creating pull request...", "::".blue().bold());
+        let status = Command::new("gh")
+            .current_dir(&worktree.path)
+            .args(["pr", "create", "--body", &description])
+            .logged()
+            .status()
+            .context("Failed to run gh pr create")?;
+        if !status.success() {
+            anyhow::bail!("Failed to create pull request");
+        }
+    }
+
+    Ok(())
+}
+
+/// One commit in a line's history
+struct LineChange {
+    hash: String,
+    author: String,
+    date: String,
+    subject: String,
+    path: String,
+    hunk: String,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+/// Show every commit that touched a line, oldest last (`git log -L`)
+pub fn blame_history(target: &str, line: Option<u32>) -> Result<()> {
+    let (file, line) = match (target.rsplit_once(':'), line) {
+        (_, Some(line)) => (target, line),
+        (Some((file, line)), None) => match line.parse::<u32>() {
+            Ok(line) => (file, line),
+            Err(_) => anyhow::bail!("Invalid line number: {}", line),
+        },
+        (None, None) => anyhow::bail!("Specify a line: ws worktree blame-history <file>:<line>"),
+    };
+    if line == 0 {
+        anyhow::bail!("Line numbers start at 1");
+    }
+    if !Path::new(file).is_file() {
+        anyhow::bail!("File not found: {}", file);
+    }
+
+    // -L follows the line through edits and renames on its own
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--date=short",
+            "--format=%x00%h%x09%an%x09%ad%x09%s",
+            &format!("-L{},{}:{}", line, line, file),
+        ])
+        .logged()
+        .output()
+        .context("Failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let changes = parse_line_log(&String::from_utf8_lossy(&output.stdout));
+    if changes.is_empty() {
+        println!("{} No history for {}:{}", "::".yellow().bold(), file, line);
+        return Ok(());
+    }
+
+    if which::which("delta").is_ok() {
+        // Re-emit as git log output so delta can highlight each change
+        let mut log = String::new();
+        for change in &changes {
+            log.push_str(&format!(
+                "commit {}\nAuthor: {}\nDate:   {}\n\n    {}\n\ndiff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n{}\n",
+                change.hash,
+                change.author,
+                change.date,
+                change.subject,
+                change.hunk,
+                path = change.path
+            ));
+            for old in &change.before {
+                log.push_str(&format!("-{}\n", old));
+            }
+            for new in &change.after {
+                log.push_str(&format!("+{}\n", new));
+            }
+            log.push('\n');
+        }
+        use std::io::Write;
+
+        let mut child = Command::new("delta")
+            .stdin(std::process::Stdio::piped())
+            .logged()
+            .spawn()
+            .context("Failed to run delta")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // delta may exit early (e.g. pager quit), which closes the pipe
+            let _ = stdin.write_all(log.as_bytes());
+        }
+        child.wait().context("Failed to run delta")?;
+        return Ok(());
+    }
+
+    println!(
+        "{} {}:{} ({} {})",
+        "::".blue().bold(),
+        file,
+        line,
+        changes.len(),
+        if changes.len() == 1 {
+            "commit"
+        } else {
+            "commits"
+        }
+    );
+    for change in &changes {
+        println!();
+        println!(
+            "{} {} {} {}",
+            change.hash.yellow(),
+            change.date.dimmed(),
+            change.author.cyan(),
+            change.subject
+        );
+        for old in &change.before {
+            println!("  {} {}", "-".red(), old.red());
+        }
+        for new in &change.after {
+            println!("  {} {}", "+".green(), new.green());
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `git log -L` output produced with a NUL-prefixed tab-separated header
+fn parse_line_log(log: &str) -> Vec<LineChange> {
+    let mut changes = Vec::new();
+
+    for record in log.split('\0').filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+        let header = lines.next().unwrap_or("");
+        let mut fields = header.splitn(4, '\t');
+        let mut change = LineChange {
+            hash: fields.next().unwrap_or("").to_string(),
+            author: fields.next().unwrap_or("").to_string(),
+            date: fields.next().unwrap_or("").to_string(),
+            subject: fields.next().unwrap_or("").to_string(),
+            path: String::new(),
+            hunk: String::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+        };
+
+        // `---`/`+++` are file headers only before the first hunk; inside one
+        // they're a removed `-- ...` or added `++ ...` line
+        let mut in_hunk = false;
+        for line in lines {
+            if !in_hunk && line.starts_with("+++ ") {
+                if let Some(path) = line.strip_prefix("+++ b/") {
+                    change.path = path.to_string();
+                }
+            } else if !in_hunk && line.starts_with("--- ") {
+                continue;
+            } else if line.starts_with("@@") {
+                in_hunk = true;
+                change.hunk = line.to_string();
+            } else if let Some(new) = line.strip_prefix('+') {
+                change.after.push(new.to_string());
+            } else if let Some(old) = line.strip_prefix('-') {
+                change.before.push(old.to_string());
+            }
+        }
+
+        changes.push(change);
+    }
+
+    changes
+}
+
+/// Move a worktree to a fork: push the branch there, relocate the worktree
+/// under the fork's workspace directory, and reopen its tmux session
+pub fn migrate(branch: &str, url: &str) -> Result<()> {
+    let worktree = resolve_worktree(branch)?;
+    let main_root = git::get_main_worktree_root(Some(&worktree.path))?;
+    if worktree.path == main_root {
+        anyhow::bail!("Cannot migrate the main worktree");
+    }
+
+    // https://github.com/owner/repo(.git) or git@github.com:owner/repo.git
+    let mut parts = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':']);
+    let (repo, owner) = match (parts.next(), parts.next()) {
+        (Some(repo), Some(owner)) if !repo.is_empty() && !owner.is_empty() => (repo, owner),
+        _ => anyhow::bail!("Invalid repository URL: {}", url),
+    };
+
+    let new_path = super::get_workspaces_dir()?
+        .join(format!("{}-{}", owner, repo))
+        .join(git::sanitize_branch(&worktree.branch));
+    if new_path.exists() {
+        anyhow::bail!("Destination already exists: {}", new_path.display());
+    }
+
+    // Reuse a remote that already points at the fork, otherwise add one named after the owner
+    let remotes = git::run_git(&worktree.path, &["remote", "-v"])?;
+    let remote = match remotes
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .find(|(_, rest)| rest.split_whitespace().next() == Some(url))
+    {
+        Some((name, _)) => name.to_string(),
+        None => {
+            if remotes
+                .lines()
+                .any(|line| line.split('\t').next() == Some(owner))
+            {
+                anyhow::bail!(
+                    "Remote '{}' already exists with a different URL. Remove or rename it first",
+                    owner
+                );
+            }
+            println!("{} Adding remote '{}' → {}", "::".blue().bold(), owner, url);
+            git::run_git(&worktree.path, &["remote", "add", owner, url])?;
+            owner.to_string()
+        }
+    };
+
+    // The session name is derived from the worktree directory, so the old
+    // session has to go before the new one can be created. If we're running
+    // inside it, rename it and kill it only after switching away. zellij can't
+    // rename the session ws runs in, and killing it would kill ws mid-migration.
+    let old_session = super::get_session_name(&worktree.path)?;
+    let inside_old = mux::backend().current_session().as_deref() == Some(old_session.as_str());
+    if inside_old && zellij::is_inside_zellij() {
+        anyhow::bail!(
+            "Can't migrate from inside its own zellij session ({}); run this from another session",
+            old_session
+        );
+    }
+
+    println!(
+        "{} Pushing '{}' to {}...",
+        "::".blue().bold(),
+        worktree.branch,
+        remote
+    );
+    let status = Command::new("git")
+        .current_dir(&worktree.path)
+        .args(["push", "--set-upstream", &remote, &worktree.branch])
+        .logged()
+        .status()
+        .context("Failed to run git push")?;
+    if !status.success() {
+        anyhow::bail!("Failed to push {} to {}", worktree.branch, remote);
+    }
+
+    std::fs::create_dir_all(new_path.parent().context("Invalid destination")?)
+        .context("Failed to create fork workspace directory")?;
+    let output = Command::new("git")
+        .current_dir(&main_root)
+        .arg("worktree")
+        .arg("move")
+        .arg(&worktree.path)
+        .arg(&new_path)
+        .logged()
+        .output()
+        .context("Failed to run git worktree move")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to move worktree: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    // Only touch the old session once the move succeeded, so a failed move
+    // leaves it as it was
+    let mut pending_kill = None;
+    if mux::backend().session_exists(&old_session) {
+        if inside_old {
+            let renamed = format!("{}-migrating", old_session);
+            tmux::rename_session(&old_session, &renamed)?;
+            pending_kill = Some(renamed);
+        } else {
+            mux::backend().kill_session(&old_session)?;
+            println!("{} Killed session: {}", "::".green().bold(), old_session);
+        }
+    }
+
+    println!(
+        "{} Migrated '{}' to {} (tracking {}/{})",
+        "::".green().bold(),
+        worktree.branch,
+        new_path.display(),
+        remote,
+        worktree.branch
+    );
+
+    match pending_kill {
+        Some(renamed) => {
+            super::open(Some(new_path.display().to_string()))?;
+            mux::backend().kill_session(&renamed)
+        }
+        None => super::open(Some(new_path.display().to_string())),
+    }
+}
+
+/// Save a short alias for a branch in ~/.ws/config.toml
+pub fn alias_add(short: &str, branch: &str) -> Result<()> {
+    if short.is_empty()
+        || short == branch
+        || !short
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        anyhow::bail!(
+            "Invalid alias: {} (use letters, digits, '-', '_' or '.')",
+            short
+        );
+    }
+
+    if let Ok(git_root) = git::get_root(None) {
+        if git::branch_exists(&git_root, short) {
+            println!(
+                "{} '{}' is also a branch name; the alias takes precedence",
+                "::".yellow().bold(),
+                short
+            );
+        }
+        if !git::branch_exists(&git_root, branch) {
+            println!(
+                "{} Branch '{}' doesn't exist (yet) in this repository",
+                "::".yellow().bold(),
+                branch
+            );
+        }
+    }
+
+    let mut config = Config::load()?;
+    config.aliases.insert(short.to_string(), branch.to_string());
+    config.save()?;
+
+    println!("{} {} → {}", "::".green().bold(), short, branch);
+    Ok(())
+}
+
+/// Delete a branch alias
+pub fn alias_remove(short: &str) -> Result<()> {
+    let mut config = Config::load()?;
+    if config.aliases.remove(short).is_none() {
+        anyhow::bail!("No alias named {}", short);
+    }
+    config.save()?;
+
+    println!("{} Removed alias {}", "::".green().bold(), short);
+    Ok(())
+}
+
+/// Show all branch aliases
+pub fn alias_list() -> Result<()> {
+    let config = Config::load_for_current_repo()?;
+    if config.aliases.is_empty() {
+        println!(
+            "{} No aliases. Add one with: ws worktree alias <short> <branch>",
+            "::".yellow().bold()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Branch Aliases".bold());
+    println!();
+    let width = config.aliases.keys().map(|k| k.len()).max().unwrap_or(0);
+    for (short, branch) in &config.aliases {
+        println!("  {:width$}  → {}", short.cyan(), branch, width = width);
+    }
+    Ok(())
+}
+
+/// Run the pre-merge checklist from .ws/scripts.toml, exiting with 1 if any check fails
+pub fn checklist(branch: Option<&str>, fix: bool) -> Result<()> {
+    let path = match branch {
+        Some(branch) => resolve_worktree(branch)?.path,
+        None => git::get_root(None).context("Not in a git repository")?,
+    };
+
+    let scripts = Scripts::load(&path)?;
+    if scripts.pre_merge.is_empty() {
+        anyhow::bail!(
+            "No checklist in {}. Add:\n\n[checklist]\npre_merge = [\"cargo test\", \"cargo clippy\", \"cargo fmt --check\"]",
+            Scripts::path(&path).display()
+        );
+    }
+
+    let mut results = Vec::new();
+    for check in &scripts.pre_merge {
+        let command = match fix.then(|| fix_command(check)).flatten() {
+            Some(fixed) => fixed,
+            None => check.clone(),
+        };
+
+        println!("{} {}", "::".blue().bold(), command);
+        let status = Command::new("sh")
+            .current_dir(&path)
+            .args(["-c", &command])
+            .logged()
+            .status()
+            .context(format!("Failed to run {}", command))?;
+        results.push((command, status.success()));
+    }
+
+    println!();
+    for (command, passed) in &results {
+        if *passed {
+            println!("  {} {}", "✓".green(), command);
+        } else {
+            println!("  {} {}", "✗".red(), command);
+        }
+    }
+
+    let failed = results.iter().filter(|(_, passed)| !passed).count();
+    println!();
+    if failed > 0 {
+        println!(
+            "{} {} of {} checks failed",
+            "::".red().bold(),
+            failed,
+            results.len()
+        );
+        std::process::exit(1);
+    }
+
+    println!(
+        "{} All {} checks passed",
+        "::".green().bold(),
+        results.len()
+    );
+    Ok(())
+}
+
+/// Auto-fix variant of a known check command (None if it has no fix mode)
+fn fix_command(check: &str) -> Option<String> {
+    let check = check.trim();
+
+    if let Some(rest) = check.strip_prefix("cargo clippy") {
+        return Some(format!(
+            "cargo clippy --fix --allow-dirty --allow-staged{}",
+            rest
+        ));
+    }
+    if check.contains("prettier") && check.contains("--check") {
+        return Some(check.replace("--check", "--write"));
+    }
+    if check.contains("eslint") || check.starts_with("ruff check") {
+        return Some(format!("{} --fix", check));
+    }
+    // cargo fmt, black, ruff format, gofmt-style checkers fix when --check is dropped
+    if check.contains(" --check") {
+        return Some(check.replace(" --check", ""));
+    }
+
+    None
+}
+
+/// Create sessions for every worktree that doesn't have one yet
+pub fn open_all(exclude_main: bool, attach_to: Option<&str>) -> Result<()> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let main_root = git::get_main_worktree_root(Some(&git_root))?;
+    let worktrees = git::list_worktrees(&git_root)?;
+    let active_sessions = mux::backend().get_active_sessions();
+
+    // Resolve the target up front so a typo doesn't leave us half done
+    let attach_session = match attach_to {
+        Some(target) => {
+            let worktree = git::find_worktree(&git_root, target)?
+                .context(format!("Worktree not found: {}", target))?;
+            Some(super::get_session_name(&worktree.path)?)
+        }
+        None => None,
+    };
+
+    let mut rows = Vec::new();
+    let mut created = Vec::new();
+    for wt in &worktrees {
+        if exclude_main && wt.path == main_root {
+            continue;
+        }
+
+        let session = super::get_session_name(&wt.path)?;
+        if active_sessions.contains(&session) {
+            rows.push((wt.branch.clone(), session, false));
+            continue;
+        }
+
+        let window_title = super::get_window_title(&wt.path)?;
+        mux::backend().create_session_with_title(&session, &wt.path, &window_title)?;
+        created.push(session.clone());
+        rows.push((wt.branch.clone(), session, true));
+    }
+
+    if rows.is_empty() {
+        println!("{} No worktrees to open", "::".yellow().bold());
+        return Ok(());
+    }
+
+    let branch_width = rows
+        .iter()
+        .map(|(b, _, _)| b.len())
+        .max()
+        .unwrap_or(6)
+        .max(6);
+    let session_width = rows
+        .iter()
+        .map(|(_, s, _)| s.len())
+        .max()
+        .unwrap_or(7)
+        .max(7);
+    println!(
+        "  {:bw$}  {:sw$}  {}",
+        "BRANCH".bold(),
+        "SESSION".bold(),
+        "STATUS".bold(),
+        bw = branch_width,
+        sw = session_width
+    );
+    for (branch, session, was_created) in &rows {
+        let status = if *was_created {
+            format!("{} created", "✓".green())
+        } else {
+            format!("{} running", "●".green())
+        };
+        println!(
+            "  {:bw$}  {:sw$}  {}",
+            branch,
+            session.dimmed(),
+            status,
+            bw = branch_width,
+            sw = session_width
+        );
+    }
+    println!();
+    println!(
+        "{} {} sessions created, {} already running",
+        "::".green().bold(),
+        created.len(),
+        rows.len() - created.len()
+    );
+
+    if let Some(session) = attach_session {
+        return mux::backend().attach(&session);
+    }
+
+    if created.is_empty() {
+        return Ok(());
+    }
+
+    // tmux's chooser only lists tmux sessions
+    let tool = mux::tool();
+    if tool == MultiplexerTool::Zellij || !tmux::is_inside_tmux() {
+        let attach = match tool {
+            MultiplexerTool::Tmux => format!("tmux attach -t {}", created[0]),
+            MultiplexerTool::Zellij => format!("zellij attach {}", created[0]),
+        };
+        println!("{} Attach with: {}", "::".blue().bold(), attach);
+        return Ok(());
+    }
+
+    // Session switcher limited to the sessions we just created
+    let names: Vec<String> = created.iter().map(|s| regex_escape(s)).collect();
+    let filter = format!("#{{m/r:^({})$,#{{session_name}}}}", names.join("|"));
+    let status = Command::new("tmux")
+        .args(["choose-tree", "-Zs", "-f", &filter])
+        .logged()
+        .status()
+        .context("Failed to open tmux session switcher")?;
+    if !status.success() {
+        anyhow::bail!("Failed to open tmux session switcher");
+    }
+
+    Ok(())
+}
+
+/// Escape regex metacharacters for a tmux `m/r` filter
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$#,".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Show what a worktree has locally that origin doesn't
+pub fn remote_diff(branch: &str, commits: bool) -> Result<()> {
+    let worktree = resolve_worktree(branch)?;
+    let remote_ref = format!("origin/{}", worktree.branch);
+
+    let has_upstream = Command::new("git")
+        .current_dir(&worktree.path)
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("refs/remotes/{}", remote_ref))
+        .logged()
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if !has_upstream {
+        println!(
+            "{} No upstream configured for {}",
+            "::".yellow().bold(),
+            worktree.branch
+        );
+        println!(
+            "   Push it with: git -C {} push -u origin {}",
+            worktree.path.display(),
+            worktree.branch
+        );
+        return Ok(());
+    }
+
+    let args: Vec<String> = if commits {
+        vec![
+            "log".into(),
+            "--format=%h\t%s".into(),
+            format!("{}..HEAD", remote_ref),
+        ]
+    } else {
+        vec![
+            "diff".into(),
+            "--numstat".into(),
+            format!("{}...HEAD", remote_ref),
+        ]
+    };
+    let output = Command::new("git")
+        .current_dir(&worktree.path)
+        .args(&args)
+        .logged()
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().filter(|l| !l.is_empty()).collect();
+    if lines.is_empty() {
+        println!(
+            "{} {} is in sync with {}",
+            "::".green().bold(),
+            worktree.branch,
+            remote_ref
+        );
+        return Ok(());
+    }
+
+    if commits {
+        println!(
+            "{} {} unpushed commit(s) on {}",
+            "::".blue().bold(),
+            lines.len(),
+            worktree.branch
+        );
+        for line in &lines {
+            let (sha, subject) = line.split_once('\t').unwrap_or((line, ""));
+            println!("  {} {}", sha.yellow(), subject);
+        }
+        return Ok(());
+    }
+
+    // numstat lines are "<added>\t<removed>\t<path>", with "-" counts for binary files
+    let mut rows = Vec::new();
+    for line in &lines {
+        let mut parts = line.splitn(3, '\t');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(added), Some(removed), Some(file)) => rows.push((file, added, removed)),
+            _ => continue,
+        }
+    }
+
+    let file_width = rows
+        .iter()
+        .map(|(f, _, _)| f.len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    println!(
+        "{} {} file(s) changed since {}",
+        "::".blue().bold(),
+        rows.len(),
+        remote_ref
+    );
+    println!(
+        "  {:fw$}  {:>6}  {:>6}",
+        "FILE".bold(),
+        "+".bold(),
+        "-".bold(),
+        fw = file_width
+    );
+    let (mut total_added, mut total_removed) = (0u64, 0u64);
+    for (file, added, removed) in &rows {
+        total_added += added.parse::<u64>().unwrap_or(0);
+        total_removed += removed.parse::<u64>().unwrap_or(0);
+        println!(
+            "  {:fw$}  {:>6}  {:>6}",
+            file,
+            format!("+{}", added).green(),
+            format!("-{}", removed).red(),
+            fw = file_width
+        );
+    }
+    println!(
+        "  {:fw$}  {:>6}  {:>6}",
+        "total".dimmed(),
+        format!("+{}", total_added).green(),
+        format!("-{}", total_removed).red(),
+        fw = file_width
+    );
+    Ok(())
+}
+
+/// Define (or replace) a named group of branches
+pub fn group_set(name: &str, branches: &[String]) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        anyhow::bail!(
+            "Invalid group name: {} (use letters, digits, '-', '_' or '.')",
+            name
+        );
+    }
+
+    let mut members: Vec<String> = Vec::new();
+    for branch in branches {
+        if !members.contains(branch) {
+            members.push(branch.clone());
+        }
+    }
+
+    if let Ok(git_root) = git::get_root(None) {
+        for branch in &members {
+            if !git::branch_exists(&git_root, &Config::resolve_alias(branch)) {
+                println!(
+                    "{} Branch '{}' doesn't exist (yet) in this repository",
+                    "::".yellow().bold(),
+                    branch
+                );
+            }
+        }
+    }
+
+    let mut config = Config::load()?;
+    config.groups.insert(name.to_string(), members.clone());
+    config.save()?;
+
+    println!("{} {} → {}", "::".green().bold(), name, members.join(", "));
+    Ok(())
+}
+
+/// Delete a worktree group
+pub fn group_remove(name: &str) -> Result<()> {
+    let mut config = Config::load()?;
+    if config.groups.remove(name).is_none() {
+        anyhow::bail!("No group named {}", name);
+    }
+    config.save()?;
+
+    println!("{} Removed group {}", "::".green().bold(), name);
+    Ok(())
+}
+
+/// Show all worktree groups
+pub fn group_list() -> Result<()> {
+    let config = Config::load_for_current_repo()?;
+    if config.groups.is_empty() {
+        println!(
+            "{} No groups. Add one with: ws worktree group <name> <branches...>",
+            "::".yellow().bold()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Worktree Groups".bold());
+    println!();
+    for (name, branches) in &config.groups {
+        println!("  {}", name.cyan());
+        for branch in branches {
+            println!("    {}", branch);
+        }
+    }
+    Ok(())
+}
+
+/// Run a shell command in every worktree (or only those in a group)
+pub fn foreach(command: &str, group: Option<&str>) -> Result<()> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let mut worktrees = git::list_worktrees(&git_root)?;
+
+    if let Some(group) = group {
+        let config = Config::load_for_repo(&git_root)?;
+        let members: Vec<String> = match config.groups.get(group) {
+            Some(branches) => branches.iter().map(|b| Config::resolve_alias(b)).collect(),
+            None => anyhow::bail!("No group named {}", group),
+        };
+        worktrees.retain(|wt| members.contains(&wt.branch));
+    }
+
+    if worktrees.is_empty() {
+        println!("{} No worktrees to run in", "::".yellow().bold());
+        return Ok(());
+    }
+
+    let mut results = Vec::new();
+    for wt in &worktrees {
+        println!("{} {}: {}", "::".blue().bold(), wt.branch.bold(), command);
+        let success = Command::new("sh")
+            .current_dir(&wt.path)
+            .args(["-c", command])
+            .logged()
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        results.push((wt.branch.as_str(), success));
+        println!();
+    }
+
+    let failed = results.iter().filter(|(_, ok)| !ok).count();
+    for (branch, ok) in &results {
+        if *ok {
+            println!("  {} {}", "✓".green(), branch);
+        } else {
+            println!("  {} {}", "✗".red(), branch);
+        }
+    }
+    println!();
+
+    if failed > 0 {
+        println!(
+            "{} Failed in {} of {} worktree(s)",
+            "::".red().bold(),
+            failed,
+            results.len()
+        );
+        std::process::exit(1);
+    }
+
+    println!(
+        "{} Succeeded in {} worktree(s)",
+        "::".green().bold(),
+        results.len()
+    );
+    Ok(())
+}
+
+/// Copy a worktree (commit and uncommitted changes) onto a new branch
+pub fn duplicate(source: &str, new_branch: &str) -> Result<()> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let source = resolve_worktree(source)?;
+
+    if git::branch_exists(&git_root, new_branch) {
+        anyhow::bail!("Branch '{}' already exists", new_branch);
+    }
+
+    let repo_name = git_root
+        .file_name()
+        .context("Invalid git root")?
+        .to_string_lossy()
+        .to_string();
+    let repo_dir = super::get_workspaces_dir()?.join(&repo_name);
+    let wt_path = repo_dir.join(git::sanitize_branch(new_branch));
+    if wt_path.exists() {
+        anyhow::bail!("{} already exists", wt_path.display());
+    }
+    std::fs::create_dir_all(&repo_dir).context("Failed to create repo directory")?;
+
+    let commit = git::run_git(&source.path, &["rev-parse", "HEAD"])?;
+    let commit = commit.trim();
+    let dirty = !git::run_git(&source.path, &["status", "--porcelain"])?
+        .trim()
+        .is_empty();
+
+    println!(
+        "{} Creating '{}' at {} (from '{}')",
+        "::".blue().bold(),
+        new_branch,
+        &commit[..commit.len().min(7)],
+        source.branch
+    );
+    git::run_git(
+        &git_root,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            new_branch,
+            &wt_path.to_string_lossy(),
+            commit,
+        ],
+    )?;
+
+    if dirty {
+        // The stash is shared between worktrees: apply it to the copy, then
+        // pop it back in the source so both end up with the same changes
+        let message = format!("ws duplicate {}", new_branch);
+        git::run_git(
+            &source.path,
+            &["stash", "push", "--include-untracked", "-m", &message],
+        )?;
+        let applied = git::run_git(&wt_path, &["stash", "apply", "--index"]);
+        let restored = git::run_git(&source.path, &["stash", "pop", "--index"]);
+
+        if let Err(e) = restored {
+            println!(
+                "{} Could not restore changes in '{}'; they are still in `git stash list`",
+                "✗".red(),
+                source.branch
+            );
+            return Err(e);
+        }
+        match applied {
+            Ok(_) => println!("  {} Copied uncommitted changes", "✓".green()),
+            Err(e) => println!("  {} Could not copy uncommitted changes: {}", "✗".red(), e),
+        }
+    }
+
+    println!(
+        "{} Worktree created at {}",
+        "::".green().bold(),
+        wt_path.display()
+    );
+
+    super::open(Some(wt_path.display().to_string()))
+}
+
+/// Check out a branch as it was at a date, in a detached worktree
+pub fn time_travel(branch: &str, date: &str) -> Result<()> {
+    let git_root = git::get_root(None).context("Not in a git repository")?;
+    let branch = Config::resolve_alias(branch);
+
+    let commit = git::run_git(
+        &git_root,
+        &["rev-list", "-1", &format!("--before={}", date), &branch],
+    )?;
+    let commit = commit.trim();
+    if commit.is_empty() {
+        anyhow::bail!("No commit on {} before {}", branch, date);
+    }
+
+    let repo_name = git_root
+        .file_name()
+        .context("Invalid git root")?
+        .to_string_lossy()
+        .to_string();
+    let date_safe: String = date
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let repo_dir = super::get_workspaces_dir()?.join(&repo_name);
+    let wt_path = repo_dir.join(format!(
+        "time-travel-{}-{}",
+        git::sanitize_branch(&branch),
+        date_safe
+    ));
+
+    if wt_path.exists() {
+        println!(
+            "{} Time-travel worktree already exists at {}",
+            "::".yellow().bold(),
+            wt_path.display()
+        );
+    } else {
+        std::fs::create_dir_all(&repo_dir).context("Failed to create repo directory")?;
+        let subject = git::run_git(&git_root, &["log", "-1", "--format=%h %cs %s", commit])?;
+        println!(
+            "{} {} at {}: {}",
+            "::".blue().bold(),
+            branch,
+            date,
+            subject.trim()
+        );
+        git::run_git(
+            &git_root,
+            &[
+                "worktree",
+                "add",
+                "--detach",
+                &wt_path.to_string_lossy(),
+                commit,
+            ],
+        )?;
+        println!(
+            "{} Worktree created at {}",
+            "::".green().bold(),
+            wt_path.display()
+        );
+    }
+
+    println!(
+        "{} This is a read-only snapshot (detached HEAD); don't commit from it. Remove it with: ws delete {}",
+        "⚠".yellow().bold(),
+        wt_path.display()
+    );
+
+    super::open(Some(wt_path.display().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_log_keeps_dash_and_plus_lines_inside_hunks() {
+        let log = "abc123\tAda\t2024-01-01\tTweak query\n\
+                   diff --git a/q.sql b/q.sql\n\
+                   --- a/q.sql\n\
+                   +++ b/q.sql\n\
+                   @@ -1,2 +1,2 @@\n\
+                   --- old comment\n\
+                   +++ new counter\n\
+                    select 1;\n";
+        let changes = parse_line_log(log);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "q.sql");
+        assert_eq!(changes[0].before, vec!["-- old comment"]);
+        assert_eq!(changes[0].after, vec!["++ new counter"]);
+    }
+}
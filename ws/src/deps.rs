@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Branch dependencies for stacked PR workflows (~/.ws/<repo>/deps.toml)
+#[derive(Debug, Default)]
+pub struct Dependencies {
+    /// Branch -> branches it depends on
+    pub deps: BTreeMap<String, Vec<String>>,
+    path: PathBuf,
+}
+
+impl Dependencies {
+    /// Get the deps file path for a repository
+    pub fn path(repo_name: &str) -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not determine home directory")?;
+        Ok(home.join(".ws").join(repo_name).join("deps.toml"))
+    }
+
+    /// Load dependencies for a repository, or return an empty set
+    pub fn load(repo_name: &str) -> Result<Self> {
+        let path = Self::path(repo_name)?;
+        let mut deps = BTreeMap::new();
+
+        if path.exists() {
+            let content = fs::read_to_string(&path).context("Failed to read deps file")?;
+
+            for line in content.lines() {
+                let line = line.trim();
+                if line.starts_with('#') || line.is_empty() {
+                    continue;
+                }
+
+                if let Some((key, value)) = line.split_once('=') {
+                    let branch = key.trim().trim_matches('"').to_string();
+                    let bases: Vec<String> = value
+                        .trim()
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .split(',')
+                        .map(|b| b.trim().trim_matches('"').to_string())
+                        .filter(|b| !b.is_empty())
+                        .collect();
+
+                    if !branch.is_empty() && !bases.is_empty() {
+                        deps.insert(branch, bases);
+                    }
+                }
+            }
+        }
+
+        Ok(Self { deps, path })
+    }
+
+    /// Save dependencies to file
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context("Failed to create deps directory")?;
+        }
+
+        let mut content =
+            String::from("# Branch dependencies: branch = [\"branches it depends on\"]\n");
+        for (branch, bases) in &self.deps {
+            let list: Vec<String> = bases.iter().map(|b| format!("\"{}\"", b)).collect();
+            content.push_str(&format!("\"{}\" = [{}]\n", branch, list.join(", ")));
+        }
+
+        fs::write(&self.path, content).context("Failed to write deps file")?;
+        Ok(())
+    }
+
+    /// Record that `branch` depends on `base` (returns false if already recorded)
+    pub fn add(&mut self, branch: &str, base: &str) -> bool {
+        let bases = self.deps.entry(branch.to_string()).or_default();
+        if bases.iter().any(|b| b == base) {
+            return false;
+        }
+        bases.push(base.to_string());
+        true
+    }
+
+    /// Remove a single dependency (returns false if it wasn't recorded)
+    pub fn remove(&mut self, branch: &str, base: &str) -> bool {
+        let removed = match self.deps.get_mut(branch) {
+            Some(bases) => {
+                let before = bases.len();
+                bases.retain(|b| b != base);
+                bases.len() != before
+            }
+            None => false,
+        };
+        self.deps.retain(|_, bases| !bases.is_empty());
+        removed
+    }
+
+    /// Drop every dependency involving a branch (returns true if anything changed)
+    pub fn forget(&mut self, branch: &str) -> bool {
+        let mut changed = self.deps.remove(branch).is_some();
+        for bases in self.deps.values_mut() {
+            let before = bases.len();
+            bases.retain(|b| b != branch);
+            changed |= bases.len() != before;
+        }
+        self.deps.retain(|_, bases| !bases.is_empty());
+        changed
+    }
+
+    /// Branches that directly depend on `base`
+    pub fn dependents_of(&self, base: &str) -> Vec<&str> {
+        self.deps
+            .iter()
+            .filter(|(_, bases)| bases.iter().any(|b| b == base))
+            .map(|(branch, _)| branch.as_str())
+            .collect()
+    }
+
+    /// Check whether `from` depends on `to`, directly or through other branches
+    pub fn depends_on(&self, from: &str, to: &str) -> bool {
+        let mut stack = vec![from];
+        let mut seen = std::collections::HashSet::new();
+
+        while let Some(branch) = stack.pop() {
+            if !seen.insert(branch) {
+                continue;
+            }
+            if let Some(bases) = self.deps.get(branch) {
+                for base in bases {
+                    if base == to {
+                        return true;
+                    }
+                    stack.push(base);
+                }
+            }
+        }
+        false
+    }
+}
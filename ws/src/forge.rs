@@ -0,0 +1,195 @@
+use crate::config::{Config, Forge};
+use crate::git::RunLogged;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// A pull request (GitHub) or merge request (GitLab)
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub branch: String,
+    /// OPEN, MERGED or CLOSED
+    pub state: String,
+    pub url: String,
+    /// GitHub's statusCheckRollup; for GitLab a single entry with the head pipeline's state
+    pub checks: Value,
+}
+
+/// The forge selected by `forge` in the config (GitHub by default)
+pub fn current() -> Forge {
+    Config::load_for_current_repo()
+        .map(|c| c.forge)
+        .unwrap_or(Forge::GitHub)
+}
+
+/// Whether the forge's CLI is installed
+pub fn is_installed(forge: Forge) -> bool {
+    which::which(forge.binary()).is_ok()
+}
+
+/// Bail with an install hint if the forge's CLI is missing
+pub fn require(forge: Forge) -> Result<()> {
+    if !is_installed(forge) {
+        anyhow::bail!("{}", forge.install_hint());
+    }
+    Ok(())
+}
+
+/// List open pull requests, optionally only the ones from `head`
+pub fn list(forge: Forge, dir: &Path, head: Option<&str>, limit: u32) -> Result<Vec<PullRequest>> {
+    let limit = limit.to_string();
+    let mut args: Vec<&str> = match forge {
+        Forge::GitHub => vec![
+            "pr",
+            "list",
+            "--json",
+            "number,title,headRefName,state,url,statusCheckRollup",
+            "--limit",
+            &limit,
+        ],
+        Forge::GitLab => vec!["mr", "list", "--output", "json", "--per-page", &limit],
+    };
+    if let Some(head) = head {
+        args.push(match forge {
+            Forge::GitHub => "--head",
+            Forge::GitLab => "--source-branch",
+        });
+        args.push(head);
+    }
+
+    let output = Command::new(forge.binary())
+        .current_dir(dir)
+        .args(&args)
+        .logged()
+        .output()
+        .with_context(|| format!("Failed to run {} {} list", forge.binary(), args[0]))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to list pull requests: {}", stderr.trim());
+    }
+
+    let prs: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    Ok(prs.iter().filter_map(|pr| parse(forge, pr)).collect())
+}
+
+/// Look up a single pull request by number
+pub fn view(forge: Forge, dir: &Path, number: u32) -> Result<PullRequest> {
+    let number_str = number.to_string();
+    let args: Vec<&str> = match forge {
+        Forge::GitHub => vec![
+            "pr",
+            "view",
+            &number_str,
+            "--json",
+            "number,title,headRefName,state,url,statusCheckRollup",
+        ],
+        Forge::GitLab => vec!["mr", "view", &number_str, "--output", "json"],
+    };
+
+    let output = Command::new(forge.binary())
+        .current_dir(dir)
+        .args(&args)
+        .logged()
+        .output()
+        .context("Failed to get PR info")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to get PR info. Make sure PR #{} exists.", number);
+    }
+
+    let pr: Value = serde_json::from_slice(&output.stdout).context("Failed to parse PR info")?;
+    parse(forge, &pr).context("PR has no branch")
+}
+
+/// Open the forge's web form to create a pull request for the current branch
+pub fn create_web(forge: Forge, dir: &Path) -> Result<()> {
+    let kind = match forge {
+        Forge::GitHub => "pr",
+        Forge::GitLab => "mr",
+    };
+
+    let status = Command::new(forge.binary())
+        .current_dir(dir)
+        .args([kind, "create", "--web"])
+        .logged()
+        .status()
+        .with_context(|| format!("Failed to run {} {} create", forge.binary(), kind))?;
+
+    if !status.success() {
+        anyhow::bail!("Failed to create pull request");
+    }
+
+    Ok(())
+}
+
+/// Open a pull request in the browser, without printing anything (safe inside a TUI)
+pub fn open_web(forge: Forge, dir: &Path, number: u32) -> Result<()> {
+    let kind = match forge {
+        Forge::GitHub => "pr",
+        Forge::GitLab => "mr",
+    };
+
+    let status = Command::new(forge.binary())
+        .current_dir(dir)
+        .args([kind, "view", &number.to_string(), "--web"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .logged()
+        .status()
+        .with_context(|| format!("Failed to run {} {} view", forge.binary(), kind))?;
+
+    if !status.success() {
+        anyhow::bail!("{} {} view --web failed", forge.binary(), kind);
+    }
+
+    Ok(())
+}
+
+fn parse(forge: Forge, pr: &Value) -> Option<PullRequest> {
+    match forge {
+        Forge::GitHub => Some(PullRequest {
+            number: pr["number"].as_u64()?,
+            title: pr["title"].as_str().unwrap_or("").to_string(),
+            branch: pr["headRefName"].as_str()?.to_string(),
+            state: pr["state"].as_str().unwrap_or("").to_string(),
+            url: pr["url"].as_str().unwrap_or("").to_string(),
+            checks: pr["statusCheckRollup"].clone(),
+        }),
+        Forge::GitLab => Some(PullRequest {
+            number: pr["iid"].as_u64()?,
+            title: pr["title"].as_str().unwrap_or("").to_string(),
+            branch: pr["source_branch"].as_str()?.to_string(),
+            state: match pr["state"].as_str().unwrap_or("") {
+                "opened" => "OPEN",
+                "merged" => "MERGED",
+                "closed" | "locked" => "CLOSED",
+                _ => "",
+            }
+            .to_string(),
+            url: pr["web_url"].as_str().unwrap_or("").to_string(),
+            checks: pipeline_checks(&pr["head_pipeline"]),
+        }),
+    }
+}
+
+/// Express a GitLab pipeline as a one-entry check rollup, so the GitHub
+/// check parsing applies to both forges
+fn pipeline_checks(pipeline: &Value) -> Value {
+    let state = match pipeline["status"].as_str() {
+        Some("success") => "SUCCESS",
+        Some("failed") | Some("canceled") => "FAILURE",
+        Some("created")
+        | Some("waiting_for_resource")
+        | Some("preparing")
+        | Some("pending")
+        | Some("running")
+        | Some("scheduled")
+        | Some("manual") => "PENDING",
+        _ => return Value::Array(Vec::new()),
+    };
+    serde_json::json!([{ "state": state }])
+}
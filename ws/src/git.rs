@@ -1,9 +1,66 @@
 use anyhow::{Context, Result};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
-/// Get the default branch for a repository (main, master, develop, etc.)
+/// Whether external commands are echoed to stderr (`ws --verbose`)
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// Log a command (program, args, cwd) to stderr before it runs, when `--verbose` is set
+pub trait RunLogged {
+    fn logged(&mut self) -> &mut Self;
+}
+
+impl RunLogged for Command {
+    fn logged(&mut self) -> &mut Self {
+        if VERBOSE.load(Ordering::Relaxed) {
+            let mut line = self.get_program().to_string_lossy().to_string();
+            for arg in self.get_args() {
+                line.push(' ');
+                line.push_str(&arg.to_string_lossy());
+            }
+            let cwd = match self.get_current_dir() {
+                Some(dir) => dir.to_path_buf(),
+                None => std::env::current_dir().unwrap_or_default(),
+            };
+            eprintln!("$ {}  (in {})", line, cwd.display());
+        }
+        self
+    }
+}
+
+/// Default branches already looked up during this invocation, by directory
+static DEFAULT_BRANCHES: Mutex<BTreeMap<PathBuf, String>> = Mutex::new(BTreeMap::new());
+
+/// Get the default branch for a repository: origin/HEAD if set (e.g. `trunk`),
+/// else the first of main, master, develop that exists. Cached per directory.
 pub fn get_default_branch(git_root: Option<&Path>) -> String {
+    let key = match git_root {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir().unwrap_or_default(),
+    };
+    if let Some(branch) = DEFAULT_BRANCHES
+        .lock()
+        .ok()
+        .and_then(|c| c.get(&key).cloned())
+    {
+        return branch;
+    }
+
+    let branch = detect_default_branch(git_root);
+    if let Ok(mut cache) = DEFAULT_BRANCHES.lock() {
+        cache.insert(key, branch.clone());
+    }
+    branch
+}
+
+fn detect_default_branch(git_root: Option<&Path>) -> String {
     // Try to get from remote HEAD (most reliable for repos with remotes)
     let mut cmd = Command::new("git");
     if let Some(path) = git_root {
@@ -11,7 +68,7 @@ pub fn get_default_branch(git_root: Option<&Path>) -> String {
     }
     cmd.args(["symbolic-ref", "refs/remotes/origin/HEAD", "--short"]);
 
-    if let Ok(output) = cmd.output() {
+    if let Ok(output) = cmd.logged().output() {
         if output.status.success() {
             let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
             // Remove "origin/" prefix if present
@@ -33,7 +90,7 @@ pub fn get_default_branch(git_root: Option<&Path>) -> String {
         }
         cmd.args(["rev-parse", "--verify", &format!("origin/{}", candidate)]);
 
-        if let Ok(output) = cmd.output() {
+        if let Ok(output) = cmd.logged().output() {
             if output.status.success() {
                 return candidate.to_string();
             }
@@ -46,7 +103,7 @@ pub fn get_default_branch(git_root: Option<&Path>) -> String {
         }
         cmd.args(["rev-parse", "--verify", candidate]);
 
-        if let Ok(output) = cmd.output() {
+        if let Ok(output) = cmd.logged().output() {
             if output.status.success() {
                 return candidate.to_string();
             }
@@ -57,6 +114,24 @@ pub fn get_default_branch(git_root: Option<&Path>) -> String {
     "main".to_string()
 }
 
+/// Run git in a directory and return its stdout, failing with its stderr
+pub fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .logged()
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// Information about a git worktree
 #[derive(Debug, Clone)]
 pub struct Worktree {
@@ -72,7 +147,7 @@ pub fn get_root(path: Option<&Path>) -> Result<PathBuf> {
     }
     cmd.args(["rev-parse", "--show-toplevel"]);
 
-    let output = cmd.output().context("Failed to run git")?;
+    let output = cmd.logged().output().context("Failed to run git")?;
 
     if !output.status.success() {
         anyhow::bail!("Not in a git repository");
@@ -90,7 +165,7 @@ pub fn get_main_worktree_root(path: Option<&Path>) -> Result<PathBuf> {
     }
     cmd.args(["rev-parse", "--git-common-dir"]);
 
-    let output = cmd.output().context("Failed to run git")?;
+    let output = cmd.logged().output().context("Failed to run git")?;
 
     if !output.status.success() {
         anyhow::bail!("Not in a git repository");
@@ -99,6 +174,11 @@ pub fn get_main_worktree_root(path: Option<&Path>) -> Result<PathBuf> {
     let git_common_dir = String::from_utf8(output.stdout)?.trim().to_string();
     let git_common_path = PathBuf::from(&git_common_dir);
 
+    if git_common_dir == ".git" {
+        // We're in the main worktree, use show-toplevel
+        return get_root(path);
+    }
+
     // If it ends with .git, get the parent directory
     if git_common_path.ends_with(".git") {
         if let Some(parent) = git_common_path.parent() {
@@ -106,12 +186,6 @@ pub fn get_main_worktree_root(path: Option<&Path>) -> Result<PathBuf> {
         }
     }
 
-    // Otherwise it might be a bare repo or relative path
-    if git_common_dir == ".git" {
-        // We're in the main worktree, use show-toplevel
-        return get_root(path);
-    }
-
     // For absolute paths ending in .git
     if git_common_path.is_absolute() {
         if let Some(parent) = git_common_path.parent() {
@@ -128,6 +202,7 @@ pub fn get_branch(path: &Path) -> Result<String> {
     let output = Command::new("git")
         .current_dir(path)
         .args(["branch", "--show-current"])
+        .logged()
         .output()
         .context("Failed to get branch")?;
 
@@ -140,6 +215,67 @@ pub fn get_branch(path: &Path) -> Result<String> {
     }
 }
 
+/// Branch, upstream distance and dirty state of a worktree
+#[derive(Debug, Clone, Default)]
+pub struct StatusSummary {
+    /// Current branch, or "detached"
+    pub branch: String,
+    pub ahead: u32,
+    pub behind: u32,
+    /// Uncommitted or untracked changes
+    pub dirty: bool,
+}
+
+/// Get branch, ahead/behind and dirty state from a single `git status` call
+pub fn status_summary(path: &Path) -> Result<StatusSummary> {
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["status", "--porcelain=v2", "--branch"])
+        .logged()
+        .output()
+        .context("Failed to run git status")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Not in a git repository");
+    }
+
+    let mut summary = StatusSummary::default();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            summary.branch = match head {
+                "(detached)" => "detached".to_string(),
+                name => name.to_string(),
+            };
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            // "+<ahead> -<behind>"
+            let mut counts = ab
+                .split_whitespace()
+                .map(|n| n.trim_start_matches(['+', '-']).parse().unwrap_or(0));
+            summary.ahead = counts.next().unwrap_or(0);
+            summary.behind = counts.next().unwrap_or(0);
+        } else if !line.starts_with('#') {
+            summary.dirty = true;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Subject and relative date ("2 days ago") of the last commit
+pub fn last_commit(path: &Path) -> Option<(String, String)> {
+    let output = Command::new("git")
+        .current_dir(path)
+        .args(["log", "-1", "--format=%s|%cr"])
+        .logged()
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // The subject may contain '|', the date never does
+    let (subject, date) = line.rsplit_once('|')?;
+    Some((subject.to_string(), date.to_string()))
+}
+
 /// Sanitize a branch name for use in paths/session names
 pub fn sanitize_branch(branch: &str) -> String {
     branch.replace('/', "-")
@@ -150,6 +286,7 @@ pub fn list_worktrees(git_root: &Path) -> Result<Vec<Worktree>> {
     let output = Command::new("git")
         .current_dir(git_root)
         .args(["worktree", "list", "--porcelain"])
+        .logged()
         .output()
         .context("Failed to list worktrees")?;
 
@@ -207,6 +344,7 @@ pub fn create_worktree(
     let _ = Command::new("git")
         .current_dir(git_root)
         .args(["fetch", "origin", base])
+        .logged()
         .output();
 
     // Try to create with new branch from origin/base
@@ -220,6 +358,7 @@ pub fn create_worktree(
             target_path.to_str().unwrap(),
             &format!("origin/{}", base),
         ])
+        .logged()
         .output()?;
 
     if result.status.success() {
@@ -230,6 +369,25 @@ pub fn create_worktree(
     let result = Command::new("git")
         .current_dir(git_root)
         .args(["worktree", "add", target_path.to_str().unwrap(), branch])
+        .logged()
+        .output()?;
+
+    if result.status.success() {
+        return Ok(());
+    }
+
+    // Try with new branch from a local-only base (e.g. an unpushed feature branch)
+    let result = Command::new("git")
+        .current_dir(git_root)
+        .args([
+            "worktree",
+            "add",
+            "-b",
+            branch,
+            target_path.to_str().unwrap(),
+            base,
+        ])
+        .logged()
         .output()?;
 
     if result.status.success() {
@@ -254,6 +412,7 @@ pub fn remove_worktree(git_root: &Path, worktree_path: &Path, force: bool) -> Re
     let result = Command::new("git")
         .current_dir(git_root)
         .args(&args)
+        .logged()
         .output()
         .context("Failed to remove worktree")?;
 
@@ -272,6 +431,7 @@ pub fn delete_branch(git_root: &Path, branch: &str, force: bool) -> Result<()> {
     let result = Command::new("git")
         .current_dir(git_root)
         .args(["branch", flag, branch])
+        .logged()
         .output()
         .context("Failed to delete branch")?;
 
@@ -286,9 +446,89 @@ pub fn delete_branch(git_root: &Path, branch: &str, force: bool) -> Result<()> {
     Ok(())
 }
 
-/// Find a worktree by branch name or path
+/// Get a branch's upstream as (remote, remote branch name), if it tracks one
+pub fn get_upstream(git_root: &Path, branch: &str) -> Option<(String, String)> {
+    let config = |key: &str| {
+        Command::new("git")
+            .current_dir(git_root)
+            .args(["config", &format!("branch.{}.{}", branch, key)])
+            .logged()
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+
+    let remote = config("remote")?;
+    let merge = config("merge")?;
+    let name = merge
+        .strip_prefix("refs/heads/")
+        .unwrap_or(&merge)
+        .to_string();
+    // "." means the upstream is a local branch
+    (remote != "." && !name.is_empty()).then_some((remote, name))
+}
+
+/// Delete a branch on a remote
+pub fn delete_remote_branch(git_root: &Path, remote: &str, branch: &str) -> Result<()> {
+    let result = Command::new("git")
+        .current_dir(git_root)
+        .args(["push", remote, "--delete", branch])
+        .logged()
+        .output()
+        .context("Failed to run git push --delete")?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        anyhow::bail!("Failed to delete {}/{}: {}", remote, branch, stderr.trim());
+    }
+
+    Ok(())
+}
+
+/// Check whether a local branch exists
+pub fn branch_exists(git_root: &Path, branch: &str) -> bool {
+    Command::new("git")
+        .current_dir(git_root)
+        .args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch),
+        ])
+        .logged()
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Get a remote's URL (None if the remote does not exist)
+pub fn get_remote_url(git_root: &Path, remote: &str) -> Option<String> {
+    Command::new("git")
+        .current_dir(git_root)
+        .args(["remote", "get-url", remote])
+        .logged()
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Get the repository name (directory name of the main worktree)
+pub fn get_repo_name(path: Option<&Path>) -> Result<String> {
+    let main_root = get_main_worktree_root(path)?;
+    main_root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .context("Could not determine repository name")
+}
+
+/// Find a worktree by branch name, branch alias, or path
 pub fn find_worktree(git_root: &Path, target: &str) -> Result<Option<Worktree>> {
     let worktrees = list_worktrees(git_root)?;
+    let target = crate::config::Config::resolve_alias(target);
+    let target = target.as_str();
 
     // Try to match by branch name first
     if let Some(wt) = worktrees.iter().find(|wt| wt.branch == target) {
@@ -339,3 +579,56 @@ pub fn find_worktree(git_root: &Path, target: &str) -> Result<Option<Worktree>>
 
     Ok(None)
 }
+
+/// Find worktrees whose branch or directory name contains the query (case-insensitive)
+pub fn find_worktree_fuzzy(git_root: &Path, query: &str) -> Result<Vec<Worktree>> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(list_worktrees(git_root)?
+        .into_iter()
+        .filter(|wt| {
+            let dir_name = wt
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            wt.branch.to_lowercase().contains(&query) || dir_name.contains(&query)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=ws", "-c", "user.email=ws@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn default_branch_follows_origin_head() {
+        let tmp = std::env::temp_dir().join(format!("ws-default-branch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        let remote = tmp.join("remote");
+        std::fs::create_dir_all(&remote).unwrap();
+
+        // The remote has a main branch too, but its HEAD is trunk
+        git(&remote, &["init", "-q", "-b", "trunk"]);
+        git(&remote, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&remote, &["branch", "main"]);
+        git(&tmp, &["clone", "-q", "remote", "clone"]);
+
+        let clone = tmp.join("clone");
+        assert_eq!(get_default_branch(Some(&clone)), "trunk");
+        std::fs::remove_dir_all(&tmp).unwrap();
+    }
+}
@@ -1,8 +1,14 @@
 mod commands;
 mod config;
+mod deps;
+mod forge;
 mod git;
+mod mux;
 mod onboarding;
+mod perf;
+mod scripts;
 mod tmux;
+mod zellij;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -20,6 +26,10 @@ struct Cli {
     /// Output status bar info for tmux (internal use)
     #[arg(long, hide = true)]
     status_bar: Option<String>,
+
+    /// Print each git/tmux command (with its working directory) to stderr before running it
+    #[arg(short, long, global = true)]
+    verbose: bool,
 }
 
 #[derive(Subcommand)]
@@ -38,8 +48,16 @@ enum Commands {
         branch: String,
 
         /// Base branch to create from (auto-detects: main, master, or develop)
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "from_current")]
         from: Option<String>,
+
+        /// Base the worktree on the current branch instead of the default branch
+        #[arg(long)]
+        from_current: bool,
+
+        /// Skip the post_create_hook from the config
+        #[arg(long)]
+        no_hook: bool,
     },
 
     /// Interactive worktree selector (fzf)
@@ -56,9 +74,13 @@ enum Commands {
         /// Branch name or path of the worktree to delete
         target: String,
 
-        /// Force delete even with uncommitted changes
+        /// Force delete even with uncommitted changes (and skip the --remote prompt)
         #[arg(short, long)]
         force: bool,
+
+        /// Also delete the branch on its remote (asks first unless --force)
+        #[arg(long)]
+        remote: bool,
     },
 
     /// Reload tmux session for a worktree (recreates with current config)
@@ -87,7 +109,11 @@ enum Commands {
     },
 
     /// Show status dashboard with worktrees and sessions
-    Status,
+    Status {
+        /// Print worktrees and orphans as JSON instead of opening the dashboard
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Configure workspace settings
     Config {
@@ -113,6 +139,14 @@ enum Commands {
     Clone {
         /// Repository URL to clone
         url: String,
+
+        /// Shallow clone with this many commits of history
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        depth: Option<u32>,
+
+        /// Clone bare into .bare/ and check out the default branch as the main/ worktree
+        #[arg(long)]
+        bare: bool,
     },
 
     /// Create a pull request from current worktree
@@ -123,8 +157,8 @@ enum Commands {
 
     /// Review a pull request in a new worktree
     Review {
-        /// PR number to review
-        number: u32,
+        /// PR to review: a number, #number, or PR URL
+        pr: String,
     },
 
     /// Garbage collect merged branches and their worktrees
@@ -132,11 +166,26 @@ enum Commands {
         /// Force delete without confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// List what would be deleted and exit without touching anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Keep branches in this group (repeatable)
+        #[arg(long = "preserve-group", value_name = "GROUP")]
+        preserve_group: Vec<String>,
     },
 
     /// Update ws and texplore via Homebrew
     Update,
 
+    /// Advanced worktree operations
+    #[command(alias = "wt")]
+    Worktree {
+        #[command(subcommand)]
+        action: WorktreeCommands,
+    },
+
     /// Toggle tmux layout based on display size
     #[command(alias = "l")]
     Layout {
@@ -155,17 +204,300 @@ enum PrCommands {
     List,
 }
 
+#[derive(Subcommand)]
+enum WorktreeCommands {
+    /// Reorder, squash, edit, or drop commits in a TUI
+    InteractiveRebase {
+        /// Branch name, path, or worktree directory name
+        branch: String,
+
+        /// Base to rebase onto (defaults to origin/<default branch>)
+        #[arg(long)]
+        onto: Option<String>,
+    },
+
+    /// Write a handoff note and post it on the branch's PR
+    Handoff {
+        /// Branch name, path, or worktree directory name
+        branch: String,
+
+        /// GitHub username taking over the branch
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Summarize recent work across worktrees for a standup
+    Standup {
+        /// Start of the period (any git date, e.g. "yesterday", "2 days ago")
+        #[arg(long, default_value = "yesterday")]
+        since: String,
+
+        /// Output format
+        #[arg(long, default_value = "plain", value_parser = ["plain", "slack", "notion"])]
+        format: String,
+
+        /// Copy the summary to the clipboard
+        #[arg(long)]
+        clipboard: bool,
+    },
+
+    /// Run the project's linters (clippy, eslint, ruff) in a worktree
+    Lint {
+        /// Branch name, path, or worktree directory name (defaults to current)
+        branch: Option<String>,
+
+        /// Apply automatic fixes
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Show tmux session startup times (P50/P90/P99 over recent sessions)
+    Performance {
+        #[command(subcommand)]
+        action: Option<PerformanceCommands>,
+    },
+
+    /// Copy uncommitted changes from another worktree into the current one
+    CopyChanges {
+        /// Source branch name, path, or worktree directory name
+        source: String,
+
+        /// Only copy files matching this pathspec glob (e.g. "*.rs")
+        #[arg(long)]
+        files: Option<String>,
+    },
+
+    /// Restore executable bits to the modes committed in HEAD
+    FixPermissions {
+        /// Branch name, path, or worktree directory name (defaults to current)
+        branch: Option<String>,
+
+        /// Only show which files would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Prepare a release branch: bump versions, update CHANGELOG.md, push, open a PR
+    CreateRelease {
+        /// Version to release (e.g. 1.2.3)
+        version: String,
+
+        /// Preview the changes without creating anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Generate a PR description for a branch with the configured AI tool
+    Summary {
+        /// Branch name or worktree path
+        branch: String,
+
+        /// AI tool to use instead of the configured one
+        #[arg(long)]
+        ai_tool: Option<String>,
+
+        /// Open a PR with the summary as its body (gh pr create)
+        #[arg(long)]
+        create_pr: bool,
+    },
+
+    /// Show every commit that changed a line (git log -L)
+    BlameHistory {
+        /// File, optionally with a line (src/main.rs:42)
+        file: String,
+
+        /// Line number (if not given as file:line)
+        line: Option<u32>,
+    },
+
+    /// Move a worktree to a fork (push, relocate, reopen the session)
+    Migrate {
+        /// Branch name or worktree path
+        branch: String,
+
+        /// Fork repository URL
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Run the pre-merge checklist from .ws/scripts.toml
+    Checklist {
+        /// Branch name or worktree path (defaults to the current worktree)
+        branch: Option<String>,
+
+        /// Run checks in their auto-fix mode where possible
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Create tmux sessions for all worktrees and open a session switcher
+    OpenAll {
+        /// Skip the main worktree
+        #[arg(long)]
+        exclude_main: bool,
+
+        /// Switch to this branch's session once all sessions exist
+        #[arg(long)]
+        attach_to: Option<String>,
+    },
+
+    /// Show local changes that haven't been pushed to origin
+    RemoteDiff {
+        /// Branch or worktree name
+        branch: String,
+
+        /// List unpushed commits instead of changed files
+        #[arg(long)]
+        commits: bool,
+    },
+
+    /// Open a branch as it was at a date (detached, read-only worktree)
+    TimeTravel {
+        /// Branch name
+        branch: String,
+
+        /// Date understood by git, e.g. 2024-01-15
+        date: String,
+    },
+
+    /// Browse the git log of every worktree in a TUI
+    GitLogTui,
+
+    /// Copy a worktree, including uncommitted changes, onto a new branch
+    Duplicate {
+        /// Branch or worktree to copy
+        source: String,
+
+        /// Name of the new branch
+        new_branch: String,
+    },
+
+    /// Define a named group of branches (stored in ~/.ws/config.toml)
+    #[command(args_conflicts_with_subcommands = true)]
+    Group {
+        #[command(subcommand)]
+        action: Option<GroupCommands>,
+
+        /// Group name (`ws open <name>` picks a branch from it)
+        #[arg(requires = "branches")]
+        name: Option<String>,
+
+        /// Branches in the group
+        branches: Vec<String>,
+    },
+
+    /// Run a shell command in every worktree
+    Foreach {
+        /// Command to run (through `sh -c`)
+        command: String,
+
+        /// Only run in worktrees of this group
+        #[arg(long)]
+        group: Option<String>,
+    },
+
+    /// Create a short alias for a branch (stored in ~/.ws/config.toml)
+    #[command(args_conflicts_with_subcommands = true)]
+    Alias {
+        #[command(subcommand)]
+        action: Option<AliasCommands>,
+
+        /// Short name
+        #[arg(requires = "branch")]
+        short: Option<String>,
+
+        /// Branch the alias points to
+        branch: Option<String>,
+    },
+
+    /// Declare that a branch depends on another (stacked branches)
+    #[command(args_conflicts_with_subcommands = true)]
+    Depend {
+        #[command(subcommand)]
+        action: Option<DependCommands>,
+
+        /// Branch that depends on another
+        #[arg(requires = "on")]
+        branch: Option<String>,
+
+        /// Branch it depends on
+        #[arg(long)]
+        on: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PerformanceCommands {
+    /// Record a startup milestone for the current session (called from shell hooks)
+    #[command(hide = true)]
+    Mark {
+        /// Milestone to record: first-key or tool
+        #[arg(value_parser = ["first-key", "tool"])]
+        metric: String,
+
+        /// Session name (defaults to the current tmux session)
+        #[arg(long)]
+        session: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GroupCommands {
+    /// Show all groups
+    #[command(alias = "ls")]
+    List,
+
+    /// Remove a group
+    #[command(alias = "rm")]
+    Remove {
+        /// Group name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AliasCommands {
+    /// Show all aliases
+    #[command(alias = "ls")]
+    List,
+
+    /// Remove an alias
+    #[command(alias = "rm")]
+    Remove {
+        /// Short name
+        short: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DependCommands {
+    /// Show the dependency graph
+    #[command(alias = "ls")]
+    List,
+
+    /// Remove a dependency
+    #[command(alias = "rm")]
+    Remove {
+        /// Branch that depends on another
+        branch: String,
+
+        /// Branch it depends on
+        #[arg(long)]
+        on: String,
+    },
+}
+
 fn handle_status_action(action: StatusAction) -> Result<()> {
     match action {
         StatusAction::None => Ok(()),
         StatusAction::Open(path) => commands::open(Some(path.to_string_lossy().to_string())),
         StatusAction::Ai => commands::ai(None),
-        StatusAction::ReviewPr(number) => commands::review(number),
+        StatusAction::ReviewPr(number) => commands::review(&number.to_string()),
     }
 }
 
 /// Print status bar info for tmux (called via #(ws --status-bar "dir"))
-/// Uses caching to avoid calling gh on every tmux refresh
+/// Uses caching to avoid calling gh/glab on every tmux refresh
 fn print_status_bar(dir: &str) {
     use std::fs;
     use std::path::Path;
@@ -173,8 +505,9 @@ fn print_status_bar(dir: &str) {
 
     let dir_path = Path::new(dir);
 
-    // Get branch name (fast, no caching needed)
-    let branch = git::get_branch(dir_path).unwrap_or_default();
+    // Branch, ahead/behind and dirty state in one git call (fast, no caching needed)
+    let summary = git::status_summary(dir_path).unwrap_or_default();
+    let branch = summary.branch.clone();
     if branch.is_empty() {
         return;
     }
@@ -216,43 +549,39 @@ fn print_status_bar(dir: &str) {
         info
     });
 
-    // Format: "branch | #123 Title ✓" or just "branch" if no PR
+    // Format: "branch* ↑1↓2 | #123 Title ✓" or just "branch" if clean, in sync and no PR
+    let mut label = branch;
+    if summary.dirty {
+        label.push('*');
+    }
+    if summary.ahead > 0 || summary.behind > 0 {
+        label.push(' ');
+        if summary.ahead > 0 {
+            label.push_str(&format!("↑{}", summary.ahead));
+        }
+        if summary.behind > 0 {
+            label.push_str(&format!("↓{}", summary.behind));
+        }
+    }
+
     if pr_info.is_empty() {
-        print!("{}", branch);
+        print!("{}", label);
     } else {
-        print!("{} │ {}", branch, pr_info);
+        print!("{} │ {}", label, pr_info);
     }
 }
 
 /// Fetch PR info for a branch (number, title, check status)
 fn fetch_pr_info_for_branch(dir: &std::path::Path, branch: &str) -> String {
-    // Skip if gh is not installed
-    if which::which("gh").is_err() {
+    // Skip if the forge CLI (gh or glab) is not installed
+    let forge = forge::current();
+    if !forge::is_installed(forge) {
         return String::new();
     }
 
-    // Query gh for PR on this branch
-    let output = std::process::Command::new("gh")
-        .current_dir(dir)
-        .args([
-            "pr",
-            "list",
-            "--head",
-            branch,
-            "--json",
-            "number,title,statusCheckRollup",
-            "--limit",
-            "1",
-        ])
-        .output();
-
-    let output = match output {
-        Ok(o) if o.status.success() => o,
-        _ => return String::new(),
-    };
-
-    let prs: Vec<serde_json::Value> = match serde_json::from_slice(&output.stdout) {
-        Ok(v) => v,
+    // Query the forge for a PR on this branch
+    let prs = match forge::list(forge, dir, Some(branch), 1) {
+        Ok(prs) => prs,
         Err(_) => return String::new(),
     };
 
@@ -261,11 +590,11 @@ fn fetch_pr_info_for_branch(dir: &std::path::Path, branch: &str) -> String {
         None => return String::new(),
     };
 
-    let number = pr["number"].as_u64().unwrap_or(0);
-    let title = pr["title"].as_str().unwrap_or("");
+    let number = pr.number;
+    let title = pr.title.as_str();
 
     // Parse check status
-    let check_icon = parse_check_status_icon(&pr["statusCheckRollup"]);
+    let check_icon = parse_check_status_icon(&pr.checks);
 
     // Truncate title if too long
     let title_short: String = title.chars().take(30).collect();
@@ -331,6 +660,7 @@ fn parse_check_status_icon(rollup: &serde_json::Value) -> &'static str {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    git::set_verbose(cli.verbose);
 
     // Handle --status-bar flag (for tmux status bar, needs to be fast)
     if let Some(dir) = cli.status_bar {
@@ -355,30 +685,137 @@ fn main() -> Result<()> {
 
     match cli.command {
         Some(Commands::Open { target }) => commands::open(target),
-        Some(Commands::New { branch, from }) => {
-            let base = from.unwrap_or_else(|| git::get_default_branch(None));
-            commands::new(&branch, &base)
+        Some(Commands::New {
+            branch,
+            from,
+            from_current,
+            no_hook,
+        }) => {
+            // --from, then --from-current, then the default branch
+            let base = match from {
+                Some(base) => base,
+                None if from_current => {
+                    let current = git::get_branch(&std::env::current_dir()?)?;
+                    if current == "detached" {
+                        anyhow::bail!(
+                            "--from-current needs a checked-out branch (HEAD is detached)"
+                        );
+                    }
+                    current
+                }
+                None => git::get_default_branch(None),
+            };
+            commands::new(&branch, &base, !no_hook)
         }
 
         Some(Commands::Select { path }) => commands::select(path),
-        Some(Commands::Delete { target, force }) => commands::delete(&target, force),
+        Some(Commands::Delete {
+            target,
+            force,
+            remote,
+        }) => commands::delete(&target, force, remote),
         Some(Commands::Reload { target }) => commands::reload(target),
         Some(Commands::Sync { create, delete }) => commands::sync(create, delete),
         Some(Commands::Doctor { install }) => commands::doctor(install),
-        Some(Commands::Status) => handle_status_action(commands::status()?),
+        Some(Commands::Status { json }) => handle_status_action(commands::status(json)?),
         Some(Commands::Config { key, value }) => commands::config(key, value),
         Some(Commands::Init) => commands::init(),
         Some(Commands::Ai { tool }) => commands::ai(tool),
 
-        Some(Commands::Clone { url }) => commands::clone_repo(&url),
+        Some(Commands::Clone { url, depth, bare }) => commands::clone_repo(&url, depth, bare),
         Some(Commands::Pr { action }) => match action {
             Some(PrCommands::List) => commands::pr_list(),
             None => commands::pr_create(),
         },
-        Some(Commands::Review { number }) => commands::review(number),
-        Some(Commands::Gc { force }) => commands::gc(force),
+        Some(Commands::Review { pr }) => commands::review(&pr),
+        Some(Commands::Gc {
+            force,
+            dry_run,
+            preserve_group,
+        }) => commands::gc(force, dry_run, &preserve_group),
         Some(Commands::Update) => commands::update(),
         Some(Commands::Layout { expand, shrink }) => commands::layout(expand, shrink),
+        Some(Commands::Worktree { action }) => match action {
+            WorktreeCommands::InteractiveRebase { branch, onto } => {
+                commands::interactive_rebase(&branch, onto.as_deref())
+            }
+            WorktreeCommands::Handoff { branch, to } => commands::handoff(&branch, &to),
+            WorktreeCommands::Standup {
+                since,
+                format,
+                clipboard,
+            } => commands::standup(&since, &format, clipboard),
+            WorktreeCommands::Lint { branch, fix } => commands::lint(branch.as_deref(), fix),
+            WorktreeCommands::Performance { action } => match action {
+                Some(PerformanceCommands::Mark { metric, session }) => {
+                    commands::performance_mark(&metric, session.as_deref())
+                }
+                None => commands::performance(),
+            },
+            WorktreeCommands::CopyChanges { source, files } => {
+                commands::copy_changes(&source, files.as_deref())
+            }
+            WorktreeCommands::FixPermissions { branch, dry_run } => {
+                commands::fix_permissions(branch.as_deref(), dry_run)
+            }
+            WorktreeCommands::CreateRelease { version, dry_run } => {
+                commands::create_release(&version, dry_run)
+            }
+            WorktreeCommands::Summary {
+                branch,
+                ai_tool,
+                create_pr,
+            } => commands::summary(&branch, ai_tool.as_deref(), create_pr),
+            WorktreeCommands::BlameHistory { file, line } => commands::blame_history(&file, line),
+            WorktreeCommands::Migrate { branch, to } => commands::migrate(&branch, &to),
+            WorktreeCommands::Checklist { branch, fix } => {
+                commands::checklist(branch.as_deref(), fix)
+            }
+            WorktreeCommands::OpenAll {
+                exclude_main,
+                attach_to,
+            } => commands::open_all(exclude_main, attach_to.as_deref()),
+            WorktreeCommands::RemoteDiff { branch, commits } => {
+                commands::remote_diff(&branch, commits)
+            }
+            WorktreeCommands::TimeTravel { branch, date } => commands::time_travel(&branch, &date),
+            WorktreeCommands::GitLogTui => commands::git_log_tui(),
+            WorktreeCommands::Duplicate { source, new_branch } => {
+                commands::duplicate(&source, &new_branch)
+            }
+            WorktreeCommands::Group {
+                action,
+                name,
+                branches,
+            } => match (action, name) {
+                (Some(GroupCommands::List), _) => commands::group_list(),
+                (Some(GroupCommands::Remove { name }), _) => commands::group_remove(&name),
+                (None, Some(name)) => commands::group_set(&name, &branches),
+                (None, None) => commands::group_list(),
+            },
+            WorktreeCommands::Foreach { command, group } => {
+                commands::foreach(&command, group.as_deref())
+            }
+            WorktreeCommands::Alias {
+                action,
+                short,
+                branch,
+            } => match (action, short, branch) {
+                (Some(AliasCommands::List), _, _) => commands::alias_list(),
+                (Some(AliasCommands::Remove { short }), _, _) => commands::alias_remove(&short),
+                (None, Some(short), Some(branch)) => commands::alias_add(&short, &branch),
+                _ => commands::alias_list(),
+            },
+            WorktreeCommands::Depend { action, branch, on } => match (action, branch, on) {
+                (Some(DependCommands::List), _, _) => commands::depend_list(),
+                (Some(DependCommands::Remove { branch, on }), _, _) => {
+                    commands::depend_remove(&branch, &on)
+                }
+                (None, Some(branch), Some(on)) => commands::depend_add(&branch, &on),
+                (None, None, Some(_)) => anyhow::bail!("Specify the branch that depends on --on"),
+                (None, _, None) => commands::depend_list(),
+            },
+        },
         None => {
             // Check if config exists AND we're in a git repo - if so, show dashboard
             let config_path = crate::config::Config::path()?;
@@ -390,11 +827,11 @@ fn main() -> Result<()> {
             } else {
                 // No config or not in git repo - run onboarding
                 if let Some(result) = onboarding::run_onboarding()? {
-                    let config = crate::config::Config {
-                        ai_tool: result.ai_tool,
-                        git_tool: result.git_tool,
-                        explorer_tool: result.explorer_tool,
-                    };
+                    let config = crate::config::Config::with_wizard_choices(
+                        result.ai_tool,
+                        result.git_tool,
+                        result.explorer_tool,
+                    );
                     config.save()?;
                     if let Some(path) = result.path {
                         commands::open(Some(path.to_string_lossy().to_string()))
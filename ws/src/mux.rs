@@ -0,0 +1,101 @@
+use crate::config::{Config, MultiplexerTool};
+use crate::{tmux, zellij};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Session operations shared by the supported terminal multiplexers
+pub trait Multiplexer {
+    fn session_exists(&self, name: &str) -> bool;
+    fn create_session_with_title(
+        &self,
+        session: &str,
+        dir: &Path,
+        window_title: &str,
+    ) -> Result<()>;
+    fn attach(&self, session: &str) -> Result<()>;
+    fn kill_session(&self, session: &str) -> Result<()>;
+    fn get_active_sessions(&self) -> HashSet<String>;
+    /// The session ws is running inside, if any
+    fn current_session(&self) -> Option<String>;
+}
+
+pub struct Tmux;
+pub struct Zellij;
+
+impl Multiplexer for Tmux {
+    fn session_exists(&self, name: &str) -> bool {
+        tmux::session_exists(name)
+    }
+
+    fn create_session_with_title(
+        &self,
+        session: &str,
+        dir: &Path,
+        window_title: &str,
+    ) -> Result<()> {
+        tmux::create_session_with_title(session, dir, window_title)
+    }
+
+    fn attach(&self, session: &str) -> Result<()> {
+        tmux::attach(session)
+    }
+
+    fn kill_session(&self, session: &str) -> Result<()> {
+        tmux::kill_session(session)
+    }
+
+    fn get_active_sessions(&self) -> HashSet<String> {
+        tmux::get_active_sessions()
+    }
+
+    fn current_session(&self) -> Option<String> {
+        tmux::get_current_session()
+    }
+}
+
+impl Multiplexer for Zellij {
+    fn session_exists(&self, name: &str) -> bool {
+        zellij::session_exists(name)
+    }
+
+    fn create_session_with_title(
+        &self,
+        session: &str,
+        dir: &Path,
+        window_title: &str,
+    ) -> Result<()> {
+        zellij::create_session_with_title(session, dir, window_title)
+    }
+
+    fn attach(&self, session: &str) -> Result<()> {
+        zellij::attach(session)
+    }
+
+    fn kill_session(&self, session: &str) -> Result<()> {
+        zellij::kill_session(session)
+    }
+
+    fn get_active_sessions(&self) -> HashSet<String> {
+        zellij::get_active_sessions()
+    }
+
+    fn current_session(&self) -> Option<String> {
+        zellij::get_current_session()
+    }
+}
+
+/// The multiplexer selected by `multiplexer` in the config (tmux by default)
+pub fn tool() -> MultiplexerTool {
+    Config::load()
+        .map(|c| c.multiplexer)
+        .unwrap_or(MultiplexerTool::Tmux)
+}
+
+/// Session operations for the configured multiplexer
+pub fn backend() -> &'static dyn Multiplexer {
+    match tool() {
+        MultiplexerTool::Zellij => &Zellij,
+        MultiplexerTool::Tmux => &Tmux,
+    }
+}
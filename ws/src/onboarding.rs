@@ -447,26 +447,18 @@ impl OnboardingApp {
             Screen::SelectPath => match key {
                 KeyCode::Enter => self.confirm_path(),
                 KeyCode::Esc => self.go_back(),
-                KeyCode::Backspace => {
-                    if self.cursor_position > 0 {
-                        self.path_input.remove(self.cursor_position - 1);
-                        self.cursor_position -= 1;
-                    }
+                KeyCode::Backspace if self.cursor_position > 0 => {
+                    self.path_input.remove(self.cursor_position - 1);
+                    self.cursor_position -= 1;
                 }
-                KeyCode::Delete => {
-                    if self.cursor_position < self.path_input.len() {
-                        self.path_input.remove(self.cursor_position);
-                    }
+                KeyCode::Delete if self.cursor_position < self.path_input.len() => {
+                    self.path_input.remove(self.cursor_position);
                 }
-                KeyCode::Left => {
-                    if self.cursor_position > 0 {
-                        self.cursor_position -= 1;
-                    }
+                KeyCode::Left if self.cursor_position > 0 => {
+                    self.cursor_position -= 1;
                 }
-                KeyCode::Right => {
-                    if self.cursor_position < self.path_input.len() {
-                        self.cursor_position += 1;
-                    }
+                KeyCode::Right if self.cursor_position < self.path_input.len() => {
+                    self.cursor_position += 1;
                 }
                 KeyCode::Home => {
                     self.cursor_position = 0;
@@ -895,11 +887,8 @@ pub fn check_and_run_onboarding() -> Result<Option<PathBuf>> {
         let git_name = result.git_tool.name().to_string();
         let explorer_name = result.explorer_tool.name().to_string();
 
-        let config = Config {
-            ai_tool: result.ai_tool,
-            git_tool: result.git_tool,
-            explorer_tool: result.explorer_tool,
-        };
+        let config =
+            Config::with_wizard_choices(result.ai_tool, result.git_tool, result.explorer_tool);
         config.save()?;
 
         println!();
@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of sessions kept in ~/.ws/perf.toml
+const MAX_SESSIONS: usize = 30;
+
+/// Startup timings for one tmux session (all durations in milliseconds)
+#[derive(Debug, Clone, Default)]
+pub struct SessionTiming {
+    pub name: String,
+    /// Unix time in milliseconds when creation started
+    pub created_at: u64,
+    /// Time taken by `tmux new-session` plus layout setup
+    pub create_ms: u64,
+    /// Time until the first command in the session (reported by a shell hook)
+    pub first_key_ms: Option<u64>,
+    /// Time until the AI/git tool started (reported by a hook)
+    pub tool_ms: Option<u64>,
+}
+
+/// Session startup log (~/.ws/perf.toml)
+#[derive(Debug, Default)]
+pub struct PerfLog {
+    pub sessions: Vec<SessionTiming>,
+}
+
+/// Current unix time in milliseconds
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl PerfLog {
+    /// Get the perf log path
+    pub fn path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Could not determine home directory")?;
+        Ok(home.join(".ws").join("perf.toml"))
+    }
+
+    /// Load the perf log, or return an empty one
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        let mut sessions: Vec<SessionTiming> = Vec::new();
+
+        if path.exists() {
+            let content = fs::read_to_string(&path).context("Failed to read perf log")?;
+
+            for line in content.lines() {
+                let line = line.trim();
+                if line == "[[session]]" {
+                    sessions.push(SessionTiming::default());
+                    continue;
+                }
+                if line.starts_with('#') || line.is_empty() {
+                    continue;
+                }
+
+                if let (Some(session), Some((key, value))) =
+                    (sessions.last_mut(), line.split_once('='))
+                {
+                    let value = value.trim().trim_matches('"');
+                    match key.trim() {
+                        "name" => session.name = value.to_string(),
+                        "created_at" => session.created_at = value.parse().unwrap_or(0),
+                        "create_ms" => session.create_ms = value.parse().unwrap_or(0),
+                        "first_key_ms" => session.first_key_ms = value.parse().ok(),
+                        "tool_ms" => session.tool_ms = value.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(Self { sessions })
+    }
+
+    /// Save the perf log, keeping only the most recent sessions
+    pub fn save(&mut self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create perf log directory")?;
+        }
+
+        if self.sessions.len() > MAX_SESSIONS {
+            self.sessions.drain(..self.sessions.len() - MAX_SESSIONS);
+        }
+
+        let mut content =
+            String::from("# Session startup timings in milliseconds (most recent last)\n");
+        for session in &self.sessions {
+            content.push_str("\n[[session]]\n");
+            content.push_str(&format!("name = \"{}\"\n", session.name));
+            content.push_str(&format!("created_at = {}\n", session.created_at));
+            content.push_str(&format!("create_ms = {}\n", session.create_ms));
+            if let Some(ms) = session.first_key_ms {
+                content.push_str(&format!("first_key_ms = {}\n", ms));
+            }
+            if let Some(ms) = session.tool_ms {
+                content.push_str(&format!("tool_ms = {}\n", ms));
+            }
+        }
+
+        fs::write(&path, content).context("Failed to write perf log")?;
+        Ok(())
+    }
+
+    /// Record a newly created session
+    pub fn record_created(session: &str, started_at: u64, create_ms: u64) -> Result<()> {
+        let mut log = Self::load()?;
+        log.sessions.push(SessionTiming {
+            name: session.to_string(),
+            created_at: started_at,
+            create_ms,
+            first_key_ms: None,
+            tool_ms: None,
+        });
+        log.save()
+    }
+
+    /// Record a startup milestone ("first-key" or "tool") for the latest run of a session.
+    /// Only the first report per session counts.
+    pub fn mark(session: &str, metric: &str) -> Result<bool> {
+        let mut log = Self::load()?;
+        let timing = match log.sessions.iter_mut().rev().find(|s| s.name == session) {
+            Some(timing) => timing,
+            None => return Ok(false),
+        };
+
+        let elapsed = now_ms().saturating_sub(timing.created_at);
+        let slot = match metric {
+            "first-key" => &mut timing.first_key_ms,
+            "tool" => &mut timing.tool_ms,
+            _ => anyhow::bail!("Unknown metric: {} (use first-key or tool)", metric),
+        };
+        if slot.is_some() {
+            return Ok(false);
+        }
+        *slot = Some(elapsed);
+
+        log.save()?;
+        Ok(true)
+    }
+}
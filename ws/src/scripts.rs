@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Repository scripts (.ws/scripts.toml, committed alongside the code)
+#[derive(Debug, Default)]
+pub struct Scripts {
+    /// Commands that must pass before a branch is merged ([checklist] pre_merge)
+    pub pre_merge: Vec<String>,
+}
+
+impl Scripts {
+    /// Get the scripts file path for a worktree
+    pub fn path(worktree: &Path) -> PathBuf {
+        worktree.join(".ws").join("scripts.toml")
+    }
+
+    /// Load scripts for a worktree, or return an empty set
+    pub fn load(worktree: &Path) -> Result<Self> {
+        let path = Self::path(worktree);
+        let mut scripts = Self::default();
+
+        if !path.exists() {
+            return Ok(scripts);
+        }
+
+        let content = fs::read_to_string(&path).context("Failed to read .ws/scripts.toml")?;
+        let mut section = String::new();
+        let mut lines = content.lines();
+
+        while let Some(line) = lines.next() {
+            let line = line.trim();
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                section = line
+                    .trim_matches(|c| c == '[' || c == ']')
+                    .trim()
+                    .to_string();
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                // Arrays may span several lines
+                let mut value = value.trim().to_string();
+                if value.starts_with('[') {
+                    while !value.ends_with(']') {
+                        match lines.next() {
+                            Some(next) => {
+                                let next = next.trim();
+                                if !next.starts_with('#') {
+                                    value.push_str(next);
+                                }
+                            }
+                            None => break,
+                        }
+                    }
+                }
+
+                if section == "checklist" && key.trim() == "pre_merge" {
+                    scripts.pre_merge = parse_string_array(&value);
+                }
+            }
+        }
+
+        Ok(scripts)
+    }
+}
+
+/// Parse `["a", "b"]` into its strings (commas inside quotes are kept)
+pub(crate) fn parse_string_array(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in value
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .chars()
+    {
+        match quote {
+            Some('"') if escaped => {
+                current.push(unescape(c));
+                escaped = false;
+            }
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => {
+                items.push(std::mem::take(&mut current));
+                quote = None;
+            }
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None => {}
+        }
+    }
+
+    items.into_iter().filter(|item| !item.is_empty()).collect()
+}
+
+/// Parse a `"quoted"` value, undoing backslash escapes (bare values are kept as-is)
+pub(crate) fn parse_string(value: &str) -> String {
+    let value = value.trim();
+    let inner = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => inner,
+        None => return value.trim_matches('"').to_string(),
+    };
+
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    result.push(unescape(next));
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// The character a backslash escape stands for
+fn unescape(c: char) -> char {
+    match c {
+        'n' => '\n',
+        't' => '\t',
+        _ => c,
+    }
+}
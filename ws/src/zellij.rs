@@ -0,0 +1,189 @@
+use crate::config::{Config, PaneLayout};
+use crate::git::RunLogged;
+use crate::perf::{self, PerfLog};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Check if a zellij session exists and is running
+pub fn session_exists(name: &str) -> bool {
+    get_active_sessions().contains(name)
+}
+
+/// Get list of running zellij session names (exited, resurrectable sessions are skipped)
+pub fn get_active_sessions() -> HashSet<String> {
+    let output = Command::new("zellij")
+        .args(["list-sessions", "--no-formatting"])
+        .logged()
+        .output();
+
+    match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .filter(|line| !line.contains("EXITED"))
+            .filter_map(|line| line.split_whitespace().next())
+            .map(|s| s.to_string())
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
+/// Check if we're running inside a zellij session
+pub fn is_inside_zellij() -> bool {
+    std::env::var("ZELLIJ").is_ok()
+}
+
+/// Name of the zellij session we're running in, if any
+pub fn get_current_session() -> Option<String> {
+    std::env::var("ZELLIJ_SESSION_NAME")
+        .ok()
+        .filter(|name| !name.is_empty())
+}
+
+/// Attach to an existing zellij session (replaces current process)
+pub fn attach(session: &str) -> Result<()> {
+    if is_inside_zellij() {
+        // zellij has no switch-client equivalent that works from a child process
+        anyhow::bail!(
+            "Already inside zellij; detach first (Ctrl-o d), then run: zellij attach {}",
+            session
+        );
+    }
+
+    let err = crate::tmux::exec::execvp("zellij", &["zellij", "attach", session]);
+    anyhow::bail!("Failed to attach to zellij: {}", err);
+}
+
+/// Kill a zellij session and forget it, so it isn't offered for resurrection
+pub fn kill_session(session: &str) -> Result<()> {
+    let result = Command::new("zellij")
+        .args(["delete-session", "--force", session])
+        .logged()
+        .output()
+        .context("Failed to kill zellij session")?;
+
+    if !result.status.success() {
+        anyhow::bail!("Failed to kill session: {}", session);
+    }
+
+    Ok(())
+}
+
+/// Create a new background zellij session with the same pane layout as tmux
+pub fn create_session_with_title(session: &str, dir: &Path, window_title: &str) -> Result<()> {
+    let dir_str = dir.to_str().context("Invalid path")?;
+    let started_at = perf::now_ms();
+
+    // Load config to get the pane layout and panel tools
+    let config = Config::load_for_repo(dir).unwrap_or_default();
+    let panes = layout_panes(
+        &config.pane_layout(crate::tmux::is_large_display()),
+        &config,
+    );
+    let layout = wrap_layout(window_title, dir_str, &panes);
+    let layout_path = layout_path(session)?;
+    fs::write(&layout_path, layout).context("Failed to write zellij layout")?;
+    let layout_str = layout_path.to_str().context("Invalid layout path")?;
+
+    let result = Command::new("zellij")
+        .args([
+            "attach",
+            "--create-background",
+            session,
+            "options",
+            "--default-layout",
+            layout_str,
+            "--default-cwd",
+            dir_str,
+        ])
+        .logged()
+        .output()
+        .context("Failed to create zellij session")?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        anyhow::bail!("Failed to create zellij session: {}", stderr.trim());
+    }
+
+    // Timing is best-effort, never fail session creation over it
+    let _ = PerfLog::record_created(session, started_at, perf::now_ms() - started_at);
+
+    Ok(())
+}
+
+/// Layout file for a session (~/.ws/zellij/<session>.kdl)
+fn layout_path(session: &str) -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+    let dir = home.join(".ws").join("zellij");
+    fs::create_dir_all(&dir).context("Failed to create zellij layout directory")?;
+    Ok(dir.join(format!("{}.kdl", session)))
+}
+
+/// KDL for the layout's columns and rows
+fn layout_panes(layout: &PaneLayout, config: &Config) -> String {
+    let focus = layout.focus_index();
+    let mut index = 0;
+    let mut columns = Vec::new();
+    for (width, panes) in layout.columns() {
+        let mut rows = Vec::new();
+        for pane in panes {
+            let (cmd, _) = config.pane_command(pane);
+            rows.push(command_pane(cmd, index == focus));
+            index += 1;
+        }
+        columns.push(format!(
+            "            pane size=\"{}%\" split_direction=\"horizontal\" {{\n{}\n            }}",
+            width,
+            rows.join("\n")
+        ));
+    }
+    format!(
+        "        pane split_direction=\"vertical\" {{\n{}\n        }}",
+        columns.join("\n")
+    )
+}
+
+/// Wrap the panes in a tab rooted at `dir`, with zellij's tab bar and status bar
+fn wrap_layout(title: &str, dir: &str, panes: &str) -> String {
+    format!(
+        r#"layout {{
+    cwd "{}"
+    default_tab_template {{
+        pane size=1 borderless=true {{
+            plugin location="zellij:tab-bar"
+        }}
+        children
+        pane size=2 borderless=true {{
+            plugin location="zellij:status-bar"
+        }}
+    }}
+    tab name="{}" {{
+{}
+    }}
+}}
+"#,
+        kdl_escape(dir),
+        kdl_escape(title),
+        panes
+    )
+}
+
+/// A pane running `cmd` through the shell, dropping back to the shell when it exits
+/// (like the tmux panes, where the command is typed into an interactive shell)
+fn command_pane(cmd: &str, focus: bool) -> String {
+    let focus = if focus { " focus=true" } else { "" };
+    let script = format!("{}; exec \"${{SHELL:-sh}}\"", cmd);
+    format!(
+        r#"                pane{} command="sh" {{
+                    args "-c" "{}"
+                }}"#,
+        focus,
+        kdl_escape(&script)
+    )
+}
+
+fn kdl_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}